@@ -0,0 +1,56 @@
+//! Prints the expected attacker win rates for each combat model as markdown tables.
+//!
+//! Run with `cargo run --example combat_odds` from program/hexone and paste the output
+//! into the "Combat odds" section of program/README.md.
+
+use hexone::combat::{
+    attacker_win_probability_ppm, CombatSettings, COMBAT_MODEL_DICE, COMBAT_MODEL_FLAT,
+    COMBAT_MODEL_RATIO, PROBABILITY_SCALE,
+};
+use hexone::constants::{MAX_COMBAT_DICE, RESOURCES_PER_COMBAT_DIE};
+
+// Resources on the attacking tile (one stays behind) and on the defending tile
+const ATTACKER_RESOURCES: [u16; 7] = [2, 6, 11, 21, 51, 101, 201];
+const DEFENDER_RESOURCES: [u16; 7] = [1, 5, 10, 20, 50, 100, 200];
+
+fn print_table(name: &str, combat_model: u8) {
+    let settings = CombatSettings {
+        combat_model,
+        resources_per_combat_die: RESOURCES_PER_COMBAT_DIE,
+        max_combat_dice: MAX_COMBAT_DICE,
+    };
+
+    println!("### {}\n", name);
+    print!("| Attacker \\ Defender |");
+    for defender in DEFENDER_RESOURCES {
+        print!(" {} |", defender);
+    }
+    println!();
+    print!("|---|");
+    for _ in DEFENDER_RESOURCES {
+        print!("---|");
+    }
+    println!();
+
+    for attacker in ATTACKER_RESOURCES {
+        print!("| {} |", attacker);
+        for defender in DEFENDER_RESOURCES {
+            let ppm = attacker_win_probability_ppm(&settings, attacker, defender);
+            print!(" {:.1}% |", ppm as f64 * 100.0 / PROBABILITY_SCALE as f64);
+        }
+        println!();
+    }
+    println!();
+}
+
+fn main() {
+    print_table("Flat (model 0)", COMBAT_MODEL_FLAT);
+    print_table("Ratio (model 1)", COMBAT_MODEL_RATIO);
+    print_table(
+        &format!(
+            "Dice (model 2, {} resources per die, max {} dice)",
+            RESOURCES_PER_COMBAT_DIE, MAX_COMBAT_DICE
+        ),
+        COMBAT_MODEL_DICE,
+    );
+}
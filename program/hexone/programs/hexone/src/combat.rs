@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+use crate::error::HexoneError;
+
+// Combat models a game can use to decide attacks (stored in Game::combat_model)
+pub const COMBAT_MODEL_FLAT: u8 = 0;  // Higher roll wins, resources only cap the hit (50/50)
+pub const COMBAT_MODEL_RATIO: u8 = 1; // Attacker wins with probability committed / (committed + defending)
+pub const COMBAT_MODEL_DICE: u8 = 2;  // Risk-style: each side rolls dice by resource count, best die wins
+
+// Win probabilities are expressed in parts per million
+pub const PROBABILITY_SCALE: u64 = 1_000_000;
+
+/// Combat settings copied out of the Game account
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CombatSettings {
+    pub combat_model: u8,
+    pub resources_per_combat_die: u16, // Dice model: one extra die per this many resources
+    pub max_combat_dice: u8,           // Dice model: cap on dice per side
+}
+
+impl CombatSettings {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.combat_model <= COMBAT_MODEL_DICE,
+            HexoneError::InvalidCombatSettings
+        );
+        require!(
+            self.resources_per_combat_die > 0 && self.max_combat_dice > 0,
+            HexoneError::InvalidCombatSettings
+        );
+        Ok(())
+    }
+}
+
+/// Resources the attacker actually commits (one always stays behind on the attacking tile)
+pub fn committed_attack_resources(attacker_resources: u16) -> u16 {
+    attacker_resources.saturating_sub(1)
+}
+
+/// Number of dice a side rolls in the dice model: 1 die, plus one per resources_per_combat_die
+/// beyond the first resource, capped at max_combat_dice
+pub fn combat_dice(resources: u16, settings: &CombatSettings) -> u64 {
+    let extra_dice = (resources.saturating_sub(1) / settings.resources_per_combat_die.max(1)) as u64;
+    (1 + extra_dice).min(settings.max_combat_dice.max(1) as u64)
+}
+
+/// Probability (parts per million) that the attacker wins a single attack
+/// The flat model keeps the original "higher roll wins, ties go to the defender" odds
+pub fn attacker_win_probability_ppm(
+    settings: &CombatSettings,
+    attacker_resources: u16,
+    defender_resources: u16,
+) -> u64 {
+    let committed = committed_attack_resources(attacker_resources) as u64;
+    let defending = defender_resources as u64;
+
+    match settings.combat_model {
+        COMBAT_MODEL_RATIO => (committed * PROBABILITY_SCALE)
+            .checked_div(committed + defending)
+            .unwrap_or(PROBABILITY_SCALE / 2),
+        COMBAT_MODEL_DICE => {
+            // Best of m uniform dice beats best of n with probability m / (m + n)
+            let attacker_dice = combat_dice(committed as u16, settings);
+            let defender_dice = combat_dice(defender_resources, settings);
+            attacker_dice * PROBABILITY_SCALE / (attacker_dice + defender_dice)
+        }
+        // 499,500 of the 1,000,000 roll pairs have attacker > defender
+        _ => 499_500,
+    }
+}
+
+/// Decide an attack from the two rolls (0-999 each)
+/// Returns (attacker_won, difference) where difference (0-999) is how decisive the win was
+/// and drives the hit size
+pub fn decide_attack(
+    settings: &CombatSettings,
+    attacker_resources: u16,
+    defender_resources: u16,
+    attacker_roll: u16,
+    defender_roll: u16,
+) -> (bool, u64) {
+    let attacker_roll = attacker_roll as u64;
+    let defender_roll = defender_roll as u64;
+
+    if settings.combat_model == COMBAT_MODEL_FLAT {
+        // Higher number wins
+        let attacker_won = attacker_roll > defender_roll;
+        let difference = if attacker_won {
+            attacker_roll - defender_roll
+        } else {
+            defender_roll - attacker_roll
+        };
+        return (attacker_won, difference);
+    }
+
+    // Weighted models: combine both rolls into one uniform draw in 0..1,000,000
+    // and compare it against the attacker's win probability
+    let draw = attacker_roll * 1000 + defender_roll;
+    let threshold = attacker_win_probability_ppm(settings, attacker_resources, defender_resources)
+        .min(PROBABILITY_SCALE);
+
+    if draw < threshold {
+        // Attacker wins; the further below the threshold, the more decisive
+        (true, (threshold - 1 - draw) * 1000 / threshold)
+    } else {
+        (false, (draw - threshold) * 1000 / (PROBABILITY_SCALE - threshold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(combat_model: u8) -> CombatSettings {
+        CombatSettings {
+            combat_model,
+            resources_per_combat_die: 10,
+            max_combat_dice: 5,
+        }
+    }
+
+    // Fraction of all 1,000,000 roll pairs the attacker wins, in ppm
+    fn empirical_win_rate_ppm(settings: &CombatSettings, attacker: u16, defender: u16) -> u64 {
+        let mut wins = 0u64;
+        for attacker_roll in 0..1000u16 {
+            for defender_roll in 0..1000u16 {
+                if decide_attack(settings, attacker, defender, attacker_roll, defender_roll).0 {
+                    wins += 1;
+                }
+            }
+        }
+        wins
+    }
+
+    #[test]
+    fn flat_model_ignores_resources() {
+        let flat = settings(COMBAT_MODEL_FLAT);
+        assert_eq!(attacker_win_probability_ppm(&flat, 200, 2), 499_500);
+        assert_eq!(empirical_win_rate_ppm(&flat, 200, 2), 499_500);
+        assert_eq!(decide_attack(&flat, 200, 2, 700, 200), (true, 500));
+        assert_eq!(decide_attack(&flat, 200, 2, 300, 300), (false, 0));
+    }
+
+    #[test]
+    fn ratio_model_matches_its_probability() {
+        let ratio = settings(COMBAT_MODEL_RATIO);
+        // 199 committed vs 2 defending
+        assert_eq!(attacker_win_probability_ppm(&ratio, 200, 2), 199 * 1_000_000 / 201);
+        assert_eq!(attacker_win_probability_ppm(&ratio, 11, 10), 500_000);
+        assert_eq!(
+            empirical_win_rate_ppm(&ratio, 200, 2),
+            attacker_win_probability_ppm(&ratio, 200, 2)
+        );
+    }
+
+    #[test]
+    fn dice_model_counts_dice_by_resources() {
+        let dice = settings(COMBAT_MODEL_DICE);
+        assert_eq!(combat_dice(1, &dice), 1);
+        assert_eq!(combat_dice(10, &dice), 1);
+        assert_eq!(combat_dice(11, &dice), 2);
+        assert_eq!(combat_dice(500, &dice), 5);
+
+        // 199 committed (5 dice) vs 2 (1 die) wins 5 / 6 of the time
+        assert_eq!(attacker_win_probability_ppm(&dice, 200, 2), 833_333);
+        assert_eq!(
+            empirical_win_rate_ppm(&dice, 200, 2),
+            attacker_win_probability_ppm(&dice, 200, 2)
+        );
+    }
+
+    #[test]
+    fn difference_stays_in_roll_range() {
+        let ratio = settings(COMBAT_MODEL_RATIO);
+        for (attacker_roll, defender_roll) in [(0, 0), (999, 999), (0, 999), (999, 0), (500, 500)] {
+            let (_, difference) = decide_attack(&ratio, 50, 30, attacker_roll, defender_roll);
+            assert!(difference <= 999);
+        }
+    }
+
+    #[test]
+    fn settings_are_validated() {
+        assert!(settings(COMBAT_MODEL_DICE).validate().is_ok());
+        assert!(settings(3).validate().is_err());
+        let mut no_dice = settings(COMBAT_MODEL_DICE);
+        no_dice.max_combat_dice = 0;
+        assert!(no_dice.validate().is_err());
+    }
+}
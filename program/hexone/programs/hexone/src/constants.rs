@@ -7,6 +7,50 @@ pub const DEFAULT_GAME_COST: u64 = 100_000_000; // 0.1 SOL in lamports
 pub const RESOURCES_PER_MINUTE: u32 = 10;
 pub const XP_PER_MINUTE_PER_TILE: u32 = 1;
 
+// Players per game: 1v1 duels up to 6-player free-for-alls
+pub const MIN_PLAYERS: u8 = 2;
+pub const MAX_PLAYERS: usize = 6;
+pub const DEFAULT_MAX_PLAYERS: u8 = 4;
+// Games that haven't filled within this long can be cancelled by anyone (24 hours)
+pub const DEFAULT_JOIN_WINDOW_SECONDS: u32 = 24 * 60 * 60;
+
+// Shortest reinforcement window a game can set (3 seconds)
+// The defender can reinforce the attacked tile during the window, and the attack resolves once it closes
+pub const MIN_ATTACK_DURATION: i64 = 3;
+
+// tick_game only runs (and pays its crank reward) once a game has gone this long without accruing
+pub const MIN_TICK_INTERVAL_SECONDS: i64 = 60;
+// Crank rewards paid over a game's life are capped at this share of its entry fees, in basis points
+pub const MAX_CRANK_REWARDS_BPS: u64 = 100; // 1%
+
+// Coordinated attacks: at most one contributing tile per hex neighbour of the target
+pub const MAX_ATTACK_CONTRIBUTORS: usize = 6;
+// Coordinated attack allocations are expressed in basis points of the surviving resources
+pub const ALLOCATION_BPS_DENOMINATOR: u16 = 10_000;
+
+// Payout tables split the prize pool by finishing place, in basis points
+pub const PAYOUT_BPS_DENOMINATOR: u16 = 10_000;
+// Places have this long after the winner is determined to claim their payout (7 days)
+pub const PAYOUT_CLAIM_WINDOW_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+// Platform fee taken from each prize pool before it's split, in basis points
+pub const DEFAULT_PLATFORM_FEE_BPS: u16 = 0;
+pub const MAX_PLATFORM_FEE_BPS: u16 = 2_000; // 20%
+
+// Commit-reveal attacks: both sides must commit and reveal within this many seconds of the attack
+pub const COMMIT_REVEAL_DEADLINE_SECONDS: i64 = 60;
+
+// Unresolved attacks can be closed by anyone this many seconds after they started
+// Kept inside the SlotHashes horizon (see randomness.rs), so an attack whose blocks have rolled
+// out of the sysvar, and can no longer be resolved, can always be expired
+pub const ATTACK_EXPIRY_SECONDS: i64 = 180;
+// Resources removed from the attacking tile when its attack expires (0 = no penalty)
+pub const EXPIRED_ATTACK_PENALTY: u8 = 5;
+
+// Dice combat model: one extra die per this many resources, up to the max per side
+pub const RESOURCES_PER_COMBAT_DIE: u16 = 10;
+pub const MAX_COMBAT_DICE: u8 = 5;
+
 // Tier bonus XP per minute constants
 pub const GOLD_TIER_BONUS_XP_PER_MIN: u8 = 100;
 pub const SILVER_TIER_BONUS_XP_PER_MIN: u8 = 50;
@@ -33,4 +33,140 @@ pub enum HexoneError {
     /// 6007 - Invalid game state
     #[msg("Invalid game state")]
     InvalidGameState,
+
+    /// 6008 - Not enough blocks since the attack started to roll
+    #[msg("Not enough blocks have been produced since the attack started")]
+    AttackRandomnessNotReady,
+
+    /// 6009 - SlotHashes no longer covers the attack's slots
+    #[msg("Slot hashes for this attack are no longer available")]
+    AttackRandomnessExpired,
+
+    /// 6010 - Attack is not using commit-reveal
+    #[msg("Commit-reveal is not enabled for this attack")]
+    CommitRevealDisabled,
+
+    /// 6011 - Side has already committed
+    #[msg("A commitment has already been made for this side")]
+    AlreadyCommitted,
+
+    /// 6012 - Reveal before both commitments exist
+    #[msg("Both attacker and defender must commit before revealing")]
+    CommitmentsIncomplete,
+
+    /// 6013 - Revealed secret does not match commitment
+    #[msg("Revealed secret does not match the commitment")]
+    InvalidReveal,
+
+    /// 6014 - Commit or reveal after the deadline
+    #[msg("The commit-reveal window for this attack has closed")]
+    RevealWindowClosed,
+
+    /// 6015 - Resolve before reveals are in or the deadline has passed
+    #[msg("Waiting for both sides to reveal or for the reveal deadline")]
+    RevealPending,
+
+    /// 6016 - Unknown randomness source
+    #[msg("Invalid randomness source")]
+    InvalidRandomnessSource,
+
+    /// 6017 - VRF randomness has not been fulfilled yet
+    #[msg("Randomness for this attack has not been fulfilled yet")]
+    RandomnessNotFulfilled,
+
+    /// 6018 - VRF randomness was already fulfilled
+    #[msg("Randomness for this attack has already been fulfilled")]
+    RandomnessAlreadyFulfilled,
+
+    /// 6019 - Attack has not reached its expiry
+    #[msg("Attack has not expired yet")]
+    AttackNotExpired,
+
+    /// 6020 - Unknown combat model or zero dice parameters
+    #[msg("Invalid combat settings")]
+    InvalidCombatSettings,
+
+    /// 6021 - Reinforcement after the attack's reinforcement window
+    #[msg("The reinforcement window for this attack has closed")]
+    ReinforcementWindowClosed,
+
+    /// 6022 - Moving resources out of a tile with a pending attack
+    #[msg("Resources can't be withdrawn from a tile with a pending attack")]
+    AttackingTileLocked,
+
+    /// 6023 - Coordinated attack contributors or allocation are invalid
+    #[msg("Invalid coordinated attack allocation")]
+    InvalidAllocation,
+
+    /// 6024 - Attacking from a tile that is still cooling down
+    #[msg("This tile is still on attack cooldown")]
+    AttackCooldownActive,
+
+    /// 6025 - Player already has the maximum number of open attacks
+    #[msg("Too many open attacks")]
+    TooManyOpenAttacks,
+
+    /// 6026 - create_game rules outside their allowed bounds
+    #[msg("Invalid game config")]
+    InvalidGameConfig,
+
+    /// 6027 - Game account still uses the v1 layout
+    #[msg("Game must be migrated before it can be played")]
+    GameNeedsMigration,
+
+    /// 6028 - tick_game on a game that accrued less than a minute ago
+    #[msg("Nothing to tick yet")]
+    TickTooEarly,
+
+    /// 6029 - claim_payout from a place that's owed nothing or already claimed
+    #[msg("No payout due")]
+    NoPayoutDue,
+
+    /// 6030 - claim_payout after the payout deadline
+    #[msg("The payout claim window has closed")]
+    PayoutClaimWindowClosed,
+
+    /// 6031 - expire_payouts before the payout deadline
+    #[msg("The payout claim window is still open")]
+    PayoutClaimWindowOpen,
+
+    /// 6032 - set_platform_fee above MAX_PLATFORM_FEE_BPS
+    #[msg("Platform fee too high")]
+    InvalidPlatformFee,
+
+    /// 6033 - withdraw_fees for more than the fee vault holds above its rent reserve
+    #[msg("Not enough fees to withdraw")]
+    InsufficientFees,
+
+    /// 6034 - cancel_game by someone other than the admin before the join deadline
+    #[msg("Only the game admin can cancel before the join deadline")]
+    JoinDeadlineNotReached,
+
+    /// 6035 - cancel_game without a (wallet, player) pair for every joined seat
+    #[msg("Wallet and player accounts must be passed for every joined player")]
+    MissingPlayerAccounts,
+
+    /// 6036 - join_game from a wallet that already has a seat in the game
+    #[msg("Player has already joined this game")]
+    AlreadyJoined,
+
+    /// 6037 - finish_game_for_player while the player is still in the game
+    #[msg("The game is not over for this player")]
+    GameNotFinished,
+
+    /// 6038 - resign from a player who has already resigned
+    #[msg("Player is no longer in play")]
+    PlayerNotInPlay,
+
+    /// 6039 - resign while the player still has unresolved attacks
+    #[msg("Resolve or expire open attacks first")]
+    OpenAttacksPending,
+
+    /// 6040 - resign handing tiles to a colour that can't take them, or in a game that doesn't allow it
+    #[msg("Invalid resign beneficiary")]
+    InvalidResignBeneficiary,
+
+    /// 6041 - join_game after the game's join deadline
+    #[msg("The join deadline has passed")]
+    JoinDeadlinePassed,
 }
@@ -2,6 +2,10 @@ use anchor_lang::prelude::*;
 
 #[event]
 pub struct AttackResolved {
+    pub game_id: u64,
+    pub attacker_tile_index: u16,
+    pub defender_tile_index: u16,
+    pub attacker_won: bool,
     pub attacker_tile_color: u8,
     pub attacker_resources: u16,
     pub attacker_roll_result: u16, // 0-999
@@ -11,8 +15,231 @@ pub struct AttackResolved {
     pub hit_resource_count: u8,    // Number of resources lost by the loser
 }
 
+#[event]
+pub struct RandomnessRequested {
+    pub game: Pubkey,
+    pub defender: Pubkey,
+    pub defender_tile_index: u16,
+}
+
 #[event]
 pub struct GameStarted {
     pub game_id: u64,
 }
 
+
+#[event]
+pub struct GameCreated {
+    pub game_id: u64,
+    pub admin: Pubkey,
+    pub rows: u8,
+    pub columns: u8,
+    pub max_players: u8,
+    pub starting_tile_indices: Vec<u16>, // Starting tile for each colour (index 0 = red)
+    pub starting_resources: u16,
+}
+
+#[event]
+pub struct GameCancelled {
+    pub game_id: u64,
+    pub cancelled_by: Pubkey,
+    pub players_refunded: u8,
+    pub refund_total: u64, // Lamports returned from the treasury
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameMigrated {
+    pub game_id: u64,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct PlatformMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+#[event]
+pub struct PlayerCreated {
+    pub wallet: Pubkey,
+    pub hotwallet: Pubkey,
+    pub name: [u8; 32],
+}
+
+#[event]
+pub struct PlayerJoined {
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub color: u8,
+    pub starting_tile_index: u16,
+}
+
+#[event]
+pub struct PlayerLeft {
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub color: u8,   // Seat freed for the next player to join
+    pub refund: u64, // Entry cost returned from the treasury
+}
+
+#[event]
+pub struct PlayerFinishedGame {
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub game_state: u8, // State of the game when the player was released
+}
+
+#[event]
+pub struct PlayerEliminated {
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub color: u8,
+    pub eliminated_order: u8, // 1 = first colour out
+    pub players_left: u8,     // Colours still in play
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlayerResigned {
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub color: u8,
+    pub beneficiary: u8, // Colour that took their tiles (0 = they went neutral)
+    pub tiles: u32,      // Tiles they held
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ResourcesAdded {
+    pub game_id: u64,
+    pub color: u8,
+    pub tile_index: u16,
+    pub resources_added: u16,
+    pub tile_resource_count: u16, // Resources on the tile afterwards
+}
+
+#[event]
+pub struct ResourcesMoved {
+    pub game_id: u64,
+    pub color: u8,
+    pub source_tile_index: u16,
+    pub destination_tile_index: u16,
+    pub resources_moved: u16,
+    pub source_resource_count: u16,      // Resources left on the source tile
+    pub destination_resource_count: u16, // Resources on the destination tile afterwards
+}
+
+#[event]
+pub struct AttackStarted {
+    pub game_id: u64,
+    pub attacker_color: u8,
+    pub defender_color: u8,
+    pub attacker_tile_indices: Vec<u16>,       // One tile, or every contributor of a coordinated attack
+    pub contributor_allocation_bps: Vec<u16>, // Share of survivors each contributor gets back on capture
+    pub defender_tile_index: u16,
+    pub defender_resources: u16,
+}
+
+#[event]
+pub struct DefenseReinforced {
+    pub game_id: u64,
+    pub color: u8,
+    pub source_tile_index: u16,
+    pub defender_tile_index: u16,
+    pub resources_committed: u16,
+}
+
+#[event]
+pub struct AttackExpired {
+    pub game_id: u64,
+    pub attacker_color: u8,
+    pub attacker_tile_index: u16,
+    pub defender_tile_index: u16,
+    pub penalty: u16, // Resources removed from the attacking tile
+}
+
+#[event]
+pub struct AttackVoided {
+    pub game_id: u64,
+    pub attacker_color: u8,
+    pub attacker_tile_index: u16,
+    pub defender_tile_index: u16,
+}
+
+#[event]
+pub struct TileCaptured {
+    pub game_id: u64,
+    pub tile_index: u16,
+    pub previous_color: u8,
+    pub new_color: u8,
+    pub attacker_tile_index: u16,
+    pub resource_count: u16, // Resources moved onto the captured tile
+}
+
+#[event]
+pub struct XpAccrued {
+    pub game_id: u64,
+    pub color: u8,
+    pub xp_added: u32,
+    pub total_xp: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GameTicked {
+    pub game_id: u64,
+    pub cranker: Pubkey,
+    pub reward: u64, // Lamports paid from the treasury (0 if none configured or the treasury is short)
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinnerDetermined {
+    pub game_id: u64,
+    pub winner: Pubkey,
+    pub color: u8,
+    pub xp: u64,
+    pub reached_limit_at: i64, // Second the winner reached winning_xp_limit (0 for last standing)
+    pub win_condition: u8,     // WIN_CONDITION_* (see state/game.rs)
+    pub standings: Vec<u8>,    // Colours in finishing order
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutClaimed {
+    pub game_id: u64,
+    pub wallet: Pubkey,
+    pub color: u8,
+    pub place: u8,       // 1 = winner
+    pub amount: u64,     // Includes the treasury's rent reserve on the last claim
+    pub completed: bool, // Whether this was the last payout owed
+}
+
+#[event]
+pub struct PayoutsExpired {
+    pub game_id: u64,
+    pub unclaimed: u64, // Lamports swept to the platform fee vault
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PlatformFeeCollected {
+    pub game_id: u64,
+    pub amount: u64, // Lamports moved from the game treasury to the fee vault
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct PlatformFeeUpdated {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub fees_withdrawn: u64, // Running total, see Platform
+}
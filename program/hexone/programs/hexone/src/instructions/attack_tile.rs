@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::game::{Game, GameBoard, GAME_STATE_IN_PROGRESS};
 use crate::state::player::Player;
 use crate::state::defender::Defender;
 use crate::error::HexoneError;
+use crate::constants::{COMMIT_REVEAL_DEADLINE_SECONDS, ATTACK_EXPIRY_SECONDS, MAX_ATTACK_CONTRIBUTORS};
+use crate::randomness::RANDOMNESS_SOURCE_VRF;
+use crate::events::{AttackStarted, RandomnessRequested};
 
 // Import the adjacency check function from move_resources
 use crate::instructions::move_resources::are_tiles_adjacent;
@@ -12,7 +15,7 @@ pub fn attack_tile(
     attacker_tile_index: u16,
     defender_tile_index: u16,
 ) -> Result<()> {
-    let game = &ctx.accounts.game.load()?;
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
     let wallet_key = ctx.accounts.player_wallet.key();
     let clock = Clock::get()?;
 
@@ -23,21 +26,12 @@ pub fn attack_tile(
     );
 
     // Check if player is in the game and determine their color
-    let attacker_color = if game.player1 == wallet_key {
-        1u8 // Red
-    } else if game.player2 == wallet_key {
-        2u8 // Yellow
-    } else if game.player3 == wallet_key {
-        3u8 // Green
-    } else if game.player4 == wallet_key {
-        4u8 // Blue
-    } else {
-        return Err(HexoneError::PlayerNotAuthorized.into());
-    };
+    let attacker_color = game.player_color(&wallet_key);
+    require!(attacker_color != 0, HexoneError::PlayerNotAuthorized);
 
     // Validate tile indices
     require!(
-        attacker_tile_index < 144 && defender_tile_index < 144,
+        attacker_tile_index < game.tile_count() && defender_tile_index < game.tile_count(),
         HexoneError::Invalid
     );
 
@@ -84,16 +78,132 @@ pub fn attack_tile(
     // Initialize defending account
     // Note: Anchor's `init` will automatically fail if the account already exists,
     // which means this tile is already being attacked
+    let game_key = ctx.accounts.game.key();
+    let defender_key = ctx.accounts.defender.key();
     let defender = &mut ctx.accounts.defender;
-    defender.game = ctx.accounts.game.key();
+    defender.bump = ctx.bumps.defender;
+    defender.payer = ctx.accounts.signer_wallet.key();
+    start_attack(
+        game,
+        game_key,
+        defender,
+        defender_key,
+        &clock,
+        &AttackPlan {
+            defender_tile_index,
+            attacker_color,
+            contributor_tile_indices: &[attacker_tile_index],
+            contributor_allocation_bps: &[0],
+        },
+    )
+}
+
+/// The target and contributing tiles of a new attack, as validated by its instruction
+pub(crate) struct AttackPlan<'a> {
+    pub defender_tile_index: u16,
+    pub attacker_color: u8,
+    pub contributor_tile_indices: &'a [u16],
+    pub contributor_allocation_bps: &'a [u16],
+}
+
+/// Fill in a freshly created Defender record and lock the attacking tiles
+/// Shared by attack_tile and coordinated_attack; callers validate the tiles first
+pub(crate) fn start_attack(
+    game: &mut GameBoard,
+    game_key: Pubkey,
+    defender: &mut Defender,
+    defender_key: Pubkey,
+    clock: &Clock,
+    plan: &AttackPlan,
+) -> Result<()> {
+    let AttackPlan {
+        defender_tile_index,
+        attacker_color,
+        contributor_tile_indices,
+        contributor_allocation_bps,
+    } = *plan;
+
+    // Every contributing tile must be off cooldown and the player under the open attack cap
+    for tile_index in contributor_tile_indices {
+        require!(
+            clock.unix_timestamp >= game.tile_data[*tile_index as usize].attack_cooldown_until,
+            HexoneError::AttackCooldownActive
+        );
+    }
+    let max_open_attacks = game.max_open_attacks_per_player;
+    let open_attacks = game.open_attacks_mut(attacker_color)?;
+    require!(
+        max_open_attacks == 0 || *open_attacks < max_open_attacks,
+        HexoneError::TooManyOpenAttacks
+    );
+    *open_attacks += 1;
+
+    let defender_tile = game.tile_data[defender_tile_index as usize];
+    defender.game = game_key;
     defender.defender_tile_index = defender_tile_index;
     defender.defender_tile_color = defender_tile.color;
-    defender.attacker_tile_index = attacker_tile_index;
+    defender.attacker_tile_index = contributor_tile_indices[0];
     defender.attacker_tile_color = attacker_color;
     defender.attack_started_at = clock.unix_timestamp;
+    defender.attack_started_slot = clock.slot;
     defender.is_attack_resolved = false;
     defender.attacker_won = false;
-    defender.bump = ctx.bumps.defender;
+
+    // Snapshot the defence; the defender can add to it with reinforce_defense during the window
+    defender.defender_resources_at_start = defender_tile.resource_count;
+    defender.reinforced_resources = 0;
+
+    // Record the contributing tiles, start their cooldown and lock them against withdrawals
+    // until the attack is resolved or expires
+    let cooldown_until = clock.unix_timestamp + game.tile_attack_cooldown_seconds as i64;
+    defender.contributor_count = contributor_tile_indices.len() as u8;
+    defender.contributor_tile_indices = [0u16; MAX_ATTACK_CONTRIBUTORS];
+    defender.contributor_allocation_bps = [0u16; MAX_ATTACK_CONTRIBUTORS];
+    for (i, tile_index) in contributor_tile_indices.iter().enumerate() {
+        defender.contributor_tile_indices[i] = *tile_index;
+        defender.contributor_allocation_bps[i] = contributor_allocation_bps[i];
+
+        let attacker_tile = &mut game.tile_data[*tile_index as usize];
+        attacker_tile.attack_cooldown_until = cooldown_until;
+        attacker_tile.pending_attacks = attacker_tile
+            .pending_attacks
+            .checked_add(1)
+            .ok_or(HexoneError::Invalid)?;
+    }
+
+    emit!(AttackStarted {
+        game_id: game.game_id,
+        attacker_color,
+        defender_color: defender_tile.color,
+        attacker_tile_indices: contributor_tile_indices.to_vec(),
+        contributor_allocation_bps: contributor_allocation_bps.to_vec(),
+        defender_tile_index,
+        defender_resources: defender_tile.resource_count,
+    });
+
+    // Unresolved attacks expire so they can't pin the defending tile forever
+    defender.expires_at = clock.unix_timestamp + ATTACK_EXPIRY_SECONDS;
+
+    // In commit-reveal games both sides commit and reveal a secret before the attack can roll
+    defender.commit_reveal = game.commit_reveal_enabled != 0;
+    defender.reveal_deadline = clock.unix_timestamp + COMMIT_REVEAL_DEADLINE_SECONDS;
+    defender.attacker_commitment = [0u8; 32];
+    defender.defender_commitment = [0u8; 32];
+    defender.attacker_secret = [0u8; 32];
+    defender.defender_secret = [0u8; 32];
+    defender.attacker_revealed = false;
+    defender.defender_revealed = false;
+
+    // VRF games ask the oracle for randomness; it answers through fulfill_randomness
+    defender.vrf_randomness = [0u8; 32];
+    defender.vrf_fulfilled = false;
+    if game.randomness_source == RANDOMNESS_SOURCE_VRF {
+        emit!(RandomnessRequested {
+            game: game_key,
+            defender: defender_key,
+            defender_tile_index,
+        });
+    }
 
     Ok(())
 }
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_CANCELLED, GAME_STATE_WAITING};
+use crate::state::player::Player;
+use crate::error::HexoneError;
+use crate::events::GameCancelled;
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    /// The game admin, or anyone once the join deadline has passed
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [b"game_treasury", game.key().as_ref()],
+        bump,
+    )]
+    pub game_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (wallet, player) for every joined seat, in colour order, both writable
+}
+
+/// Cancel a game that never filled: refund every joined wallet its entry cost, set their
+/// players back to ready and move the game to GAME_STATE_CANCELLED
+pub fn cancel_game<'info>(ctx: Context<'_, '_, 'info, 'info, CancelGame<'info>>) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(game.game_state == GAME_STATE_WAITING, HexoneError::GameNotWaiting);
+    require!(
+        ctx.accounts.caller.key() == game.admin || current_time > game.join_deadline,
+        HexoneError::JoinDeadlineNotReached
+    );
+
+    let joined: Vec<Pubkey> = game
+        .seated_players()
+        .iter()
+        .map(|slot| slot.wallet)
+        .filter(|wallet| *wallet != Pubkey::default())
+        .collect();
+    require!(
+        ctx.remaining_accounts.len() == joined.len() * 2,
+        HexoneError::MissingPlayerAccounts
+    );
+
+    let game_key = ctx.accounts.game.key();
+    let seeds = &[
+        b"game_treasury",
+        game_key.as_ref(),
+        &[ctx.bumps.game_treasury],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let mut refund_total: u64 = 0;
+    for (wallet, accounts) in joined.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let (wallet_info, player_info) = (&accounts[0], &accounts[1]);
+        require!(wallet_info.key() == *wallet, HexoneError::MissingPlayerAccounts);
+
+        let mut player: Account<'info, Player> = Account::try_from(player_info)?;
+        let expected_player = Pubkey::create_program_address(
+            &[b"player", wallet.as_ref(), &[player.bump]],
+            ctx.program_id,
+        )
+        .map_err(|_| HexoneError::MissingPlayerAccounts)?;
+        require!(
+            expected_player == player_info.key() && player.wallet == *wallet,
+            HexoneError::MissingPlayerAccounts
+        );
+
+        if game.entry_cost > 0 {
+            anchor_lang::solana_program::program::invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.game_treasury.key,
+                    wallet_info.key,
+                    game.entry_cost,
+                ),
+                &[
+                    ctx.accounts.game_treasury.to_account_info(),
+                    wallet_info.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+            refund_total = refund_total.saturating_add(game.entry_cost);
+        }
+
+        // A game that never started doesn't count as played
+        if player.release(game_key) {
+            player.games_played = player.games_played.saturating_sub(1);
+        }
+        player.exit(ctx.program_id)?;
+    }
+
+    game.game_state = GAME_STATE_CANCELLED;
+
+    emit!(GameCancelled {
+        game_id: game.game_id,
+        cancelled_by: ctx.accounts.caller.key(),
+        players_refunded: joined.len() as u8,
+        refund_total,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_COMPLETED, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT, check_for_winner};
+use crate::state::economy::accrue_xp;
+use crate::state::platform::Platform;
+use crate::state::player::Player;
+use crate::error::HexoneError;
+use crate::events::{PayoutClaimed, PlatformFeeCollected};
+
+#[derive(Accounts)]
+pub struct ClaimPayout<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    /// CHECK: This is the game treasury PDA that holds the prize pool
+    #[account(
+        mut,
+        seeds = [b"game_treasury", game.key().as_ref()],
+        bump,
+    )]
+    pub game_treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == wallet.key() @ HexoneError::PlayerNotAuthorized,
+    )]
+    pub player: Account<'info, Player>,
+
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = platform.fee_vault_bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay a finishing place its share of the prize pool
+/// The first claim locks in the pool (the treasury less its rent reserve), moves the platform fee
+/// to the fee vault and splits the rest by the game's payout table. The game is completed by the
+/// last claim owed, which also takes the rent reserve as the treasury is emptied
+pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+
+    // Get current time
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+
+    // First, update XP for all players
+    accrue_xp(game, current_time);
+
+    // Check if any player has reached the limit and determine winner if needed
+    // This will set the winner if limit is reached and flag is not set
+    check_for_winner(game, current_time)?;
+
+    require!(
+        game.game_state == GAME_STATE_WINNER_FOUND_NOT_PAID_OUT,
+        HexoneError::InvalidGameState
+    );
+    require!(
+        current_time <= game.payout_deadline,
+        HexoneError::PayoutClaimWindowClosed
+    );
+
+    // Only players in the final standings can claim, once each
+    let wallet_key = ctx.accounts.wallet.key();
+    let place = game
+        .standing_place(game.player_color(&wallet_key))
+        .ok_or(HexoneError::PlayerNotAuthorized)?;
+    require!(
+        game.standings[place].payout_claimed == 0,
+        HexoneError::NoPayoutDue
+    );
+
+    let game_key = ctx.accounts.game.key();
+    let seeds = &[
+        b"game_treasury",
+        game_key.as_ref(),
+        &[ctx.bumps.game_treasury],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if game.standings.iter().all(|standing| standing.payout_claimed == 0) {
+        // The treasury keeps its rent reserve until the last payout
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let pool = ctx.accounts.game_treasury.lamports().saturating_sub(rent_exempt_minimum);
+        let platform_fee = game.lock_payouts(pool);
+
+        if platform_fee > 0 {
+            anchor_lang::solana_program::program::invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.game_treasury.key,
+                    ctx.accounts.fee_vault.key,
+                    platform_fee,
+                ),
+                &[
+                    ctx.accounts.game_treasury.to_account_info(),
+                    ctx.accounts.fee_vault.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+
+            let platform = &mut ctx.accounts.platform;
+            platform.fees_collected = platform.fees_collected
+                .checked_add(platform_fee)
+                .ok_or(HexoneError::Invalid)?;
+
+            emit!(PlatformFeeCollected {
+                game_id: game.game_id,
+                amount: platform_fee,
+                fee_bps: game.fee_bps,
+            });
+        }
+    }
+
+    // First place always claims (it records the win); other places only when they're owed
+    require!(
+        place == 0 || game.standings[place].payout > 0,
+        HexoneError::NoPayoutDue
+    );
+    game.standings[place].payout_claimed = 1;
+
+    // The last claim empties the treasury
+    let completed = !game.payouts_outstanding();
+    let amount = if completed {
+        ctx.accounts.game_treasury.lamports()
+    } else {
+        game.standings[place].payout
+    };
+
+    if amount > 0 {
+        // We need to use invoke_signed because the treasury is a PDA and needs program signature
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.game_treasury.key,
+                ctx.accounts.wallet.key,
+                amount,
+            ),
+            &[
+                ctx.accounts.game_treasury.to_account_info(),
+                ctx.accounts.wallet.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    if completed {
+        game.game_state = GAME_STATE_COMPLETED;
+        let platform = &mut ctx.accounts.platform;
+        platform.games_completed = platform.games_completed
+            .checked_add(1)
+            .ok_or(HexoneError::Invalid)?;
+    }
+
+    emit!(PayoutClaimed {
+        game_id: game.game_id,
+        wallet: wallet_key,
+        color: game.standings[place].color,
+        place: (place + 1) as u8,
+        amount,
+        completed,
+    });
+
+    // Claiming is the player's last step in the game
+    let player = &mut ctx.accounts.player;
+    player.release(game_key);
+
+    // Increment games won count for the winner
+    if place == 0 {
+        player.games_won = player.games_won
+            .checked_add(1)
+            .ok_or(HexoneError::Invalid)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::player::Player;
+use crate::state::defender::Defender;
+use crate::error::HexoneError;
+
+pub fn commit_roll(ctx: Context<CommitRoll>, commitment: [u8; 32]) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+    let defender = &mut ctx.accounts.defender;
+    let wallet_key = ctx.accounts.player_wallet.key();
+    let clock = Clock::get()?;
+
+    // Check game state
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::Invalid
+    );
+
+    require!(defender.commit_reveal, HexoneError::CommitRevealDisabled);
+    require!(
+        clock.unix_timestamp < defender.reveal_deadline,
+        HexoneError::RevealWindowClosed
+    );
+
+    // An all-zero commitment is used to mean "not committed yet"
+    require!(commitment != [0u8; 32], HexoneError::Invalid);
+
+    // Find the attacker's and defender's wallets from the tile colors
+    let attacker_wallet = game.slot(defender.attacker_tile_color)?.wallet;
+    let defender_wallet = game.slot(defender.defender_tile_color)?.wallet;
+
+    // Each side commits once: commitment = sha256(secret || wallet)
+    if wallet_key == attacker_wallet {
+        require!(
+            defender.attacker_commitment == [0u8; 32],
+            HexoneError::AlreadyCommitted
+        );
+        defender.attacker_commitment = commitment;
+    } else if wallet_key == defender_wallet {
+        require!(
+            defender.defender_commitment == [0u8; 32],
+            HexoneError::AlreadyCommitted
+        );
+        defender.defender_commitment = commitment;
+    } else {
+        return Err(HexoneError::PlayerNotAuthorized.into());
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CommitRoll<'info> {
+    /// CHECK: The attacker's or defender's wallet (used for PDA derivation, not necessarily the signer)
+    pub player_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Signer must be either player's wallet or player's hotwallet
+    #[account(mut)]
+    pub signer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"player", player_wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == player_wallet.key() @ HexoneError::PlayerNotAuthorized,
+        constraint = (signer_wallet.key() == player.wallet || signer_wallet.key() == player.hotwallet) @ HexoneError::PlayerNotAuthorized
+    )]
+    pub player: Account<'info, Player>,
+
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"defender",
+            game.key().as_ref(),
+            defender.defender_tile_index.to_le_bytes().as_ref(),
+        ],
+        bump = defender.bump,
+        constraint = !defender.is_attack_resolved @ HexoneError::Invalid
+    )]
+    pub defender: Account<'info, Defender>,
+}
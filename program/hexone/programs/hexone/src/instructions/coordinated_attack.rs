@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::player::Player;
+use crate::state::defender::Defender;
+use crate::error::HexoneError;
+use crate::constants::{MAX_ATTACK_CONTRIBUTORS, ALLOCATION_BPS_DENOMINATOR};
+use crate::instructions::attack_tile::{start_attack, AttackPlan};
+
+// Import the adjacency check function from move_resources
+use crate::instructions::move_resources::are_tiles_adjacent;
+
+/// Attack one enemy tile from several of the player's own adjacent tiles at once
+/// Every contributor commits all but 1 of its resources to the attack. If the tile is captured,
+/// contributor i gets contributor_allocation_bps[i] / 10,000 of the surviving resources back
+/// and the captured tile gets the rest
+pub fn coordinated_attack(
+    ctx: Context<CoordinatedAttack>,
+    defender_tile_index: u16,
+    contributor_tile_indices: Vec<u16>,
+    contributor_allocation_bps: Vec<u16>,
+) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let wallet_key = ctx.accounts.player_wallet.key();
+    let clock = Clock::get()?;
+
+    // Check game state
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::Invalid
+    );
+
+    // Check if player is in the game and determine their color
+    let attacker_color = game.player_color(&wallet_key);
+    require!(attacker_color != 0, HexoneError::PlayerNotAuthorized);
+
+    // At least two contributors (a single tile attacks with attack_tile), one allocation each,
+    // and the captured tile must keep a share
+    require!(
+        contributor_tile_indices.len() >= 2
+            && contributor_tile_indices.len() <= MAX_ATTACK_CONTRIBUTORS
+            && contributor_allocation_bps.len() == contributor_tile_indices.len(),
+        HexoneError::InvalidAllocation
+    );
+    let total_allocation_bps = contributor_allocation_bps
+        .iter()
+        .try_fold(0u16, |total, bps| total.checked_add(*bps))
+        .ok_or(HexoneError::InvalidAllocation)?;
+    require!(
+        total_allocation_bps < ALLOCATION_BPS_DENOMINATOR,
+        HexoneError::InvalidAllocation
+    );
+
+    // Check defender tile belongs to another player (not empty, not attacker's color)
+    require!(defender_tile_index < game.tile_count(), HexoneError::Invalid);
+    let defender_tile = game.tile_data[defender_tile_index as usize];
+    require!(
+        defender_tile.color != 0 && defender_tile.color != attacker_color,
+        HexoneError::Invalid
+    );
+
+    // Each contributor must be a distinct tile of the attacker's, adjacent to the target,
+    // with at least 2 resources (must leave at least 1)
+    for (i, tile_index) in contributor_tile_indices.iter().enumerate() {
+        require!(
+            !contributor_tile_indices[..i].contains(tile_index),
+            HexoneError::InvalidAllocation
+        );
+        require!(*tile_index < game.tile_count(), HexoneError::Invalid);
+        require!(
+            are_tiles_adjacent(*tile_index, defender_tile_index, game.rows, game.columns),
+            HexoneError::Invalid
+        );
+        let tile = game.tile_data[*tile_index as usize];
+        require!(tile.color == attacker_color, HexoneError::Invalid);
+        require!(tile.resource_count >= 2, HexoneError::Invalid);
+    }
+
+    // Initialize defending account (init fails if this tile is already being attacked)
+    let game_key = ctx.accounts.game.key();
+    let defender_key = ctx.accounts.defender.key();
+    let defender = &mut ctx.accounts.defender;
+    defender.bump = ctx.bumps.defender;
+    defender.payer = ctx.accounts.signer_wallet.key();
+    start_attack(
+        game,
+        game_key,
+        defender,
+        defender_key,
+        &clock,
+        &AttackPlan {
+            defender_tile_index,
+            attacker_color,
+            contributor_tile_indices: &contributor_tile_indices,
+            contributor_allocation_bps: &contributor_allocation_bps,
+        },
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(defender_tile_index: u16)]
+pub struct CoordinatedAttack<'info> {
+    /// CHECK: The player's wallet (used for PDA derivation, not necessarily the signer)
+    pub player_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Signer must be either player's wallet or player's hotwallet
+    #[account(mut)]
+    pub signer_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", player_wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == player_wallet.key() @ HexoneError::PlayerNotAuthorized,
+        constraint = (signer_wallet.key() == player.wallet || signer_wallet.key() == player.hotwallet) @ HexoneError::PlayerNotAuthorized
+    )]
+    pub player: Account<'info, Player>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        init,
+        payer = signer_wallet,
+        space = Defender::LEN,
+        seeds = [
+            b"defender",
+            game.key().as_ref(),
+            defender_tile_index.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub defender: Account<'info, Defender>,
+
+    pub system_program: Program<'info, System>,
+}
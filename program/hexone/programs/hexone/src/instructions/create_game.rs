@@ -1,17 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::state::game::Game;
+use bytemuck::Zeroable;
+use crate::state::game::{Game, PlayerSlot, GAME_VERSION, get_tile_tier, update_tier_count_on_gain};
+use crate::constants::MAX_PLAYERS;
 use crate::state::platform::Platform;
-use crate::error::HexoneError;
-use crate::constants::{
-    RESOURCES_PER_MINUTE, 
-    XP_PER_MINUTE_PER_TILE,
-    GOLD_TIER_BONUS_XP_PER_MIN,
-    SILVER_TIER_BONUS_XP_PER_MIN,
-    BRONZE_TIER_BONUS_XP_PER_MIN,
-    IRON_TIER_BONUS_XP_PER_MIN,
-};
+use crate::state::game_config::GameConfig;
+use crate::events::GameCreated;
 
 #[derive(Accounts)]
+#[instruction(config: GameConfig)]
 pub struct CreateGame<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -26,7 +22,7 @@ pub struct CreateGame<'info> {
     #[account(
         init,
         payer = admin,
-        space = Game::LEN,
+        space = Game::space(config.rows, config.columns),
         seeds = [b"GAME-", platform.game_count.to_le_bytes().as_ref()],
         bump
     )]
@@ -35,8 +31,11 @@ pub struct CreateGame<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn create_game(ctx: Context<CreateGame>) -> Result<()> {
-    let game = &mut ctx.accounts.game.load_init()?;
+pub fn create_game(ctx: Context<CreateGame>, config: GameConfig) -> Result<()> {
+    // Validate the rules before touching any accounts
+    config.validate()?;
+
+    let game = &mut Game::load_board_init(&ctx.accounts.game, config.rows, config.columns)?;
     let platform = &mut ctx.accounts.platform;
 
     // Set admin
@@ -45,106 +44,115 @@ pub fn create_game(ctx: Context<CreateGame>) -> Result<()> {
     // Set game_id from platform game_count (before incrementing)
     game.game_id = platform.game_count;
 
-    // Set game dimensions
-    game.rows = 11;
-    game.columns = 13;
+    // Set game dimensions and player count
+    game.rows = config.rows;
+    game.columns = config.columns;
+    game.max_players = config.max_players;
+    game.player_count = 0;
+
+    // Anyone can cancel the game and refund its players if it hasn't filled by this time
+    game.join_deadline = Clock::get()?
+        .unix_timestamp
+        .saturating_add(config.join_window_seconds as i64);
 
     // Set resources per minute
-    game.resources_per_minute = RESOURCES_PER_MINUTE;
+    game.resources_per_minute = config.resources_per_minute;
 
-    // Store local variables for calculations
-    let rows = game.rows as usize;
-    let columns = game.columns as usize;
+    // Set entry cost and starting resources
+    game.entry_cost = config.entry_cost;
+    game.starting_resources = config.starting_resources;
 
     // Initialize tile data
-    for i in 0..game.tile_data.len() {
-        game.tile_data[i].color = 0;
-        game.tile_data[i].resource_count = 0;
+    for tile in game.tile_data.iter_mut() {
+        tile.color = 0;
+        tile.resource_count = 0;
+        tile.attack_cooldown_until = 0;
     }
 
-    // Set initial tiles for each player
-    // Red (color 1) - top left
-    game.tile_data[0].color = 1;
-    game.tile_data[0].resource_count = 100;
-
-    // Yellow (color 2) - top right
-    game.tile_data[columns - 1].color = 2;
-    game.tile_data[columns - 1].resource_count = 100;
-
-    // Green (color 3) - bottom left
-    game.tile_data[(rows - 1) * columns].color = 3;
-    game.tile_data[(rows - 1) * columns].resource_count = 100;
-
-    // Blue (color 4) - bottom right
-    game.tile_data[(rows * columns) - 1].color = 4;
-    game.tile_data[(rows * columns) - 1].resource_count = 100;
-
     // Initialize game state
     game.game_state = 0;
-    game.version = 1;
+    game.version = GAME_VERSION;
     game.bump = ctx.bumps.game;
 
     // Initialize resource tracking fields
     game.available_resources_timestamp = 0; // Will be set when game starts
     game.total_resources_available = 0;
-    game.resources_spent_player1 = 0;
-    game.resources_spent_player2 = 0;
-    game.resources_spent_player3 = 0;
-    game.resources_spent_player4 = 0;
 
     // Initialize XP tracking fields
-    game.xp_per_minute_per_tile = XP_PER_MINUTE_PER_TILE;
-    game.xp_timestamp_player1 = 0; // Will be set when game starts
-    game.xp_timestamp_player2 = 0; // Will be set when game starts
-    game.xp_timestamp_player3 = 0; // Will be set when game starts
-    game.xp_timestamp_player4 = 0; // Will be set when game starts
-    game.xp_player1 = 0;
-    game.xp_player2 = 0;
-    game.xp_player3 = 0;
-    game.xp_player4 = 0;
-    
-    // Initialize tile counts (each player starts with 1 tile)
-    game.tile_count_color1 = 1;
-    game.tile_count_color2 = 1;
-    game.tile_count_color3 = 1;
-    game.tile_count_color4 = 1;
-
-    // Initialize tier counts to 0 for all players
-    // Player 1
-    game.iron_tile_count_player1 = 0;
-    game.bronze_tile_count_player1 = 0;
-    game.silver_tile_count_player1 = 0;
-    game.gold_tile_count_player1 = 0;
-    // Player 2
-    game.iron_tile_count_player2 = 0;
-    game.bronze_tile_count_player2 = 0;
-    game.silver_tile_count_player2 = 0;
-    game.gold_tile_count_player2 = 0;
-    // Player 3
-    game.iron_tile_count_player3 = 0;
-    game.bronze_tile_count_player3 = 0;
-    game.silver_tile_count_player3 = 0;
-    game.gold_tile_count_player3 = 0;
-    // Player 4
-    game.iron_tile_count_player4 = 0;
-    game.bronze_tile_count_player4 = 0;
-    game.silver_tile_count_player4 = 0;
-    game.gold_tile_count_player4 = 0;
+    game.xp_per_minute_per_tile = config.xp_per_minute_per_tile;
+
+    // Empty seats; XP timestamps are set when the game starts
+    game.players = [PlayerSlot::zeroed(); MAX_PLAYERS];
+
+    // Set initial tiles for each player (see Game::starting_tile_index for the layout)
+    let mut starting_tile_indices = Vec::with_capacity(game.max_players as usize);
+    for color in 1..=game.max_players {
+        let tile_index = game.starting_tile_index(color)?;
+        game.tile_data[tile_index as usize].color = color;
+        game.tile_data[tile_index as usize].resource_count = config.starting_resources;
+
+        // Each player starts with 1 tile, which may sit in a tier ring on small boards
+        game.slot_mut(color)?.tile_count = 1;
+        let tier = get_tile_tier(tile_index, game.rows, game.columns);
+        update_tier_count_on_gain(game, color, tier)?;
+
+        starting_tile_indices.push(tile_index);
+    }
 
     // Initialize tier bonus XP per minute
-    game.gold_tier_bonus_xp_per_min = GOLD_TIER_BONUS_XP_PER_MIN;
-    game.silver_tier_bonus_xp_per_min = SILVER_TIER_BONUS_XP_PER_MIN;
-    game.bronze_tier_bonus_xp_per_min = BRONZE_TIER_BONUS_XP_PER_MIN;
-    game.iron_tier_bonus_xp_per_min = IRON_TIER_BONUS_XP_PER_MIN;
+    game.gold_tier_bonus_xp_per_min = config.gold_tier_bonus_xp_per_min;
+    game.silver_tier_bonus_xp_per_min = config.silver_tier_bonus_xp_per_min;
+    game.bronze_tier_bonus_xp_per_min = config.bronze_tier_bonus_xp_per_min;
+    game.iron_tier_bonus_xp_per_min = config.iron_tier_bonus_xp_per_min;
 
     // Initialize winning player and XP limit
     game.winning_player_pubkey = Pubkey::default();
-    game.winning_xp_limit = 10_000; // Default to 10,000 XP
+    game.winning_xp_limit = config.winning_xp_limit;
     game.winner_calculation_flag = 0; // Flag to track if winner calculation has been triggered
 
+    // Reward for keepers that drive the game forward with tick_game
+    game.crank_reward_lamports = config.crank_reward_lamports;
+
+    // Prize pool split by finishing place, after the platform fee in force when the game was created
+    game.payout_bps = config.payout_bps;
+    game.fee_bps = platform.fee_bps;
+
     // Initialize attack hit calculation parameters
-    game.max_hit_threshold = 500;        // Difference threshold for max hit
-    game.max_hit_resource_count = 5;     // Max resources lost on a hit
+    game.max_hit_threshold = config.max_hit_threshold;           // Difference threshold for max hit
+    game.max_hit_resource_count = config.max_hit_resource_count; // Max resources lost on a hit
+    game.expired_attack_penalty = config.expired_attack_penalty; // Lost by attackers who never resolve
+
+    // Competitive lobbies roll attacks with commit-reveal between attacker and defender
+    game.commit_reveal_enabled = config.commit_reveal_enabled as u8;
+
+    // Randomness source used to roll attacks, and the oracle allowed to fulfil VRF requests
+    game.randomness_source = config.randomness_source;
+    game.vrf_authority = config.vrf_authority;
+
+    // How resource counts weigh into attack odds
+    game.combat_model = config.combat_model;
+    game.resources_per_combat_die = config.resources_per_combat_die;
+    game.max_combat_dice = config.max_combat_dice;
+
+    // Attack cooldowns and the per-player cap on open attacks
+    game.tile_attack_cooldown_seconds = config.tile_attack_cooldown_seconds;
+    game.max_open_attacks_per_player = config.max_open_attacks_per_player;
+
+    // How long defenders have to reinforce an attacked tile
+    game.reinforce_window_seconds = config.reinforce_window_seconds;
+
+    // Whether resigning players can give their tiles to another colour
+    game.resign_beneficiary_enabled = config.resign_beneficiary_enabled as u8;
+
+    emit!(GameCreated {
+        game_id: game.game_id,
+        admin: game.admin,
+        rows: game.rows,
+        columns: game.columns,
+        max_players: game.max_players,
+        starting_tile_indices,
+        starting_resources: game.starting_resources,
+    });
 
     // Increment platform game count
     platform.game_count += 1;
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::constants::*;
-use crate::state::platform::Platform;
+use crate::state::platform::{Platform, PLATFORM_VERSION};
 
 #[derive(Accounts)]
 pub struct CreatePlatform<'info> {
@@ -15,12 +15,37 @@ pub struct CreatePlatform<'info> {
         bump
     )]
     pub platform: Account<'info, Platform>,
+    /// Holds platform fees; funded with its rent reserve here so any fee can be paid into it
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn create_platform(
     ctx: Context<CreatePlatform>
 ) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(ctx.accounts.fee_vault.lamports());
+    if rent_due > 0 {
+        anchor_lang::solana_program::program::invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.admin.key,
+                ctx.accounts.fee_vault.key,
+                rent_due,
+            ),
+            &[
+                ctx.accounts.admin.to_account_info(),
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
     let platform = &mut ctx.accounts.platform;
     let bump = ctx.bumps.platform;
 
@@ -29,8 +54,13 @@ pub fn create_platform(
     platform.games_completed = 0;
     platform.total_players = 0;
     platform.game_cost = DEFAULT_GAME_COST;
-    platform.version = 1;
+    platform.fees_collected = 0;
+    platform.unclaimed_collected = 0;
+    platform.fees_withdrawn = 0;
+    platform.fee_bps = DEFAULT_PLATFORM_FEE_BPS;
+    platform.version = PLATFORM_VERSION;
     platform.bump = bump;
+    platform.fee_vault_bump = ctx.bumps.fee_vault;
 
     Ok(())
-} 
\ No newline at end of file
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::player::{Player, PLAYER_STATUS_READY};
 use crate::state::platform::Platform;
+use crate::events::PlayerCreated;
 
 #[derive(Accounts)]
 pub struct CreatePlayer<'info> {
@@ -40,5 +41,11 @@ pub fn create_player(ctx: Context<CreatePlayer>, name: [u8; 32], hotwallet: Pubk
     // Increment total players count
     ctx.accounts.platform.total_players += 1;
 
+    emit!(PlayerCreated {
+        wallet: player.wallet,
+        hotwallet,
+        name,
+    });
+
     Ok(())
 } 
\ No newline at end of file
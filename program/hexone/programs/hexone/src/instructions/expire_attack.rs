@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::defender::Defender;
+use crate::error::HexoneError;
+use crate::events::AttackExpired;
+
+/// Close an attack that was never resolved before its expiry
+/// Anyone can call this; the rent goes back to whoever paid for the defender account
+pub fn expire_attack(ctx: Context<ExpireAttack>) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let defender = &ctx.accounts.defender;
+    let clock = Clock::get()?;
+
+    require!(
+        clock.unix_timestamp >= defender.expires_at,
+        HexoneError::AttackNotExpired
+    );
+
+    // Unlock the attacking tiles
+    for tile_index in defender.contributor_tiles() {
+        let tile = &mut game.tile_data[*tile_index as usize];
+        tile.pending_attacks = tile.pending_attacks.saturating_sub(1);
+    }
+    let open_attacks = game.open_attacks_mut(defender.attacker_tile_color)?;
+    *open_attacks = open_attacks.saturating_sub(1);
+
+    // Penalise the attacking tile if the game is still running and the attacker still holds it
+    // The tile always keeps at least 1 resource so ownership doesn't change
+    let attacker_tile_idx = defender.attacker_tile_index as usize;
+    let attacker_tile = game.tile_data[attacker_tile_idx];
+    let mut penalty = 0u16;
+    if game.game_state == GAME_STATE_IN_PROGRESS
+        && game.expired_attack_penalty > 0
+        && attacker_tile.color == defender.attacker_tile_color
+        && attacker_tile.resource_count > 1
+    {
+        penalty = (game.expired_attack_penalty as u16).min(attacker_tile.resource_count - 1);
+        game.tile_data[attacker_tile_idx].resource_count = attacker_tile.resource_count - penalty;
+        msg!(
+            "Attack on tile {} expired, attacker tile {} loses {} resources",
+            defender.defender_tile_index,
+            defender.attacker_tile_index,
+            penalty
+        );
+    } else {
+        msg!("Attack on tile {} expired", defender.defender_tile_index);
+    }
+
+    emit!(AttackExpired {
+        game_id: game.game_id,
+        attacker_color: defender.attacker_tile_color,
+        attacker_tile_index: defender.attacker_tile_index,
+        defender_tile_index: defender.defender_tile_index,
+        penalty,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireAttack<'info> {
+    /// Anyone can clean up an expired attack
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        close = payer,  // Rent goes back to whoever created the attack
+        seeds = [
+            b"defender",
+            game.key().as_ref(),
+            defender.defender_tile_index.to_le_bytes().as_ref(),
+        ],
+        bump = defender.bump,
+        constraint = !defender.is_attack_resolved @ HexoneError::Invalid
+    )]
+    pub defender: Account<'info, Defender>,
+
+    /// CHECK: Original payer of the defender account, checked against the stored payer
+    #[account(
+        mut,
+        address = defender.payer @ HexoneError::Invalid
+    )]
+    pub payer: UncheckedAccount<'info>,
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_COMPLETED, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT};
+use crate::state::platform::Platform;
+use crate::error::HexoneError;
+use crate::events::PayoutsExpired;
+
+#[derive(Accounts)]
+pub struct ExpirePayouts<'info> {
+    /// Anyone can close out a game once its claim window has passed
+    pub caller: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [b"game_treasury", game.key().as_ref()],
+        bump,
+    )]
+    pub game_treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    /// Receives whatever wasn't claimed
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = platform.fee_vault_bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Complete a game whose payout claim window has closed with payouts still unclaimed
+/// What's left in the treasury goes to the platform fee vault
+pub fn expire_payouts(ctx: Context<ExpirePayouts>) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        game.game_state == GAME_STATE_WINNER_FOUND_NOT_PAID_OUT,
+        HexoneError::InvalidGameState
+    );
+    require!(
+        current_time > game.payout_deadline,
+        HexoneError::PayoutClaimWindowOpen
+    );
+
+    let unclaimed = ctx.accounts.game_treasury.lamports();
+    if unclaimed > 0 {
+        let game_key = ctx.accounts.game.key();
+        let seeds = &[
+            b"game_treasury",
+            game_key.as_ref(),
+            &[ctx.bumps.game_treasury],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.game_treasury.key,
+                ctx.accounts.fee_vault.key,
+                unclaimed,
+            ),
+            &[
+                ctx.accounts.game_treasury.to_account_info(),
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    game.game_state = GAME_STATE_COMPLETED;
+
+    let platform = &mut ctx.accounts.platform;
+    platform.unclaimed_collected = platform.unclaimed_collected
+        .checked_add(unclaimed)
+        .ok_or(HexoneError::Invalid)?;
+    platform.games_completed = platform.games_completed
+        .checked_add(1)
+        .ok_or(HexoneError::Invalid)?;
+
+    emit!(PayoutsExpired {
+        game_id: game.game_id,
+        unclaimed,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
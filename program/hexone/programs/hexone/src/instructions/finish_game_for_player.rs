@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{
+    Game, GAME_STATE_CANCELLED, GAME_STATE_COMPLETED, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT,
+};
+use crate::state::player::Player;
+use crate::error::HexoneError;
+use crate::events::PlayerFinishedGame;
+
+#[derive(Accounts)]
+pub struct FinishGameForPlayer<'info> {
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == wallet.key() @ HexoneError::PlayerNotAuthorized,
+    )]
+    pub player: Account<'info, Player>,
+
+    pub game: AccountLoader<'info, Game>,
+}
+
+/// Set a player back to ready once their game is over for them: it has a winner, has been
+/// completed or cancelled, or they've been eliminated from it. claim_payout, leave_game and
+/// cancel_game do this on their own; this is the path for everyone else
+pub fn finish_game_for_player(ctx: Context<FinishGameForPlayer>) -> Result<()> {
+    let game = Game::load_header(&ctx.accounts.game)?;
+    let game_key = ctx.accounts.game.key();
+    let player = &mut ctx.accounts.player;
+
+    require!(player.last_game == Some(game_key), HexoneError::PlayerNotAuthorized);
+
+    let game_over = matches!(
+        game.game_state,
+        GAME_STATE_COMPLETED | GAME_STATE_WINNER_FOUND_NOT_PAID_OUT | GAME_STATE_CANCELLED
+    );
+    let color = game.player_color(&ctx.accounts.wallet.key());
+    let eliminated = color != 0 && game.slot(color)?.eliminated_order != 0;
+    require!(game_over || eliminated, HexoneError::GameNotFinished);
+
+    if player.release(game_key) {
+        emit!(PlayerFinishedGame {
+            game_id: game.game_id,
+            wallet: ctx.accounts.wallet.key(),
+            game_state: game.game_state,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::defender::Defender;
+use crate::randomness::RANDOMNESS_SOURCE_VRF;
+use crate::error::HexoneError;
+
+/// Callback for the VRF oracle: stores the randomness for a pending attack so it can be resolved
+pub fn fulfill_randomness(ctx: Context<FulfillRandomness>, randomness: [u8; 32]) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+    let defender = &mut ctx.accounts.defender;
+
+    // Check game state
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::Invalid
+    );
+
+    // Only VRF games accept randomness, and only from the oracle configured for the game
+    require!(
+        game.randomness_source == RANDOMNESS_SOURCE_VRF,
+        HexoneError::InvalidRandomnessSource
+    );
+    require!(
+        ctx.accounts.vrf_authority.key() == game.vrf_authority,
+        HexoneError::Unauthorized
+    );
+
+    // Randomness can only be set once per attack
+    require!(!defender.vrf_fulfilled, HexoneError::RandomnessAlreadyFulfilled);
+
+    defender.vrf_randomness = randomness;
+    defender.vrf_fulfilled = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FulfillRandomness<'info> {
+    /// Oracle authority (a keypair or the oracle program's PDA signing via CPI)
+    pub vrf_authority: Signer<'info>,
+
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"defender",
+            game.key().as_ref(),
+            defender.defender_tile_index.to_le_bytes().as_ref(),
+        ],
+        bump = defender.bump,
+        constraint = !defender.is_attack_resolved @ HexoneError::Invalid
+    )]
+    pub defender: Account<'info, Defender>,
+}
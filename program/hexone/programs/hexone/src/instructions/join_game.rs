@@ -1,9 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::state::game::{Game, GAME_STATE_WAITING, GAME_STATE_IN_PROGRESS};
-use crate::state::player::{Player, PLAYER_STATUS_PLAYING, PLAYER_STATUS_READY};
-use crate::state::platform::Platform;
+use crate::state::player::Player;
 use crate::error::HexoneError;
-use crate::events::GameStarted;
+use crate::events::{GameStarted, PlayerJoined};
 
 #[derive(Accounts)]
 pub struct JoinGame<'info> {
@@ -18,12 +17,6 @@ pub struct JoinGame<'info> {
     )]
     pub player: Account<'info, Player>,
 
-    #[account(
-        seeds = [b"platform"],
-        bump = platform.bump,
-    )]
-    pub platform: Account<'info, Platform>,
-
     #[account(mut)]
     pub game: AccountLoader<'info, Game>,
 
@@ -48,23 +41,30 @@ pub fn join_game(ctx: Context<JoinGame>, game_id: u64) -> Result<()> {
         HexoneError::Invalid
     );
 
-    let game = &mut ctx.accounts.game.load_mut()?;
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
     let player = &mut ctx.accounts.player;
-    let platform = &ctx.accounts.platform;
 
     // Check game state
     require!(game.game_state == GAME_STATE_WAITING, HexoneError::GameNotWaiting);
 
+    // Lobbies past their join deadline can only be cancelled
+    require!(
+        Clock::get()?.unix_timestamp <= game.join_deadline,
+        HexoneError::JoinDeadlinePassed
+    );
+
     // Check if game is full
-    if game.player1 != Pubkey::default() && 
-       game.player2 != Pubkey::default() && 
-       game.player3 != Pubkey::default() && 
-       game.player4 != Pubkey::default() {
-        return Err(HexoneError::GameFull.into());
-    }
+    require!(game.player_count < game.max_players, HexoneError::GameFull);
 
-    // Transfer game cost from player to game treasury
-    let game_cost = platform.game_cost;
+    // One seat per wallet, and one game per player at a time
+    require!(
+        game.player_color(&ctx.accounts.wallet.key()) == 0,
+        HexoneError::AlreadyJoined
+    );
+    player.join(ctx.accounts.game.key())?;
+
+    // Transfer the game's entry cost from player to game treasury
+    let game_cost = game.entry_cost;
     anchor_lang::solana_program::program::invoke(
         &anchor_lang::solana_program::system_instruction::transfer(
             ctx.accounts.wallet.key,
@@ -79,40 +79,37 @@ pub fn join_game(ctx: Context<JoinGame>, game_id: u64) -> Result<()> {
     )?;
 
     // Add player to first available slot
-    if game.player1 == Pubkey::default() {
-        game.player1 = ctx.accounts.wallet.key();
-    } else if game.player2 == Pubkey::default() {
-        game.player2 = ctx.accounts.wallet.key();
-    } else if game.player3 == Pubkey::default() {
-        game.player3 = ctx.accounts.wallet.key();
-    } else if game.player4 == Pubkey::default() {
-        game.player4 = ctx.accounts.wallet.key();
-    }
+    let color = game
+        .seated_players()
+        .iter()
+        .position(|slot| slot.wallet == Pubkey::default())
+        .map(|i| (i + 1) as u8)
+        .ok_or(HexoneError::GameFull)?;
+    game.slot_mut(color)?.wallet = ctx.accounts.wallet.key();
+    game.player_count += 1;
+    emit!(PlayerJoined {
+        game_id: game.game_id,
+        wallet: ctx.accounts.wallet.key(),
+        color,
+        starting_tile_index: game.starting_tile_index(color)?,
+    });
 
-    // Update player status
-    player.player_status = PLAYER_STATUS_PLAYING;
-    player.last_game = Some(ctx.accounts.game.key());
-    
     // Increment games played count
     player.games_played = player.games_played
         .checked_add(1)
         .ok_or(HexoneError::Invalid)?;
 
-    // Update game state if all players have joined
-    if game.player1 != Pubkey::default() && 
-       game.player2 != Pubkey::default() && 
-       game.player3 != Pubkey::default() && 
-       game.player4 != Pubkey::default() {
+    // Update game state once every seat is taken
+    if game.player_count == game.max_players {
         game.game_state = GAME_STATE_IN_PROGRESS;
         
         // Set all timestamps to start the game
         let clock = Clock::get()?;
         let current_timestamp = clock.unix_timestamp;
         game.available_resources_timestamp = current_timestamp;
-        game.xp_timestamp_player1 = current_timestamp;
-        game.xp_timestamp_player2 = current_timestamp;
-        game.xp_timestamp_player3 = current_timestamp;
-        game.xp_timestamp_player4 = current_timestamp;
+        for color in 1..=game.max_players {
+            game.slot_mut(color)?.xp_timestamp = current_timestamp;
+        }
         
         // Emit game started event
         emit!(GameStarted {
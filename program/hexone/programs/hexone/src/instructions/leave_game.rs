@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+use crate::state::game::{Game, PlayerSlot, GAME_STATE_WAITING};
+use crate::state::player::Player;
+use crate::error::HexoneError;
+use crate::events::PlayerLeft;
+
+#[derive(Accounts)]
+pub struct LeaveGame<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == wallet.key() @ HexoneError::PlayerNotAuthorized,
+    )]
+    pub player: Account<'info, Player>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [b"game_treasury", game.key().as_ref()],
+        bump
+    )]
+    pub game_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Leave a game before it starts: free the seat for the next player to join, refund the
+/// entry cost and undo everything join_game did to the player
+pub fn leave_game(ctx: Context<LeaveGame>) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let player = &mut ctx.accounts.player;
+
+    require!(game.game_state == GAME_STATE_WAITING, HexoneError::GameNotWaiting);
+
+    let wallet_key = ctx.accounts.wallet.key();
+    let color = game.player_color(&wallet_key);
+    require!(color != 0, HexoneError::PlayerNotAuthorized);
+
+    // Refund the entry cost join_game took
+    let refund = game.entry_cost;
+    if refund > 0 {
+        let game_key = ctx.accounts.game.key();
+        let seeds = &[
+            b"game_treasury",
+            game_key.as_ref(),
+            &[ctx.bumps.game_treasury],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.game_treasury.key,
+                ctx.accounts.wallet.key,
+                refund,
+            ),
+            &[
+                ctx.accounts.game_treasury.to_account_info(),
+                ctx.accounts.wallet.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    // Free the seat; join_game fills the first empty one, so the next player takes this colour
+    *game.slot_mut(color)? = PlayerSlot::zeroed();
+    game.player_count = game.player_count
+        .checked_sub(1)
+        .ok_or(HexoneError::Invalid)?;
+
+    // A game that never started doesn't count as played
+    if player.release(ctx.accounts.game.key()) {
+        player.games_played = player.games_played.saturating_sub(1);
+    }
+
+    emit!(PlayerLeft {
+        game_id: game.game_id,
+        wallet: wallet_key,
+        color,
+        refund,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::DEFAULT_JOIN_WINDOW_SECONDS;
+use crate::state::game::{Game, TileData, GAME_VERSION};
+use crate::state::game_v1::{GameV1, GAME_VERSION_V1};
+use crate::state::platform::Platform;
+use crate::error::HexoneError;
+use crate::events::GameMigrated;
+
+#[derive(Accounts)]
+pub struct MigrateGame<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    /// CHECK: a v1 Game account, which no longer deserializes as Game; checked below
+    #[account(mut, owner = crate::ID)]
+    pub game: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrite a v1 Game account (four fixed players, 144 inline tiles) into the PlayerSlot layout
+/// Attacks still open on a v1 game can't be resolved afterwards, so resolve or expire them first
+pub fn migrate_game(ctx: Context<MigrateGame>, game_id: u64) -> Result<()> {
+    // Derive and validate game PDA
+    let (expected_game, _bump) = Pubkey::find_program_address(
+        &[b"GAME-", game_id.to_le_bytes().as_ref()],
+        ctx.program_id,
+    );
+    require!(
+        expected_game == ctx.accounts.game.key(),
+        HexoneError::Invalid
+    );
+
+    let game_info = ctx.accounts.game.to_account_info();
+
+    // Copy the v1 game out before the account is resized and overwritten
+    let old: GameV1 = {
+        let data = game_info.try_borrow_data()?;
+        require!(data.len() == GameV1::LEN, HexoneError::Invalid);
+        require!(
+            &data[..Game::DISCRIMINATOR.len()] == Game::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        bytemuck::pod_read_unaligned(&data[Game::DISCRIMINATOR.len()..])
+    };
+    require!(old.version == GAME_VERSION_V1, HexoneError::Invalid);
+    require!(old.game_id == game_id, HexoneError::Invalid);
+    require!(
+        ctx.accounts.admin.key() == old.admin || ctx.accounts.admin.key() == ctx.accounts.platform.admin,
+        HexoneError::PlayerNotAuthorized
+    );
+
+    // Top up rent for the larger account, then resize it
+    let new_len = Game::space(old.rows, old.columns);
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(game_info.lamports());
+    if rent_due > 0 {
+        anchor_lang::solana_program::program::invoke(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.admin.key,
+                game_info.key,
+                rent_due,
+            ),
+            &[
+                ctx.accounts.admin.to_account_info(),
+                game_info.clone(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+    game_info.realloc(new_len, true)?;
+
+    {
+        let mut data = game_info.try_borrow_mut_data()?;
+        data[Game::DISCRIMINATOR.len()..].fill(0);
+        let (header, tiles) = data.split_at_mut(Game::LEN);
+        let game: &mut Game = bytemuck::from_bytes_mut(&mut header[Game::DISCRIMINATOR.len()..]);
+        let tile_data: &mut [TileData] = bytemuck::cast_slice_mut(tiles);
+        let current_time = Clock::get()?.unix_timestamp;
+        old.upgrade(ctx.accounts.platform.game_cost, game, tile_data, current_time)?;
+        // v1 lobbies get a fresh join window from the time they're migrated
+        game.join_deadline = current_time.saturating_add(DEFAULT_JOIN_WINDOW_SECONDS as i64);
+    }
+
+    emit!(GameMigrated {
+        game_id,
+        from_version: GAME_VERSION_V1,
+        to_version: GAME_VERSION,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::DEFAULT_PLATFORM_FEE_BPS;
+use crate::state::platform::{Platform, PLATFORM_VERSION, PLATFORM_VERSION_V1};
+use crate::error::HexoneError;
+use crate::events::PlatformMigrated;
+
+#[derive(Accounts)]
+pub struct MigratePlatform<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: a v1 Platform account, which is too short to deserialize as Platform; checked below
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"platform"],
+        bump,
+    )]
+    pub platform: UncheckedAccount<'info>,
+
+    /// Holds platform fees; funded with its rent reserve here, as create_platform does
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a v1 Platform account (no platform fee) to the current layout and set up its fee vault
+/// The v1 fields keep their offsets, so everything the platform has recorded carries over
+pub fn migrate_platform(ctx: Context<MigratePlatform>) -> Result<()> {
+    let platform_info = ctx.accounts.platform.to_account_info();
+
+    {
+        let data = platform_info.try_borrow_data()?;
+        require!(data.len() == Platform::V1_LEN, HexoneError::Invalid);
+        require!(
+            &data[..Platform::DISCRIMINATOR.len()] == Platform::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    // Top up rent for the larger account and the fee vault, then resize the platform
+    // Nothing is kept if the checks on the v1 fields below fail, as the whole instruction fails
+    let rent = Rent::get()?;
+    let platform_rent_due = rent
+        .minimum_balance(Platform::LEN)
+        .saturating_sub(platform_info.lamports());
+    let fee_vault_rent_due = rent
+        .minimum_balance(0)
+        .saturating_sub(ctx.accounts.fee_vault.lamports());
+    for (account, rent_due) in [
+        (platform_info.clone(), platform_rent_due),
+        (ctx.accounts.fee_vault.to_account_info(), fee_vault_rent_due),
+    ] {
+        if rent_due > 0 {
+            anchor_lang::solana_program::program::invoke(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    ctx.accounts.admin.key,
+                    account.key,
+                    rent_due,
+                ),
+                &[
+                    ctx.accounts.admin.to_account_info(),
+                    account.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+    }
+    platform_info.realloc(Platform::LEN, true)?;
+
+    {
+        let mut data = platform_info.try_borrow_mut_data()?;
+        let mut platform = Platform::try_deserialize(&mut &data[..])?;
+        require!(platform.version == PLATFORM_VERSION_V1, HexoneError::Invalid);
+        require!(
+            ctx.accounts.admin.key() == platform.admin,
+            HexoneError::PlayerNotAuthorized
+        );
+        platform.fees_collected = 0;
+        platform.unclaimed_collected = 0;
+        platform.fees_withdrawn = 0;
+        platform.fee_bps = DEFAULT_PLATFORM_FEE_BPS;
+        platform.fee_vault_bump = ctx.bumps.fee_vault;
+        platform.version = PLATFORM_VERSION;
+        platform.try_serialize(&mut &mut data[..])?;
+    }
+
+    emit!(PlatformMigrated {
+        from_version: PLATFORM_VERSION_V1,
+        to_version: PLATFORM_VERSION,
+    });
+
+    Ok(())
+}
@@ -6,7 +6,23 @@ pub mod move_resources;
 pub mod attack_tile;
 pub mod resolve_attack;
 pub mod add_resources;
-pub mod claim_prize;
+pub mod claim_payout;
+pub mod expire_payouts;
+pub mod commit_roll;
+pub mod reveal_roll;
+pub mod fulfill_randomness;
+pub mod expire_attack;
+pub mod reinforce_defense;
+pub mod coordinated_attack;
+pub mod migrate_game;
+pub mod tick_game;
+pub mod set_platform_fee;
+pub mod withdraw_fees;
+pub mod cancel_game;
+pub mod leave_game;
+pub mod finish_game_for_player;
+pub mod resign;
+pub mod migrate_platform;
 
 pub use create_game::*;
 pub use create_platform::*;
@@ -16,7 +32,23 @@ pub use move_resources::*;
 pub use attack_tile::*;
 pub use resolve_attack::*;
 pub use add_resources::*;
-pub use claim_prize::*;
+pub use claim_payout::*;
+pub use expire_payouts::*;
+pub use commit_roll::*;
+pub use reveal_roll::*;
+pub use fulfill_randomness::*;
+pub use expire_attack::*;
+pub use reinforce_defense::*;
+pub use coordinated_attack::*;
+pub use migrate_game::*;
+pub use tick_game::*;
+pub use set_platform_fee::*;
+pub use withdraw_fees::*;
+pub use cancel_game::*;
+pub use leave_game::*;
+pub use finish_game_for_player::*;
+pub use resign::*;
+pub use migrate_platform::*;
 
 
 
@@ -1,185 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::game::{Game, GAME_STATE_IN_PROGRESS, get_tile_tier, update_tier_count_on_gain, calculate_tier_bonus_xp, check_for_winner};
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS, get_tile_tier, update_tier_count_on_gain, check_for_winner};
+use crate::state::economy::accrue_xp;
 use crate::state::player::Player;
 use crate::error::HexoneError;
-
-/// Calculate new XP based on time elapsed
-/// Returns the XP to add: minutes * xp_per_minute_per_tile * number_of_tiles
-fn calculate_new_xp(
-    current_time: i64,
-    last_timestamp: i64,
-    xp_per_minute_per_tile: u32,
-    number_of_tiles: u32,
-) -> u32 {
-    let time_diff_seconds = current_time - last_timestamp;
-    
-    // Only calculate if more than 1 minute (60 seconds) has passed
-    if time_diff_seconds > 60 {
-        let minutes_elapsed = (time_diff_seconds / 60) as u32;
-        // Calculate XP: minutes * xp_per_minute_per_tile * number_of_tiles
-        minutes_elapsed
-            .checked_mul(xp_per_minute_per_tile)
-            .and_then(|x| x.checked_mul(number_of_tiles))
-            .unwrap_or(u32::MAX)
-    } else {
-        0
-    }
-}
-
-/// Update XP for all players based on time elapsed
-fn update_all_players_xp(game: &mut Game, current_time: i64) -> Result<()> {
-    let xp_per_minute_per_tile = game.xp_per_minute_per_tile;
-    
-    // Update player 1 XP
-    if game.xp_timestamp_player1 > 0 && current_time - game.xp_timestamp_player1 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player1;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player1,
-            xp_per_minute_per_tile,
-            game.tile_count_color1,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player1,
-            game.silver_tile_count_player1,
-            game.bronze_tile_count_player1,
-            game.iron_tile_count_player1,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player1 = game.xp_player1
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player1 = game.xp_timestamp_player1 + (minutes_elapsed as i64 * 60);
-    }
-    
-    // Update player 2 XP
-    if game.xp_timestamp_player2 > 0 && current_time - game.xp_timestamp_player2 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player2;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player2,
-            xp_per_minute_per_tile,
-            game.tile_count_color2,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player2,
-            game.silver_tile_count_player2,
-            game.bronze_tile_count_player2,
-            game.iron_tile_count_player2,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player2 = game.xp_player2
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player2 = game.xp_timestamp_player2 + (minutes_elapsed as i64 * 60);
-    }
-    
-    // Update player 3 XP
-    if game.xp_timestamp_player3 > 0 && current_time - game.xp_timestamp_player3 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player3;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player3,
-            xp_per_minute_per_tile,
-            game.tile_count_color3,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player3,
-            game.silver_tile_count_player3,
-            game.bronze_tile_count_player3,
-            game.iron_tile_count_player3,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player3 = game.xp_player3
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player3 = game.xp_timestamp_player3 + (minutes_elapsed as i64 * 60);
-    }
-    
-    // Update player 4 XP
-    if game.xp_timestamp_player4 > 0 && current_time - game.xp_timestamp_player4 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player4;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player4,
-            xp_per_minute_per_tile,
-            game.tile_count_color4,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player4,
-            game.silver_tile_count_player4,
-            game.bronze_tile_count_player4,
-            game.iron_tile_count_player4,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player4 = game.xp_player4
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player4 = game.xp_timestamp_player4 + (minutes_elapsed as i64 * 60);
-    }
-    
-    Ok(())
-}
+use crate::events::ResourcesMoved;
 
 /// Check if two tiles are adjacent in a hexagonal grid
 /// Tiles are indexed as: index = row * columns + column
@@ -260,7 +84,7 @@ pub(crate) fn move_resources(
     destination_tile_index: u16,
     resources_to_move: u16,
 ) -> Result<()> {
-    let game = &mut ctx.accounts.game.load_mut()?;
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
 
     // Check game state
     require!(
@@ -271,21 +95,12 @@ pub(crate) fn move_resources(
     // Check if player is in the game and determine their color and index
     // Use the player's wallet key (not the signer key) to find them in the game
     let player_wallet_key = ctx.accounts.player.wallet;
-    let (player_color, player_index) = if game.player1 == player_wallet_key {
-        (1u8, 1usize) // Red
-    } else if game.player2 == player_wallet_key {
-        (2u8, 2usize) // Yellow
-    } else if game.player3 == player_wallet_key {
-        (3u8, 3usize) // Green
-    } else if game.player4 == player_wallet_key {
-        (4u8, 4usize) // Blue
-    } else {
-        return Err(HexoneError::PlayerNotAuthorized.into());
-    };
+    let player_color = game.player_color(&player_wallet_key);
+    require!(player_color != 0, HexoneError::PlayerNotAuthorized);
 
     // Validate tile indices
     require!(
-        source_tile_index < 144 && destination_tile_index < 144,
+        source_tile_index < game.tile_count() && destination_tile_index < game.tile_count(),
         HexoneError::Invalid
     );
 
@@ -325,6 +140,12 @@ pub(crate) fn move_resources(
         );
     }
 
+    // Attackers can't withdraw from a tile while its attacks are pending
+    require!(
+        game.tile_data[source_tile_index as usize].pending_attacks == 0,
+        HexoneError::AttackingTileLocked
+    );
+
     // Check that source tile has at least 2 resources (must leave at least 1)
     require!(
         game.tile_data[source_tile_index as usize].resource_count >= 2,
@@ -356,7 +177,7 @@ pub(crate) fn move_resources(
     let current_time = clock.unix_timestamp;
     
     // Update XP for all players BEFORE changing tile counts (use old tile counts)
-    update_all_players_xp(game, current_time)?;
+    accrue_xp(game, current_time);
     
     // Check if any player has reached the winning XP limit
     check_for_winner(game, current_time)?;
@@ -368,36 +189,27 @@ pub(crate) fn move_resources(
         .ok_or(HexoneError::Invalid)?;
     game.tile_data[destination_tile_index as usize].color = player_color;
 
+    emit!(ResourcesMoved {
+        game_id: game.game_id,
+        color: player_color,
+        source_tile_index,
+        destination_tile_index,
+        resources_moved: resources_to_move,
+        source_resource_count: game.tile_data[source_tile_index as usize].resource_count,
+        destination_resource_count: game.tile_data[destination_tile_index as usize].resource_count,
+    });
+
     // If moving to an empty tile, increment tile count for the player's color
     // (This happens AFTER XP calculation, so XP was calculated with old tile count)
     if was_empty_tile {
-        match player_index {
-            1 => {
-                game.tile_count_color1 = game.tile_count_color1
-                    .checked_add(1)
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            2 => {
-                game.tile_count_color2 = game.tile_count_color2
-                    .checked_add(1)
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            3 => {
-                game.tile_count_color3 = game.tile_count_color3
-                    .checked_add(1)
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            4 => {
-                game.tile_count_color4 = game.tile_count_color4
-                    .checked_add(1)
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            _ => return Err(HexoneError::Invalid.into()),
-        }
+        let slot = game.slot_mut(player_color)?;
+        slot.tile_count = slot.tile_count
+            .checked_add(1)
+            .ok_or(HexoneError::Invalid)?;
         
         // Update tier count for the newly acquired tile
         let tier = get_tile_tier(destination_tile_index, rows, columns);
-        update_tier_count_on_gain(game, player_index, tier)?;
+        update_tier_count_on_gain(game, player_color, tier)?;
     }
 
     Ok(())
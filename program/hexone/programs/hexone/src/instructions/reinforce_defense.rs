@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::player::Player;
+use crate::state::defender::Defender;
+use crate::error::HexoneError;
+use crate::events::DefenseReinforced;
+use crate::randomness::reinforce_window_end_slot;
+
+// Import the adjacency check function from move_resources
+use crate::instructions::move_resources::are_tiles_adjacent;
+
+/// Move resources from an adjacent tile onto a tile that is under attack
+/// Only the defending colour can reinforce, and only during the game's reinforcement window
+/// before the rolls can be known; the committed amount counts towards the defence on resolve
+pub fn reinforce_defense(
+    ctx: Context<ReinforceDefense>,
+    source_tile_index: u16,
+    resources_to_commit: u16,
+) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let defender = &mut ctx.accounts.defender;
+    let wallet_key = ctx.accounts.player_wallet.key();
+    let clock = Clock::get()?;
+
+    // Check game state
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::Invalid
+    );
+
+    // Reinforcements close before resolution opens and before any randomness is known:
+    // SlotHashes rolls only use blocks after the window's last slot
+    let reinforce_window = game.reinforce_window();
+    require!(
+        clock.unix_timestamp - defender.attack_started_at < reinforce_window
+            && clock.slot <= reinforce_window_end_slot(defender.attack_started_slot, reinforce_window)
+            && !defender.vrf_fulfilled
+            && !defender.attacker_revealed
+            && !defender.defender_revealed,
+        HexoneError::ReinforcementWindowClosed
+    );
+
+    // Only the defending colour can reinforce
+    let defender_wallet = game.slot(defender.defender_tile_color)?.wallet;
+    require!(wallet_key == defender_wallet, HexoneError::PlayerNotAuthorized);
+
+    // Validate source tile: in bounds, adjacent to the defended tile and owned by the defender
+    let defended_tile_index = defender.defender_tile_index;
+    require!(source_tile_index < game.tile_count(), HexoneError::Invalid);
+    require!(
+        are_tiles_adjacent(
+            source_tile_index,
+            defended_tile_index,
+            game.rows,
+            game.columns
+        ),
+        HexoneError::Invalid
+    );
+    let source_tile = game.tile_data[source_tile_index as usize];
+    require!(
+        source_tile.color == defender.defender_tile_color,
+        HexoneError::Invalid
+    );
+    require!(
+        game.tile_data[defended_tile_index as usize].color == defender.defender_tile_color,
+        HexoneError::Invalid
+    );
+
+    // Tiles with their own pending attacks are locked
+    require!(
+        source_tile.pending_attacks == 0,
+        HexoneError::AttackingTileLocked
+    );
+
+    // Must commit at least 1 and leave at least 1 on the source tile
+    require!(
+        resources_to_commit >= 1 && resources_to_commit < source_tile.resource_count,
+        HexoneError::Invalid
+    );
+
+    // Move the resources onto the defended tile
+    game.tile_data[source_tile_index as usize].resource_count = source_tile.resource_count
+        .checked_sub(resources_to_commit)
+        .ok_or(HexoneError::Invalid)?;
+    let defended_resource_count = game.tile_data[defended_tile_index as usize].resource_count;
+    game.tile_data[defended_tile_index as usize].resource_count = defended_resource_count
+        .checked_add(resources_to_commit)
+        .ok_or(HexoneError::Invalid)?;
+
+    // Record the commitment so resolve_attack counts it towards the defence
+    defender.reinforced_resources = defender.reinforced_resources
+        .checked_add(resources_to_commit)
+        .ok_or(HexoneError::Invalid)?;
+
+    emit!(DefenseReinforced {
+        game_id: game.game_id,
+        color: defender.defender_tile_color,
+        source_tile_index,
+        defender_tile_index: defended_tile_index,
+        resources_committed: resources_to_commit,
+    });
+
+    msg!(
+        "Tile {} reinforced with {} resources from tile {}",
+        defended_tile_index,
+        resources_to_commit,
+        source_tile_index
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReinforceDefense<'info> {
+    /// CHECK: The defender's wallet (used for PDA derivation, not necessarily the signer)
+    pub player_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Signer must be either player's wallet or player's hotwallet
+    #[account(mut)]
+    pub signer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"player", player_wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == player_wallet.key() @ HexoneError::PlayerNotAuthorized,
+        constraint = (signer_wallet.key() == player.wallet || signer_wallet.key() == player.hotwallet) @ HexoneError::PlayerNotAuthorized
+    )]
+    pub player: Account<'info, Player>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"defender",
+            game.key().as_ref(),
+            defender.defender_tile_index.to_le_bytes().as_ref(),
+        ],
+        bump = defender.bump,
+        constraint = !defender.is_attack_resolved @ HexoneError::Invalid
+    )]
+    pub defender: Account<'info, Defender>,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS, check_for_winner};
+use crate::state::economy::accrue_xp;
+use crate::state::player::Player;
+use crate::error::HexoneError;
+use crate::events::PlayerResigned;
+
+#[derive(Accounts)]
+pub struct Resign<'info> {
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player", wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == wallet.key() @ HexoneError::PlayerNotAuthorized,
+    )]
+    pub player: Account<'info, Player>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+}
+
+/// Concede an in-progress game
+/// The player's tiles go neutral, or to `beneficiary` (a colour still in play) in games that allow
+/// it, and their XP stops where it is. They rank as eliminated and are free to join another game.
+/// If that leaves one player in play, that player wins
+pub fn resign(ctx: Context<Resign>, beneficiary: u8) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Settle XP on the board as it was, in case someone already reached the limit
+    accrue_xp(game, current_time);
+    check_for_winner(game, current_time)?;
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::InvalidGameState
+    );
+
+    let wallet_key = ctx.accounts.wallet.key();
+    let color = game.player_color(&wallet_key);
+    require!(color != 0, HexoneError::PlayerNotAuthorized);
+
+    let tiles = game.resign(color, beneficiary, current_time)?;
+    ctx.accounts.player.release(ctx.accounts.game.key());
+
+    emit!(PlayerResigned {
+        game_id: game.game_id,
+        wallet: wallet_key,
+        color,
+        beneficiary,
+        tiles,
+        timestamp: current_time,
+    });
+
+    // The last player in play wins
+    check_for_winner(game, current_time)?;
+
+    Ok(())
+}
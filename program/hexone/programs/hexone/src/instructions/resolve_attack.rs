@@ -1,232 +1,76 @@
 use anchor_lang::prelude::*;
-use crate::state::game::{Game, GAME_STATE_IN_PROGRESS, get_tile_tier, update_tier_count_on_gain, update_tier_count_on_loss, calculate_tier_bonus_xp, check_for_winner};
-use crate::state::defender::Defender;
-use crate::state::player::Player;
+use crate::state::game::{Game, GameBoard, GAME_STATE_IN_PROGRESS, color_name, get_tile_tier, update_tier_count_on_gain, update_tier_count_on_loss, check_for_winner};
+use crate::state::economy::accrue_xp;
+use crate::state::defender::{Defender, Forfeit};
 use crate::error::HexoneError;
-use crate::events::AttackResolved;
-use sha2::{Sha256, Digest};
+use crate::events::{AttackResolved, AttackVoided, TileCaptured};
+use crate::randomness::{RandomnessSource, RollInputs, reinforce_window_end_slot};
+use crate::combat::decide_attack;
+use crate::constants::ALLOCATION_BPS_DENOMINATOR;
 
-/// Calculate new XP based on time elapsed
-/// Returns the XP to add: minutes * xp_per_minute_per_tile * number_of_tiles
-fn calculate_new_xp(
-    current_time: i64,
-    last_timestamp: i64,
-    xp_per_minute_per_tile: u32,
-    number_of_tiles: u32,
-) -> u32 {
-    let time_diff_seconds = current_time - last_timestamp;
-    
-    // Only calculate if more than 1 minute (60 seconds) has passed
-    if time_diff_seconds > 60 {
-        let minutes_elapsed = (time_diff_seconds / 60) as u32;
-        // Calculate XP: minutes * xp_per_minute_per_tile * number_of_tiles
-        minutes_elapsed
-            .checked_mul(xp_per_minute_per_tile)
-            .and_then(|x| x.checked_mul(number_of_tiles))
-            .unwrap_or(u32::MAX)
-    } else {
-        0
+/// Resources the attacking side fights with
+/// A coordinated attack commits all but 1 from every contributor the attacker still holds,
+/// expressed like a single attacking tile (committed + 1) so the combat model treats both alike
+fn attacking_resources(game: &GameBoard, defender: &Defender) -> u16 {
+    if defender.contributor_count <= 1 {
+        return game.tile_data[defender.attacker_tile_index as usize].resource_count;
     }
+    let committed = defender
+        .contributor_tiles()
+        .iter()
+        .map(|tile_index| game.tile_data[*tile_index as usize])
+        .filter(|tile| tile.color == defender.attacker_tile_color)
+        .fold(0u16, |total, tile| total.saturating_add(tile.resource_count.saturating_sub(1)));
+    committed.saturating_add(1)
 }
 
-// Minimum time to wait before resolving (3 seconds)
-const MIN_ATTACK_DURATION: i64 = 3;
-
-/// Update XP for all players based on time elapsed
-fn update_all_players_xp(game: &mut Game, current_time: i64) -> Result<()> {
-    let xp_per_minute_per_tile = game.xp_per_minute_per_tile;
-    
-    // Update player 1 XP
-    if game.xp_timestamp_player1 > 0 && current_time - game.xp_timestamp_player1 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player1;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player1,
-            xp_per_minute_per_tile,
-            game.tile_count_color1,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player1,
-            game.silver_tile_count_player1,
-            game.bronze_tile_count_player1,
-            game.iron_tile_count_player1,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player1 = game.xp_player1
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player1 = game.xp_timestamp_player1 + (minutes_elapsed as i64 * 60);
-    }
-    
-    // Update player 2 XP
-    if game.xp_timestamp_player2 > 0 && current_time - game.xp_timestamp_player2 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player2;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player2,
-            xp_per_minute_per_tile,
-            game.tile_count_color2,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player2,
-            game.silver_tile_count_player2,
-            game.bronze_tile_count_player2,
-            game.iron_tile_count_player2,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player2 = game.xp_player2
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player2 = game.xp_timestamp_player2 + (minutes_elapsed as i64 * 60);
-    }
-    
-    // Update player 3 XP
-    if game.xp_timestamp_player3 > 0 && current_time - game.xp_timestamp_player3 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player3;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player3,
-            xp_per_minute_per_tile,
-            game.tile_count_color3,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player3,
-            game.silver_tile_count_player3,
-            game.bronze_tile_count_player3,
-            game.iron_tile_count_player3,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player3 = game.xp_player3
-            .checked_add(total_xp)
-            .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player3 = game.xp_timestamp_player3 + (minutes_elapsed as i64 * 60);
-    }
-    
-    // Update player 4 XP
-    if game.xp_timestamp_player4 > 0 && current_time - game.xp_timestamp_player4 > 60 {
-        let time_diff = current_time - game.xp_timestamp_player4;
-        let minutes_elapsed = (time_diff / 60) as u32;
-        
-        // Calculate base XP from tiles
-        let calculated_xp = calculate_new_xp(
-            current_time,
-            game.xp_timestamp_player4,
-            xp_per_minute_per_tile,
-            game.tile_count_color4,
-        );
-        
-        // Calculate tier bonus XP
-        let tier_bonus_xp = calculate_tier_bonus_xp(
-            minutes_elapsed,
-            game.gold_tile_count_player4,
-            game.silver_tile_count_player4,
-            game.bronze_tile_count_player4,
-            game.iron_tile_count_player4,
-            game.gold_tier_bonus_xp_per_min,
-            game.silver_tier_bonus_xp_per_min,
-            game.bronze_tier_bonus_xp_per_min,
-            game.iron_tier_bonus_xp_per_min,
-        )?;
-        
-        // Add both base XP and tier bonus XP
-        let total_xp = calculated_xp
-            .checked_add(tier_bonus_xp)
-            .ok_or(HexoneError::Invalid)?;
-        
-        game.xp_player4 = game.xp_player4
-            .checked_add(total_xp)
+/// Split the surviving resources of a winning coordinated attack
+/// Each contributor keeps 1 plus its declared share; returns what's left for the captured tile
+fn split_coordinated_resources(game: &mut GameBoard, defender: &Defender) -> Result<u16> {
+    let surviving = attacking_resources(game, defender) - 1;
+    // A capture has to leave the captured tile at least 1; with no contributor left holding
+    // resources there is nothing to move onto it
+    require!(surviving >= 1, HexoneError::Invalid);
+    let mut captured_resources = surviving;
+    for (i, tile_index) in defender.contributor_tiles().iter().enumerate() {
+        let tile = &mut game.tile_data[*tile_index as usize];
+        if tile.color != defender.attacker_tile_color {
+            continue;
+        }
+        let share = (surviving as u32 * defender.contributor_allocation_bps[i] as u32
+            / ALLOCATION_BPS_DENOMINATOR as u32) as u16;
+        tile.resource_count = share.checked_add(1).ok_or(HexoneError::Invalid)?;
+        captured_resources = captured_resources
+            .checked_sub(share)
             .ok_or(HexoneError::Invalid)?;
-        game.xp_timestamp_player4 = game.xp_timestamp_player4 + (minutes_elapsed as i64 * 60);
     }
-    
-    Ok(())
-}
-
-// Helper function to convert blockhash bytes to u64
-fn blockhash_to_u64(blockhash: &[u8; 32]) -> u64 {
-    // Take first 8 bytes and convert to u64
-    let mut bytes = [0u8; 8];
-    bytes.copy_from_slice(&blockhash[0..8]);
-    u64::from_le_bytes(bytes)
+    Ok(captured_resources)
 }
 
-// Helper function to simulate getting a blockhash from a recent block
-// Uses the current slot and an offset to simulate different recent blockhashes
-fn get_simulated_blockhash(slot: u64, offset: u64, attack_key: &Pubkey) -> [u8; 32] {
-    let mut hasher = Sha256::new();
-    hasher.update(&slot.to_le_bytes());
-    hasher.update(&offset.to_le_bytes());
-    hasher.update(attack_key.as_ref());
-    let hash = hasher.finalize();
-    let mut result = [0u8; 32];
-    result.copy_from_slice(&hash);
-    result
+/// Take a lost coordinated attack's hit from its contributors in order, each keeping 1
+fn apply_coordinated_loss(game: &mut GameBoard, defender: &Defender, hit_amount: u16) {
+    let mut remaining_hit = hit_amount;
+    for tile_index in defender.contributor_tiles() {
+        let tile = &mut game.tile_data[*tile_index as usize];
+        if remaining_hit == 0 {
+            break;
+        }
+        if tile.color != defender.attacker_tile_color {
+            continue;
+        }
+        let loss = remaining_hit.min(tile.resource_count.saturating_sub(1));
+        tile.resource_count -= loss;
+        remaining_hit -= loss;
+    }
 }
 
 pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
-    let game = &mut ctx.accounts.game.load_mut()?;
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
     let defender = &mut ctx.accounts.defender;
     let clock = Clock::get()?;
 
-    // Verify the signer is the attacker (player who initiated the attack)
-    // Find the attacker's wallet by matching the attacker_tile_color to the game players
-    let attacker_wallet = match defender.attacker_tile_color {
-        1 => game.player1,
-        2 => game.player2,
-        3 => game.player3,
-        4 => game.player4,
-        _ => return Err(HexoneError::Invalid.into()),
-    };
-    
-    require!(
-        ctx.accounts.player_wallet.key() == attacker_wallet,
-        HexoneError::PlayerNotAuthorized
-    );
+    // Resolution is permissionless: the attacker, the defender or a crank can resolve
+    // once the minimum duration has passed, so an attacker can't pin a tile by waiting
 
     // Check game state
     require!(
@@ -237,84 +81,106 @@ pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
     // Check attack hasn't been resolved
     require!(!defender.is_attack_resolved, HexoneError::Invalid);
 
-    // Check enough time has passed (3 seconds = ~6 blocks at 400ms per block)
+    // Resolution opens once the defender's reinforcement window has closed
+    let reinforce_window = game.reinforce_window();
     require!(
-        clock.unix_timestamp - defender.attack_started_at >= MIN_ATTACK_DURATION,
+        clock.unix_timestamp - defender.attack_started_at >= reinforce_window,
         HexoneError::Invalid
     );
 
-    // Get blockhashes for random number generation
-    // Attacker uses blockhashes at offsets 0, 4, 8 (simulating 1st, 5th, 9th recent blocks)
-    // Defender uses blockhashes at offsets 1, 5, 11 (simulating 2nd, 6th, 12th recent blocks)
-    
-    // Get current slot from clock
-    let current_slot = clock.slot;
-    let defender_key = defender.key();
-    
-    // For attacker: use offsets 0, 4, 8
-    let attacker_hash1 = get_simulated_blockhash(current_slot, 0, &defender_key);
-    let attacker_hash4 = get_simulated_blockhash(current_slot, 4, &defender_key);
-    let attacker_hash8 = get_simulated_blockhash(current_slot, 8, &defender_key);
-    
-    // For defender: use offsets 1, 5, 11
-    let defender_hash1 = get_simulated_blockhash(current_slot, 1, &defender_key);
-    let defender_hash5 = get_simulated_blockhash(current_slot, 5, &defender_key);
-    let defender_hash11 = get_simulated_blockhash(current_slot, 11, &defender_key);
-
-    // Convert blockhashes to numbers
-    let attacker_num1 = blockhash_to_u64(&attacker_hash1);
-    let attacker_num4 = blockhash_to_u64(&attacker_hash4);
-    let attacker_num8 = blockhash_to_u64(&attacker_hash8);
-    
-    let defender_num1 = blockhash_to_u64(&defender_hash1);
-    let defender_num5 = blockhash_to_u64(&defender_hash5);
-    let defender_num11 = blockhash_to_u64(&defender_hash11);
+    // An attack from tiles the attacker has since lost (captured, or with the colour eliminated)
+    // is void: it can't take a tile for a colour that isn't holding the ground it attacked from
+    if game.void_if_lost(defender)? {
+        msg!("Attack on tile {} is void, the attacker lost its attacking tiles", defender.defender_tile_index);
+        emit!(AttackVoided {
+            game_id: game.game_id,
+            attacker_color: defender.attacker_tile_color,
+            attacker_tile_index: defender.attacker_tile_index,
+            defender_tile_index: defender.defender_tile_index,
+        });
+        return Ok(());
+    }
 
-    // Hash the combined numbers
-    let mut attacker_hasher = Sha256::new();
-    attacker_hasher.update(&attacker_num1.to_le_bytes());
-    attacker_hasher.update(&attacker_num4.to_le_bytes());
-    attacker_hasher.update(&attacker_num8.to_le_bytes());
-    let attacker_hash = attacker_hasher.finalize();
-    
-    let mut defender_hasher = Sha256::new();
-    defender_hasher.update(&defender_num1.to_le_bytes());
-    defender_hasher.update(&defender_num5.to_le_bytes());
-    defender_hasher.update(&defender_num11.to_le_bytes());
-    let defender_hash = defender_hasher.finalize();
+    // Get attacker and defender resources before any changes
+    let defender_tile_idx = defender.defender_tile_index as usize;
+    let attacker_resources_before = attacking_resources(game, defender);
+    // The defence is what was on the tile when the attack started plus reinforce_defense commits
+    // (capped by what's still there); other moves onto the tile don't count for this attack
+    let defender_resources_before = game.tile_data[defender_tile_idx].resource_count.min(
+        defender
+            .defender_resources_at_start
+            .saturating_add(defender.reinforced_resources),
+    );
 
-    // Convert hash to number and mod 1000 (results in 0-999)
-    let attacker_value = u64::from_le_bytes([
-        attacker_hash[0], attacker_hash[1], attacker_hash[2], attacker_hash[3],
-        attacker_hash[4], attacker_hash[5], attacker_hash[6], attacker_hash[7],
-    ]) % 1000;
-    
-    let defender_value = u64::from_le_bytes([
-        defender_hash[0], defender_hash[1], defender_hash[2], defender_hash[3],
-        defender_hash[4], defender_hash[5], defender_hash[6], defender_hash[7],
-    ]) % 1000;
+    // Roll both sides with the game's randomness source
+    let defender_key = defender.key();
+    // Commit-reveal attacks missing a reveal wait for the deadline, then whoever
+    // didn't commit or reveal forfeits with the worst possible roll
+    if defender.commit_reveal && !(defender.attacker_revealed && defender.defender_revealed) {
+        require!(
+            clock.unix_timestamp >= defender.reveal_deadline,
+            HexoneError::RevealPending
+        );
+    }
+    let forfeit = defender.forfeit();
+    let (attacker_roll, defender_roll) = if let Some(forfeit) = forfeit {
+        match forfeit {
+            Forfeit::Attacker => (0u16, 999u16),
+            Forfeit::Defender => (999u16, 0u16),
+        }
+    } else {
+        // Commit-reveal attacks mix both revealed secrets in with the source's entropy
+        // (if neither side committed, the source rolls alone)
+        let mut player_entropy: Vec<u8> = Vec::new();
+        if defender.commit_reveal && defender.attacker_revealed && defender.defender_revealed {
+            player_entropy.extend_from_slice(&defender.attacker_secret);
+            player_entropy.extend_from_slice(&defender.defender_secret);
+        }
+        let randomness_source = RandomnessSource::from_u8(game.randomness_source)?;
+        let slot_hashes_data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        randomness_source.roll(&RollInputs {
+            current_slot: clock.slot,
+            roll_after_slot: reinforce_window_end_slot(defender.attack_started_slot, reinforce_window),
+            defender_key: &defender_key,
+            slot_hashes_data: &slot_hashes_data,
+            vrf_randomness: if defender.vrf_fulfilled {
+                Some(&defender.vrf_randomness)
+            } else {
+                None
+            },
+            player_entropy: &player_entropy,
+        })?
+    };
 
     // Store the random results (0-999)
-    defender.attacking_result = attacker_value as u16;
-    defender.defending_result = defender_value as u16;
-    
-    // Get attacker and defender resources before any changes
-    let attacker_tile_idx = defender.attacker_tile_index as usize;
-    let defender_tile_idx = defender.defender_tile_index as usize;
-    let attacker_resources_before = game.tile_data[attacker_tile_idx].resource_count;
-    let defender_resources_before = game.tile_data[defender_tile_idx].resource_count;
-    
-    // Determine winner (higher number wins)
-    let attacker_won = attacker_value > defender_value;
-    defender.attacker_won = attacker_won;
-    defender.is_attack_resolved = true;
-    
-    // Calculate the difference between results (0-999)
-    let difference = if attacker_won {
-        attacker_value - defender_value
+    defender.attacking_result = attacker_roll;
+    defender.defending_result = defender_roll;
+
+    // Determine winner with the game's combat model; a forfeit is always a maximal loss
+    // for the side that didn't commit or reveal. difference (0-999) is how decisive the win was
+    let (attacker_won, difference) = if let Some(forfeit) = forfeit {
+        (forfeit == Forfeit::Defender, 999u64)
     } else {
-        defender_value - attacker_value
+        decide_attack(
+            &game.combat_settings(),
+            attacker_resources_before,
+            defender_resources_before,
+            attacker_roll,
+            defender_roll,
+        )
     };
+    defender.attacker_won = attacker_won;
+    defender.is_attack_resolved = true;
+
+    // Unlock the attacking tiles and restart their cooldown from the resolution
+    let cooldown_until = clock.unix_timestamp + game.tile_attack_cooldown_seconds as i64;
+    for tile_index in defender.contributor_tiles() {
+        let tile = &mut game.tile_data[*tile_index as usize];
+        tile.pending_attacks = tile.pending_attacks.saturating_sub(1);
+        tile.attack_cooldown_until = cooldown_until;
+    }
+    let open_attacks = game.open_attacks_mut(defender.attacker_tile_color)?;
+    *open_attacks = open_attacks.saturating_sub(1);
     
     // Calculate hit amount based on difference
     // Minimum hit is always 1, maximum is max_hit_resource_count (5)
@@ -378,31 +244,22 @@ pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
     
     // Emit event with attacker and defender tile colors, resources, roll results, and hit count
     emit!(AttackResolved {
+        game_id: game.game_id,
+        attacker_tile_index: defender.attacker_tile_index,
+        defender_tile_index: defender.defender_tile_index,
+        attacker_won,
         attacker_tile_color: defender.attacker_tile_color,
         attacker_resources: attacker_resources_before,
-        attacker_roll_result: attacker_value as u16,
+        attacker_roll_result: attacker_roll,
         defender_tile_color: defender.defender_tile_color,
         defender_resources: defender_resources_before,
-        defender_roll_result: defender_value as u16,
+        defender_roll_result: defender_roll,
         hit_resource_count,
     });
 
     // Get color names for logging
-    let attacker_color_name = match defender.attacker_tile_color {
-        1 => "Red",
-        2 => "Yellow",
-        3 => "Green",
-        4 => "Blue",
-        _ => "Unknown",
-    };
-    
-    let defender_color_name = match defender.defender_tile_color {
-        1 => "Red",
-        2 => "Yellow",
-        3 => "Green",
-        4 => "Blue",
-        _ => "Unknown",
-    };
+    let attacker_color_name = color_name(defender.attacker_tile_color);
+    let defender_color_name = color_name(defender.defender_tile_color);
 
     // Convert tile indices to (x, y) coordinates
     let columns = game.columns as usize;
@@ -425,17 +282,17 @@ pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
         msg!(
             "Attacker {} ({}) won against {} ({})",
             attacker_color_name,
-            attacker_value,
+            attacker_roll,
             defender_color_name,
-            defender_value
+            defender_roll
         );
     } else {
         msg!(
             "Attacker {} ({}) lost against {} ({})",
             attacker_color_name,
-            attacker_value,
+            attacker_roll,
             defender_color_name,
-            defender_value
+            defender_roll
         );
     }
 
@@ -448,10 +305,15 @@ pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
     let defender_resources = game.tile_data[defender_tile_idx].resource_count;
 
     // Update XP for all players BEFORE changing tile counts (use old tile counts)
-    update_all_players_xp(game, clock.unix_timestamp)?;
+    accrue_xp(game, clock.unix_timestamp);
     
     // Check if any player has reached the winning XP limit
     check_for_winner(game, clock.unix_timestamp)?;
+
+    // A winner ends the game before this attack changes the board
+    if game.game_state != GAME_STATE_IN_PROGRESS {
+        return Ok(());
+    }
     
     if attacker_won {
         // Attacker wins: defender loses hit_resource_count resources
@@ -473,93 +335,65 @@ pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
             let old_defender_color = game.tile_data[defender_tile_idx].color;
             let attacker_color = defender.attacker_tile_color;
             
-            // Get defender and attacker player indices
-            let old_defender_index = match old_defender_color {
-                1 => 1,
-                2 => 2,
-                3 => 3,
-                4 => 4,
-                _ => return Err(HexoneError::Invalid.into()),
-            };
-            let attacker_index = match attacker_color {
-                1 => 1,
-                2 => 2,
-                3 => 3,
-                4 => 4,
-                _ => return Err(HexoneError::Invalid.into()),
-            };
-            
             // Get tier of the tile being transferred
             let tier = get_tile_tier(defender_tile_idx as u16, game.rows, game.columns);
             
-            // Calculate resources to move: all but 1 from attacker's tile
-            let resources_to_move = current_attacker_resources
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
+            let resources_to_move = if defender.contributor_count > 1 {
+                // Coordinated attack: split the survivors by the declared allocation
+                split_coordinated_resources(game, defender)?
+            } else {
+                // Calculate resources to move: all but 1 from attacker's tile
+                let resources_to_move = current_attacker_resources
+                    .checked_sub(1)
+                    .ok_or(HexoneError::Invalid)?;
+
+                // Keep 1 resource on the attacker's original tile
+                game.tile_data[attacker_tile_idx].resource_count = 1;
+                resources_to_move
+            };
             
-            // Update tile ownership
+            // Update tile ownership; the captured tile can't attack straight away
             game.tile_data[defender_tile_idx].color = attacker_color;
-            // Move all but 1 resource from attacker to the newly captured tile
+            game.tile_data[defender_tile_idx].attack_cooldown_until = cooldown_until;
+            // Move the surviving attacking resources to the newly captured tile
             game.tile_data[defender_tile_idx].resource_count = resources_to_move;
-            
-            // Keep 1 resource on the attacker's original tile
-            game.tile_data[attacker_tile_idx].resource_count = 1;
+
+            emit!(TileCaptured {
+                game_id: game.game_id,
+                tile_index: defender.defender_tile_index,
+                previous_color: old_defender_color,
+                new_color: attacker_color,
+                attacker_tile_index: defender.attacker_tile_index,
+                resource_count: resources_to_move,
+            });
             
             // Update tile counts: decrement defender's count, increment attacker's count
             // (This happens AFTER XP calculation, so XP was calculated with old tile counts)
-            match old_defender_color {
-                1 => {
-                    game.tile_count_color1 = game.tile_count_color1
-                        .checked_sub(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                2 => {
-                    game.tile_count_color2 = game.tile_count_color2
-                        .checked_sub(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                3 => {
-                    game.tile_count_color3 = game.tile_count_color3
-                        .checked_sub(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                4 => {
-                    game.tile_count_color4 = game.tile_count_color4
-                        .checked_sub(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                _ => {}
+            // A tile its owner left neutral by resigning has nobody to take it from
+            if old_defender_color != 0 {
+                let defender_slot = game.slot_mut(old_defender_color)?;
+                defender_slot.tile_count = defender_slot.tile_count
+                    .checked_sub(1)
+                    .ok_or(HexoneError::Invalid)?;
+                game.record_elimination(old_defender_color, clock.unix_timestamp)?;
+                update_tier_count_on_loss(game, old_defender_color, tier)?;
             }
             
             // Increment attacker's tile count
-            match attacker_color {
-                1 => {
-                    game.tile_count_color1 = game.tile_count_color1
-                        .checked_add(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                2 => {
-                    game.tile_count_color2 = game.tile_count_color2
-                        .checked_add(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                3 => {
-                    game.tile_count_color3 = game.tile_count_color3
-                        .checked_add(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                4 => {
-                    game.tile_count_color4 = game.tile_count_color4
-                        .checked_add(1)
-                        .ok_or(HexoneError::Invalid)?;
-                }
-                _ => return Err(HexoneError::Invalid.into()),
-            }
+            let attacker_slot = game.slot_mut(attacker_color)?;
+            attacker_slot.tile_count = attacker_slot.tile_count
+                .checked_add(1)
+                .ok_or(HexoneError::Invalid)?;
             
-            // Update tier counts: decrement from old owner, increment to new owner
-            update_tier_count_on_loss(game, old_defender_index, tier)?;
-            update_tier_count_on_gain(game, attacker_index, tier)?;
+            // Update tier counts: the old owner's loss is taken above
+            update_tier_count_on_gain(game, attacker_color, tier)?;
+
+            // Taking a colour's last tile can leave the attacker as the last one standing
+            check_for_winner(game, clock.unix_timestamp)?;
         }
+    } else if defender.contributor_count > 1 {
+        // Defender wins a coordinated attack: the hit is spread across the contributors
+        apply_coordinated_loss(game, defender, hit_resource_count.max(1) as u16);
     } else {
         // Defender wins: attacker loses hit_resource_count resources
         // Attacker must keep at least 1 resource to maintain tile ownership
@@ -589,28 +423,15 @@ pub fn resolve_attack(ctx: Context<ResolveAttack>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct ResolveAttack<'info> {
-    /// CHECK: The attacker's wallet (used for PDA derivation, not necessarily the signer)
-    pub player_wallet: UncheckedAccount<'info>,
-
-    /// CHECK: Signer must be either attacker's wallet or attacker's hotwallet
-    #[account(mut)]
-    pub signer_wallet: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"player", player_wallet.key().as_ref()],
-        bump = player.bump,
-        constraint = player.wallet == player_wallet.key() @ HexoneError::PlayerNotAuthorized,
-        constraint = (signer_wallet.key() == player.wallet || signer_wallet.key() == player.hotwallet) @ HexoneError::PlayerNotAuthorized
-    )]
-    pub player: Account<'info, Player>,
+    /// Anyone can resolve once the game's reinforcement window has passed
+    pub caller: Signer<'info>,
 
     #[account(mut)]
     pub game: AccountLoader<'info, Game>,
 
     #[account(
         mut,
-        close = destination,  // Close the account and send rent to destination
+        close = payer,  // Rent goes back to whoever created the attack
         seeds = [
             b"defender",
             game.key().as_ref(),
@@ -621,8 +442,15 @@ pub struct ResolveAttack<'info> {
     )]
     pub defender: Account<'info, Defender>,
     
-    /// CHECK: Destination account to receive rent from closed defender account
-    #[account(mut)]
-    pub destination: UncheckedAccount<'info>,
+    /// CHECK: Original payer of the defender account, checked against the stored payer
+    #[account(
+        mut,
+        address = defender.payer @ HexoneError::Invalid
+    )]
+    pub payer: UncheckedAccount<'info>,
+
+    /// CHECK: SlotHashes sysvar, checked by address and read as raw bytes
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
 }
 
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS};
+use crate::state::player::Player;
+use crate::state::defender::Defender;
+use crate::error::HexoneError;
+use sha2::{Sha256, Digest};
+
+/// Commitment for a commit-reveal roll: sha256(secret || wallet)
+/// Binding the wallet stops one side from copying the other's commitment
+pub fn roll_commitment(secret: &[u8; 32], wallet: &Pubkey) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    hasher.update(wallet.as_ref());
+    let hash = hasher.finalize();
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&hash);
+    result
+}
+
+pub fn reveal_roll(ctx: Context<RevealRoll>, secret: [u8; 32]) -> Result<()> {
+    let game = &ctx.accounts.game.load()?;
+    let defender = &mut ctx.accounts.defender;
+    let wallet_key = ctx.accounts.player_wallet.key();
+    let clock = Clock::get()?;
+
+    // Check game state
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::Invalid
+    );
+
+    require!(defender.commit_reveal, HexoneError::CommitRevealDisabled);
+    require!(
+        clock.unix_timestamp < defender.reveal_deadline,
+        HexoneError::RevealWindowClosed
+    );
+
+    // Nobody reveals until both secrets are locked in, otherwise the second
+    // side could pick their secret after seeing the first one
+    require!(
+        defender.attacker_commitment != [0u8; 32] && defender.defender_commitment != [0u8; 32],
+        HexoneError::CommitmentsIncomplete
+    );
+
+    // Find the attacker's and defender's wallets from the tile colors
+    let attacker_wallet = game.slot(defender.attacker_tile_color)?.wallet;
+    let defender_wallet = game.slot(defender.defender_tile_color)?.wallet;
+
+    let commitment = roll_commitment(&secret, &wallet_key);
+
+    if wallet_key == attacker_wallet {
+        require!(!defender.attacker_revealed, HexoneError::Invalid);
+        require!(
+            commitment == defender.attacker_commitment,
+            HexoneError::InvalidReveal
+        );
+        defender.attacker_secret = secret;
+        defender.attacker_revealed = true;
+    } else if wallet_key == defender_wallet {
+        require!(!defender.defender_revealed, HexoneError::Invalid);
+        require!(
+            commitment == defender.defender_commitment,
+            HexoneError::InvalidReveal
+        );
+        defender.defender_secret = secret;
+        defender.defender_revealed = true;
+    } else {
+        return Err(HexoneError::PlayerNotAuthorized.into());
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevealRoll<'info> {
+    /// CHECK: The attacker's or defender's wallet (used for PDA derivation, not necessarily the signer)
+    pub player_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: Signer must be either player's wallet or player's hotwallet
+    #[account(mut)]
+    pub signer_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"player", player_wallet.key().as_ref()],
+        bump = player.bump,
+        constraint = player.wallet == player_wallet.key() @ HexoneError::PlayerNotAuthorized,
+        constraint = (signer_wallet.key() == player.wallet || signer_wallet.key() == player.hotwallet) @ HexoneError::PlayerNotAuthorized
+    )]
+    pub player: Account<'info, Player>,
+
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"defender",
+            game.key().as_ref(),
+            defender.defender_tile_index.to_le_bytes().as_ref(),
+        ],
+        bump = defender.bump,
+        constraint = !defender.is_attack_resolved @ HexoneError::Invalid
+    )]
+    pub defender: Account<'info, Defender>,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_PLATFORM_FEE_BPS;
+use crate::state::platform::Platform;
+use crate::error::HexoneError;
+use crate::events::PlatformFeeUpdated;
+
+#[derive(Accounts)]
+pub struct SetPlatformFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        has_one = admin @ HexoneError::PlayerNotAuthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+}
+
+/// Set the fee taken from the prize pool of games created from now on
+/// Games already created keep the fee they were created with
+pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= MAX_PLATFORM_FEE_BPS, HexoneError::InvalidPlatformFee);
+
+    let platform = &mut ctx.accounts.platform;
+    let old_fee_bps = platform.fee_bps;
+    platform.fee_bps = fee_bps;
+
+    emit!(PlatformFeeUpdated {
+        old_fee_bps,
+        new_fee_bps: fee_bps,
+    });
+
+    Ok(())
+}
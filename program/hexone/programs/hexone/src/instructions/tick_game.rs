@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::game::{Game, GAME_STATE_IN_PROGRESS, check_for_winner};
+use crate::state::economy::{accrue_resources, accrue_xp};
+use crate::constants::MIN_TICK_INTERVAL_SECONDS;
+use crate::error::HexoneError;
+use crate::events::GameTicked;
+
+#[derive(Accounts)]
+pub struct TickGame<'info> {
+    /// Anyone can tick a game; they receive the game's crank reward
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub game: AccountLoader<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [b"game_treasury", game.key().as_ref()],
+        bump,
+    )]
+    pub game_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accrue resources and XP, and run the winner check, without a player having to move
+/// Lets keeper bots drive idle games to completion. Only succeeds once the game has gone
+/// MIN_TICK_INTERVAL_SECONDS without accruing, or when it finds the winner, so the crank
+/// reward is paid at most once a minute, and only up to the game's cap (see Game::crank_reward_due)
+pub fn tick_game(ctx: Context<TickGame>) -> Result<()> {
+    let game = &mut Game::load_board_mut(&ctx.accounts.game)?;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        game.game_state == GAME_STATE_IN_PROGRESS,
+        HexoneError::InvalidGameState
+    );
+
+    // Any instruction that touches the game accrues XP, so the latest XP timestamp is the last accrual
+    let last_accrual = game
+        .seated_players()
+        .iter()
+        .map(|slot| slot.xp_timestamp)
+        .max()
+        .unwrap_or_default();
+    let idle = current_time - last_accrual >= MIN_TICK_INTERVAL_SECONDS;
+
+    accrue_resources(game, current_time);
+    accrue_xp(game, current_time);
+    check_for_winner(game, current_time)?;
+
+    require!(
+        idle || game.game_state != GAME_STATE_IN_PROGRESS,
+        HexoneError::TickTooEarly
+    );
+
+    // Pay the crank reward if the game's cap allows it and the treasury can cover it and stay rent exempt
+    let treasury_balance = ctx.accounts.game_treasury.lamports();
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let reward_due = game.crank_reward_due();
+    let reward = if reward_due > 0 && treasury_balance >= rent_exempt_minimum.saturating_add(reward_due) {
+        reward_due
+    } else {
+        0
+    };
+
+    if reward > 0 {
+        game.crank_rewards_paid += reward;
+
+        let game_key = ctx.accounts.game.key();
+        let seeds = &[
+            b"game_treasury",
+            game_key.as_ref(),
+            &[ctx.bumps.game_treasury],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        anchor_lang::solana_program::program::invoke_signed(
+            &anchor_lang::solana_program::system_instruction::transfer(
+                ctx.accounts.game_treasury.key,
+                ctx.accounts.cranker.key,
+                reward,
+            ),
+            &[
+                ctx.accounts.game_treasury.to_account_info(),
+                ctx.accounts.cranker.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    emit!(GameTicked {
+        game_id: game.game_id,
+        cranker: ctx.accounts.cranker.key(),
+        reward,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::platform::Platform;
+use crate::error::HexoneError;
+use crate::events::FeesWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        has_one = admin @ HexoneError::PlayerNotAuthorized,
+    )]
+    pub platform: Account<'info, Platform>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_vault"],
+        bump = platform.fee_vault_bump,
+    )]
+    pub fee_vault: SystemAccount<'info>,
+
+    /// Any wallet the admin chooses to pay out to
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Move collected fees out of the fee vault; it keeps its rent reserve
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let available = ctx.accounts.fee_vault.lamports().saturating_sub(rent_exempt_minimum);
+    require!(amount > 0 && amount <= available, HexoneError::InsufficientFees);
+
+    let seeds = &[b"fee_vault".as_ref(), &[ctx.accounts.platform.fee_vault_bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            ctx.accounts.fee_vault.key,
+            ctx.accounts.destination.key,
+            amount,
+        ),
+        &[
+            ctx.accounts.fee_vault.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    let platform = &mut ctx.accounts.platform;
+    platform.fees_withdrawn = platform.fees_withdrawn
+        .checked_add(amount)
+        .ok_or(HexoneError::Invalid)?;
+
+    emit!(FeesWithdrawn {
+        destination: ctx.accounts.destination.key(),
+        amount,
+        fees_withdrawn: platform.fees_withdrawn,
+    });
+
+    Ok(())
+}
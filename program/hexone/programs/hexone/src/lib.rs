@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 
+pub mod combat;
 pub mod constants;
 pub mod error;
 pub mod events;
 pub mod instructions;
+pub mod randomness;
 pub mod state;
 
 pub use state::*;
@@ -17,8 +19,8 @@ declare_id!("4hCMsw4pRN8VsyPg6USUEyEmnX5VTApEAWyEmMdrrtGj");
 pub mod hexone {
     use super::*;
 
-    pub fn create_game(ctx: Context<CreateGame>) -> Result<()> {
-        instructions::create_game(ctx)
+    pub fn create_game(ctx: Context<CreateGame>, config: GameConfig) -> Result<()> {
+        instructions::create_game(ctx, config)
     }
 
     pub fn create_platform(ctx: Context<CreatePlatform>) -> Result<()> {
@@ -33,6 +35,18 @@ pub mod hexone {
         instructions::join_game(ctx, game_id)
     }
 
+    pub fn leave_game(ctx: Context<LeaveGame>) -> Result<()> {
+        instructions::leave_game(ctx)
+    }
+
+    pub fn finish_game_for_player(ctx: Context<FinishGameForPlayer>) -> Result<()> {
+        instructions::finish_game_for_player(ctx)
+    }
+
+    pub fn resign(ctx: Context<Resign>, beneficiary: u8) -> Result<()> {
+        instructions::resign(ctx, beneficiary)
+    }
+
     pub fn move_resources(
         ctx: Context<MoveResources>,
         source_tile_index: u16,
@@ -54,6 +68,44 @@ pub mod hexone {
         instructions::resolve_attack(ctx)
     }
 
+    pub fn commit_roll(ctx: Context<CommitRoll>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_roll(ctx, commitment)
+    }
+
+    pub fn reveal_roll(ctx: Context<RevealRoll>, secret: [u8; 32]) -> Result<()> {
+        instructions::reveal_roll(ctx, secret)
+    }
+
+    pub fn fulfill_randomness(ctx: Context<FulfillRandomness>, randomness: [u8; 32]) -> Result<()> {
+        instructions::fulfill_randomness(ctx, randomness)
+    }
+
+    pub fn expire_attack(ctx: Context<ExpireAttack>) -> Result<()> {
+        instructions::expire_attack(ctx)
+    }
+
+    pub fn coordinated_attack(
+        ctx: Context<CoordinatedAttack>,
+        defender_tile_index: u16,
+        contributor_tile_indices: Vec<u16>,
+        contributor_allocation_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::coordinated_attack(
+            ctx,
+            defender_tile_index,
+            contributor_tile_indices,
+            contributor_allocation_bps,
+        )
+    }
+
+    pub fn reinforce_defense(
+        ctx: Context<ReinforceDefense>,
+        source_tile_index: u16,
+        resources_to_commit: u16,
+    ) -> Result<()> {
+        instructions::reinforce_defense(ctx, source_tile_index, resources_to_commit)
+    }
+
     pub fn add_resources(
         ctx: Context<AddResources>,
         tile_index: u16,
@@ -62,7 +114,35 @@ pub mod hexone {
         instructions::add_resources(ctx, tile_index, resources_to_add)
     }
 
-    pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
-        instructions::claim_prize(ctx)
+    pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
+        instructions::claim_payout(ctx)
+    }
+
+    pub fn expire_payouts(ctx: Context<ExpirePayouts>) -> Result<()> {
+        instructions::expire_payouts(ctx)
+    }
+
+    pub fn tick_game(ctx: Context<TickGame>) -> Result<()> {
+        instructions::tick_game(ctx)
+    }
+
+    pub fn migrate_game(ctx: Context<MigrateGame>, game_id: u64) -> Result<()> {
+        instructions::migrate_game(ctx, game_id)
+    }
+
+    pub fn set_platform_fee(ctx: Context<SetPlatformFee>, fee_bps: u16) -> Result<()> {
+        instructions::set_platform_fee(ctx, fee_bps)
+    }
+
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount)
+    }
+
+    pub fn migrate_platform(ctx: Context<MigratePlatform>) -> Result<()> {
+        instructions::migrate_platform(ctx)
+    }
+
+    pub fn cancel_game<'info>(ctx: Context<'_, '_, 'info, 'info, CancelGame<'info>>) -> Result<()> {
+        instructions::cancel_game(ctx)
     }
 }
@@ -0,0 +1,420 @@
+use anchor_lang::prelude::*;
+use sha2::{Sha256, Digest};
+
+use crate::constants::ATTACK_EXPIRY_SECONDS;
+use crate::error::HexoneError;
+
+// Randomness sources a game can use to roll attacks (stored in Game::randomness_source)
+pub const RANDOMNESS_SOURCE_HASH: u8 = 0;        // Legacy slot-derived hash (predictable, casual games only)
+pub const RANDOMNESS_SOURCE_SLOT_HASHES: u8 = 1; // Recent blockhashes from the SlotHashes sysvar
+pub const RANDOMNESS_SOURCE_VRF: u8 = 2;         // External VRF oracle fulfilling each attack
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RandomnessSource {
+    Hash,
+    SlotHashes,
+    Vrf,
+}
+
+/// Everything a randomness source may need to roll an attack
+pub struct RollInputs<'a> {
+    pub current_slot: u64,
+    pub roll_after_slot: u64,                 // Last slot of the attack's reinforcement window (see reinforce_window_end_slot)
+    pub defender_key: &'a Pubkey,
+    pub slot_hashes_data: &'a [u8],
+    pub vrf_randomness: Option<&'a [u8; 32]>, // None until the oracle has fulfilled the attack
+    pub player_entropy: &'a [u8],             // Both revealed secrets for commit-reveal attacks, empty otherwise
+}
+
+impl RandomnessSource {
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            RANDOMNESS_SOURCE_HASH => Ok(RandomnessSource::Hash),
+            RANDOMNESS_SOURCE_SLOT_HASHES => Ok(RandomnessSource::SlotHashes),
+            RANDOMNESS_SOURCE_VRF => Ok(RandomnessSource::Vrf),
+            _ => Err(HexoneError::InvalidRandomnessSource.into()),
+        }
+    }
+
+    /// Roll the attacker and defender results (0-999 each)
+    pub fn roll(&self, inputs: &RollInputs) -> Result<(u16, u16)> {
+        match self {
+            RandomnessSource::Hash => Ok(roll_attack_from_slot_hash(
+                inputs.current_slot,
+                inputs.defender_key,
+                inputs.player_entropy,
+            )),
+            RandomnessSource::SlotHashes => roll_attack_from_slot_hashes(
+                inputs.slot_hashes_data,
+                inputs.roll_after_slot,
+                inputs.defender_key,
+                inputs.player_entropy,
+            ),
+            RandomnessSource::Vrf => {
+                let randomness = inputs
+                    .vrf_randomness
+                    .ok_or(HexoneError::RandomnessNotFulfilled)?;
+                Ok(roll_attack_from_vrf(randomness, inputs.defender_key, inputs.player_entropy))
+            }
+        }
+    }
+}
+
+// Reduce the first 8 bytes of a hash to a roll result (0-999)
+fn hash_to_roll(hash: &[u8]) -> u16 {
+    let value = u64::from_le_bytes([
+        hash[0], hash[1], hash[2], hash[3],
+        hash[4], hash[5], hash[6], hash[7],
+    ]) % 1000;
+    value as u16
+}
+
+// Helper function to convert blockhash bytes to u64
+fn blockhash_to_u64(blockhash: &[u8; 32]) -> u64 {
+    // Take first 8 bytes and convert to u64
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&blockhash[0..8]);
+    u64::from_le_bytes(bytes)
+}
+
+// Helper function to simulate getting a blockhash from a recent block
+// Uses the current slot and an offset to simulate different recent blockhashes
+fn get_simulated_blockhash(slot: u64, offset: u64, attack_key: &Pubkey) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(&slot.to_le_bytes());
+    hasher.update(&offset.to_le_bytes());
+    hasher.update(attack_key.as_ref());
+    let hash = hasher.finalize();
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&hash);
+    result
+}
+
+// Legacy roll: hashes the current slot with fixed offsets (0, 4, 8 for the attacker,
+// 1, 5, 11 for the defender). Anyone can compute this ahead of time, so it is only
+// suitable for casual games where grinding the resolve slot doesn't matter.
+fn roll_from_simulated_blockhashes(slot: u64, offsets: &[u64; 3], defender_key: &Pubkey, player_entropy: &[u8]) -> u16 {
+    let mut hasher = Sha256::new();
+    for offset in offsets.iter() {
+        let blockhash = get_simulated_blockhash(slot, *offset, defender_key);
+        hasher.update(&blockhash_to_u64(&blockhash).to_le_bytes());
+    }
+    hasher.update(player_entropy);
+    hash_to_roll(&hasher.finalize())
+}
+
+pub(crate) fn roll_attack_from_slot_hash(slot: u64, defender_key: &Pubkey, player_entropy: &[u8]) -> (u16, u16) {
+    (
+        roll_from_simulated_blockhashes(slot, &[0, 4, 8], defender_key, player_entropy),
+        roll_from_simulated_blockhashes(slot, &[1, 5, 11], defender_key, player_entropy),
+    )
+}
+
+// VRF roll: each side hashes the oracle's randomness with its own tag
+fn roll_from_vrf(randomness: &[u8; 32], side_tag: &[u8], defender_key: &Pubkey, player_entropy: &[u8]) -> u16 {
+    let mut hasher = Sha256::new();
+    hasher.update(randomness);
+    hasher.update(side_tag);
+    hasher.update(defender_key.as_ref());
+    hasher.update(player_entropy);
+    hash_to_roll(&hasher.finalize())
+}
+
+pub(crate) fn roll_attack_from_vrf(randomness: &[u8; 32], defender_key: &Pubkey, player_entropy: &[u8]) -> (u16, u16) {
+    (
+        roll_from_vrf(randomness, b"attacker", defender_key, player_entropy),
+        roll_from_vrf(randomness, b"defender", defender_key, player_entropy),
+    )
+}
+
+// Number of blocks after the reinforcement window that must exist before an attack can be rolled
+const ROLL_BLOCK_COUNT: usize = 12;
+
+// Attacker uses the 1st, 5th and 9th blocks after the window
+const ATTACKER_BLOCK_OFFSETS: [usize; 3] = [0, 4, 8];
+
+// Defender uses the 2nd, 6th and 12th blocks after the window
+const DEFENDER_BLOCK_OFFSETS: [usize; 3] = [1, 5, 11];
+
+// SlotHashes layout: u64 entry count followed by (u64 slot, [u8; 32] hash) entries, newest first
+const SLOT_HASHES_COUNT_LEN: usize = 8;
+const SLOT_HASH_ENTRY_LEN: usize = 8 + 32;
+
+// Target slot time, used to turn windows in seconds into slots
+pub const SLOT_DURATION_MS: u64 = 400;
+
+// SlotHashes keeps the last 512 slots, about 204 seconds at the 400ms target slot time
+// After that an attack's blocks are gone and it fails with AttackRandomnessExpired
+pub const SLOT_HASHES_MAX_ENTRIES: usize = 512;
+pub const SLOT_HASHES_HORIZON_SECONDS: i64 = 204;
+
+// Attacks must become expirable before they can get stuck unresolvable
+const _: () = assert!(ATTACK_EXPIRY_SECONDS < SLOT_HASHES_HORIZON_SECONDS);
+
+/// Last slot of an attack's reinforcement window. SlotHashes rolls only use blocks after it,
+/// so none of them exist while the defender can still reinforce
+pub fn reinforce_window_end_slot(attack_started_slot: u64, reinforce_window_seconds: i64) -> u64 {
+    let window_slots = (reinforce_window_seconds.max(0) as u64 * 1_000).div_ceil(SLOT_DURATION_MS);
+    attack_started_slot.saturating_add(window_slots)
+}
+
+// Helper function to read a little-endian u64 from a byte slice
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+/// Collect the blockhashes of the blocks produced after `roll_after_slot`, oldest first.
+/// The SlotHashes sysvar is too large to deserialize on-chain, so the raw account data is read directly.
+/// Fails if the oldest entry is newer than that slot (history has rolled past it), since
+/// the set of blocks used for the roll would otherwise depend on when resolve is called.
+fn blockhashes_after_slot(slot_hashes_data: &[u8], roll_after_slot: u64) -> Result<Vec<[u8; 32]>> {
+    require!(
+        slot_hashes_data.len() >= SLOT_HASHES_COUNT_LEN,
+        HexoneError::Invalid
+    );
+    let entry_count = read_u64(slot_hashes_data, 0) as usize;
+    let entries_len = entry_count
+        .checked_mul(SLOT_HASH_ENTRY_LEN)
+        .and_then(|x| x.checked_add(SLOT_HASHES_COUNT_LEN))
+        .ok_or(HexoneError::Invalid)?;
+    require!(slot_hashes_data.len() >= entries_len, HexoneError::Invalid);
+    require!(entry_count > 0, HexoneError::AttackRandomnessNotReady);
+
+    // Entries are ordered newest first, so the last entry is the oldest slot still available
+    let oldest_offset = SLOT_HASHES_COUNT_LEN + (entry_count - 1) * SLOT_HASH_ENTRY_LEN;
+    require!(
+        read_u64(slot_hashes_data, oldest_offset) <= roll_after_slot,
+        HexoneError::AttackRandomnessExpired
+    );
+
+    let mut blockhashes: Vec<[u8; 32]> = Vec::new();
+    for i in 0..entry_count {
+        let offset = SLOT_HASHES_COUNT_LEN + i * SLOT_HASH_ENTRY_LEN;
+        if read_u64(slot_hashes_data, offset) <= roll_after_slot {
+            break;
+        }
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&slot_hashes_data[offset + 8..offset + SLOT_HASH_ENTRY_LEN]);
+        blockhashes.push(hash);
+    }
+
+    // Reverse so index 0 is the first block after roll_after_slot
+    blockhashes.reverse();
+    Ok(blockhashes)
+}
+
+// Hash the selected blockhashes together with the defender account key and any
+// player-supplied entropy (commit-reveal secrets), then reduce to 0-999
+fn roll_from_blockhashes(
+    blockhashes: &[[u8; 32]],
+    offsets: &[usize; 3],
+    defender_key: &Pubkey,
+    player_entropy: &[u8],
+) -> u16 {
+    let mut hasher = Sha256::new();
+    for offset in offsets.iter() {
+        hasher.update(blockhashes[*offset]);
+    }
+    hasher.update(defender_key.as_ref());
+    hasher.update(player_entropy);
+    hash_to_roll(&hasher.finalize())
+}
+
+/// Roll the attacker and defender results (0-999 each) from the SlotHashes sysvar data.
+/// Only blocks produced after the attack's reinforcement window are used, so the outcome can't be
+/// known while the attack can still be reinforced and doesn't change depending on when it is resolved.
+/// `player_entropy` is empty for normal attacks and holds both revealed secrets for commit-reveal attacks.
+pub(crate) fn roll_attack_from_slot_hashes(
+    slot_hashes_data: &[u8],
+    roll_after_slot: u64,
+    defender_key: &Pubkey,
+    player_entropy: &[u8],
+) -> Result<(u16, u16)> {
+    let blockhashes = blockhashes_after_slot(slot_hashes_data, roll_after_slot)?;
+    require!(
+        blockhashes.len() >= ROLL_BLOCK_COUNT,
+        HexoneError::AttackRandomnessNotReady
+    );
+
+    let attacker_value = roll_from_blockhashes(&blockhashes, &ATTACKER_BLOCK_OFFSETS, defender_key, player_entropy);
+    let defender_value = roll_from_blockhashes(&blockhashes, &DEFENDER_BLOCK_OFFSETS, defender_key, player_entropy);
+    Ok((attacker_value, defender_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Build raw SlotHashes sysvar data (newest first) for slots first_slot..=last_slot,
+    // with each blockhash derived from the slot and a per-history seed
+    fn slot_hashes_data(first_slot: u64, last_slot: u64, history_seed: u8) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&(last_slot - first_slot + 1).to_le_bytes());
+        for slot in (first_slot..=last_slot).rev() {
+            let mut hasher = Sha256::new();
+            hasher.update(&slot.to_le_bytes());
+            hasher.update(&[history_seed]);
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&hasher.finalize());
+        }
+        data
+    }
+
+    #[test]
+    fn same_defender_rolls_differently_across_slot_histories() {
+        let defender_key = Pubkey::new_unique();
+        let attack_started_slot = 100;
+
+        let rolls: Vec<(u16, u16)> = (0..8u8)
+            .map(|seed| {
+                let data = slot_hashes_data(90, 120, seed);
+                roll_attack_from_slot_hashes(&data, attack_started_slot, &defender_key, &[]).unwrap()
+            })
+            .collect();
+
+        for (i, roll) in rolls.iter().enumerate() {
+            assert!(roll.0 < 1000 && roll.1 < 1000);
+            for other in rolls.iter().skip(i + 1) {
+                assert_ne!(roll, other);
+            }
+        }
+    }
+
+    #[test]
+    fn roll_is_stable_once_blocks_are_produced() {
+        let defender_key = Pubkey::new_unique();
+        let early = slot_hashes_data(90, 112, 7);
+        let late = slot_hashes_data(90, 140, 7);
+
+        // Resolving later (more blocks available) must not change the outcome
+        assert_eq!(
+            roll_attack_from_slot_hashes(&early, 100, &defender_key, &[]).unwrap(),
+            roll_attack_from_slot_hashes(&late, 100, &defender_key, &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn blocks_before_the_attack_do_not_affect_the_roll() {
+        let defender_key = Pubkey::new_unique();
+        let mut data_a = slot_hashes_data(90, 120, 1);
+        let data_b = slot_hashes_data(90, 120, 1);
+
+        // Overwrite the hash of slot 100 (the attack slot itself)
+        let entry = SLOT_HASHES_COUNT_LEN + (120 - 100) * SLOT_HASH_ENTRY_LEN;
+        data_a[entry + 8..entry + SLOT_HASH_ENTRY_LEN].copy_from_slice(&[0xAB; 32]);
+
+        assert_eq!(
+            roll_attack_from_slot_hashes(&data_a, 100, &defender_key, &[]).unwrap(),
+            roll_attack_from_slot_hashes(&data_b, 100, &defender_key, &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn blocks_inside_the_reinforcement_window_do_not_affect_the_roll() {
+        let defender_key = Pubkey::new_unique();
+        // A 3 second window after slot 100 ends at slot 108
+        let window_end = reinforce_window_end_slot(100, 3);
+        assert_eq!(window_end, 108);
+        assert_eq!(reinforce_window_end_slot(100, 60), 250);
+
+        // Blocks the defender could have seen while reinforcing don't feed the roll
+        let mut data_a = slot_hashes_data(90, 130, 6);
+        let data_b = slot_hashes_data(90, 130, 6);
+        for slot in 101..=window_end as usize {
+            let entry = SLOT_HASHES_COUNT_LEN + (130 - slot) * SLOT_HASH_ENTRY_LEN;
+            data_a[entry + 8..entry + SLOT_HASH_ENTRY_LEN].copy_from_slice(&[0xCD; 32]);
+        }
+        assert_eq!(
+            roll_attack_from_slot_hashes(&data_a, window_end, &defender_key, &[]).unwrap(),
+            roll_attack_from_slot_hashes(&data_b, window_end, &defender_key, &[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn different_defenders_roll_differently_on_the_same_history() {
+        let data = slot_hashes_data(90, 120, 3);
+        let roll_a = roll_attack_from_slot_hashes(&data, 100, &Pubkey::new_unique(), &[]).unwrap();
+        let roll_b = roll_attack_from_slot_hashes(&data, 100, &Pubkey::new_unique(), &[]).unwrap();
+        assert_ne!(roll_a, roll_b);
+    }
+
+    #[test]
+    fn revealed_secrets_change_the_roll() {
+        let defender_key = Pubkey::new_unique();
+        let data = slot_hashes_data(90, 120, 5);
+
+        let mut secrets_a = [1u8; 64].to_vec();
+        let secrets_b = [1u8; 64].to_vec();
+        secrets_a[63] = 2;
+
+        let roll_a = roll_attack_from_slot_hashes(&data, 100, &defender_key, &secrets_a).unwrap();
+        let roll_b = roll_attack_from_slot_hashes(&data, 100, &defender_key, &secrets_b).unwrap();
+        let roll_chain_only = roll_attack_from_slot_hashes(&data, 100, &defender_key, &[]).unwrap();
+        assert_ne!(roll_a, roll_b);
+        assert_ne!(roll_b, roll_chain_only);
+    }
+
+    #[test]
+    fn vrf_rolls_follow_the_oracle_randomness() {
+        let defender_key = Pubkey::new_unique();
+        let roll_a = roll_attack_from_vrf(&[1u8; 32], &defender_key, &[]);
+        let roll_b = roll_attack_from_vrf(&[2u8; 32], &defender_key, &[]);
+        assert_ne!(roll_a, roll_b);
+        assert_eq!(roll_a, roll_attack_from_vrf(&[1u8; 32], &defender_key, &[]));
+        assert!(roll_a.0 < 1000 && roll_a.1 < 1000);
+    }
+
+    #[test]
+    fn vrf_source_waits_for_fulfilment() {
+        let defender_key = Pubkey::new_unique();
+        let inputs = RollInputs {
+            current_slot: 200,
+            roll_after_slot: 100,
+            defender_key: &defender_key,
+            slot_hashes_data: &[],
+            vrf_randomness: None,
+            player_entropy: &[],
+        };
+        let result = RandomnessSource::Vrf.roll(&inputs);
+        assert_eq!(result.unwrap_err(), HexoneError::RandomnessNotFulfilled.into());
+    }
+
+    #[test]
+    fn sources_round_trip_from_game_setting() {
+        assert_eq!(RandomnessSource::from_u8(RANDOMNESS_SOURCE_HASH).unwrap(), RandomnessSource::Hash);
+        assert_eq!(RandomnessSource::from_u8(RANDOMNESS_SOURCE_SLOT_HASHES).unwrap(), RandomnessSource::SlotHashes);
+        assert_eq!(RandomnessSource::from_u8(RANDOMNESS_SOURCE_VRF).unwrap(), RandomnessSource::Vrf);
+        assert!(RandomnessSource::from_u8(3).is_err());
+    }
+
+    #[test]
+    fn roll_waits_for_enough_blocks() {
+        let data = slot_hashes_data(90, 111, 0);
+        let result = roll_attack_from_slot_hashes(&data, 100, &Pubkey::new_unique(), &[]);
+        assert_eq!(result.unwrap_err(), HexoneError::AttackRandomnessNotReady.into());
+    }
+
+    #[test]
+    fn roll_fails_once_attack_slots_leave_the_sysvar() {
+        let data = slot_hashes_data(101, 140, 0);
+        let result = roll_attack_from_slot_hashes(&data, 100, &Pubkey::new_unique(), &[]);
+        assert_eq!(result.unwrap_err(), HexoneError::AttackRandomnessExpired.into());
+    }
+
+    #[test]
+    fn attacks_resolve_until_the_sysvar_is_a_full_horizon_past_them() {
+        let defender_key = Pubkey::new_unique();
+        let started = 1_000;
+        // A full sysvar that still reaches back to the attack's slot rolls
+        let last_chance = slot_hashes_data(started, started + SLOT_HASHES_MAX_ENTRIES as u64 - 1, 4);
+        assert!(roll_attack_from_slot_hashes(&last_chance, started, &defender_key, &[]).is_ok());
+
+        // One slot later it's gone for good: only expire_attack can close the attack now, and
+        // ATTACK_EXPIRY_SECONDS is checked at compile time to open up before this point
+        let gone = slot_hashes_data(started + 1, started + SLOT_HASHES_MAX_ENTRIES as u64, 4);
+        let result = roll_attack_from_slot_hashes(&gone, started, &defender_key, &[]);
+        assert_eq!(result.unwrap_err(), HexoneError::AttackRandomnessExpired.into());
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::MAX_ATTACK_CONTRIBUTORS;
+
 #[account]
+#[derive(Default)]
 pub struct Defender {
     pub game: Pubkey,
     pub defender_tile_index: u16,
@@ -8,11 +11,37 @@ pub struct Defender {
     pub attacker_tile_index: u16,
     pub attacker_tile_color: u8,
     pub attack_started_at: i64,
+    pub attack_started_slot: u64, // Slot the attack was created in (rolls use SlotHashes after it)
     pub is_attack_resolved: bool,
     pub attacker_won: bool,
-    pub attacking_result: u16,    // Random number 0-999 from blockhash calculation
-    pub defending_result: u16,    // Random number 0-999 from blockhash calculation
+    pub attacking_result: u16,    // Random number 0-999 from SlotHashes calculation
+    pub defending_result: u16,    // Random number 0-999 from SlotHashes calculation
     pub bump: u8,
+    // Commit-reveal rolls (only used when the game has commit_reveal_enabled)
+    pub commit_reveal: bool,
+    pub reveal_deadline: i64,           // Both sides must commit and reveal before this timestamp
+    pub attacker_commitment: [u8; 32],  // sha256(secret || attacker wallet), zero until committed
+    pub defender_commitment: [u8; 32],  // sha256(secret || defender wallet), zero until committed
+    pub attacker_secret: [u8; 32],
+    pub defender_secret: [u8; 32],
+    pub attacker_revealed: bool,
+    pub defender_revealed: bool,
+    // VRF randomness (only used when the game rolls with RANDOMNESS_SOURCE_VRF)
+    pub vrf_randomness: [u8; 32],       // Filled in by the oracle's fulfil callback
+    pub vrf_fulfilled: bool,
+    // Expiry: after expires_at anyone can close the attack and the rent goes back to payer
+    pub expires_at: i64,
+    pub payer: Pubkey,
+    // Defence: resources on the tile when attacked plus reinforcements committed during the window
+    pub defender_resources_at_start: u16,
+    pub reinforced_resources: u16,
+    // Attacking tiles (attack_tile uses one, coordinated_attack up to MAX_ATTACK_CONTRIBUTORS)
+    // attacker_tile_index is always the first contributor
+    pub contributor_count: u8,
+    pub contributor_tile_indices: [u16; MAX_ATTACK_CONTRIBUTORS],
+    // Share of the surviving resources each contributor gets back on capture (basis points),
+    // the captured tile gets the rest
+    pub contributor_allocation_bps: [u16; MAX_ATTACK_CONTRIBUTORS],
 }
 
 impl Defender {
@@ -23,11 +52,99 @@ impl Defender {
         + 2                        // attacker_tile_index
         + 1                        // attacker_tile_color
         + 8                        // attack_started_at
+        + 8                        // attack_started_slot
         + 1                        // is_attack_resolved
         + 1                        // attacker_won
         + 2                        // attacking_result (0-999)
         + 2                        // defending_result (0-999)
         + 1                        // bump
-        + 3;                       // padding to align to 8 bytes
+        + 1                        // commit_reveal
+        + 8                        // reveal_deadline
+        + 32                       // attacker_commitment
+        + 32                       // defender_commitment
+        + 32                       // attacker_secret
+        + 32                       // defender_secret
+        + 1                        // attacker_revealed
+        + 1                        // defender_revealed
+        + 32                       // vrf_randomness
+        + 1                        // vrf_fulfilled
+        + 8                        // expires_at
+        + 32                       // payer
+        + 2                        // defender_resources_at_start
+        + 2                        // reinforced_resources
+        + 1                        // contributor_count
+        + 2 * MAX_ATTACK_CONTRIBUTORS // contributor_tile_indices
+        + 2 * MAX_ATTACK_CONTRIBUTORS // contributor_allocation_bps
+        + 2;                       // padding to align to 8 bytes
+
+    /// Tiles committed to this attack
+    pub fn contributor_tiles(&self) -> &[u16] {
+        &self.contributor_tile_indices[..self.contributor_count as usize]
+    }
+
+    /// Side that forfeits a commit-reveal attack which can't roll from both secrets
+    /// Neither side can reveal until both have committed, so a missing commitment is blamed
+    /// before a missing reveal. None means roll: both revealed, or neither committed and
+    /// the roll falls back to the randomness source alone
+    pub fn forfeit(&self) -> Option<Forfeit> {
+        if !self.commit_reveal || (self.attacker_revealed && self.defender_revealed) {
+            return None;
+        }
+        let attacker_committed = self.attacker_commitment != [0u8; 32];
+        let defender_committed = self.defender_commitment != [0u8; 32];
+        match (attacker_committed, defender_committed) {
+            (false, false) => None,
+            (false, true) => Some(Forfeit::Attacker),
+            (true, false) => Some(Forfeit::Defender),
+            (true, true) if !self.attacker_revealed => Some(Forfeit::Attacker),
+            (true, true) => Some(Forfeit::Defender),
+        }
+    }
+}
+
+/// Side that loses a commit-reveal attack for not committing or revealing in time
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Forfeit {
+    Attacker,
+    Defender,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit_reveal_attack() -> Defender {
+        Defender {
+            commit_reveal: true,
+            ..Defender::default()
+        }
+    }
+
+    #[test]
+    fn defender_that_never_commits_forfeits_to_the_attacker() {
+        let mut defender = commit_reveal_attack();
+        defender.attacker_commitment = [1u8; 32];
+        assert_eq!(defender.forfeit(), Some(Forfeit::Defender));
+    }
+
+    #[test]
+    fn missing_commitments_are_blamed_before_missing_reveals() {
+        let mut defender = commit_reveal_attack();
+        defender.defender_commitment = [1u8; 32];
+        assert_eq!(defender.forfeit(), Some(Forfeit::Attacker));
+
+        defender.attacker_commitment = [2u8; 32];
+        assert_eq!(defender.forfeit(), Some(Forfeit::Attacker));
+        defender.attacker_revealed = true;
+        assert_eq!(defender.forfeit(), Some(Forfeit::Defender));
+        defender.defender_revealed = true;
+        assert_eq!(defender.forfeit(), None);
+    }
+
+    #[test]
+    fn attacks_nobody_committed_to_roll_without_a_forfeit() {
+        assert_eq!(commit_reveal_attack().forfeit(), None);
+        assert_eq!(Defender::default().forfeit(), None);
+    }
 }
 
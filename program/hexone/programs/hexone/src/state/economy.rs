@@ -0,0 +1,483 @@
+use crate::constants::{MAX_PLAYERS, PAYOUT_BPS_DENOMINATOR};
+use crate::state::game::{Game, PlayerSlot};
+
+pub const SECONDS_PER_MINUTE: i64 = 60;
+
+/// Rates are per minute but accrue every second. Accrual is tracked in fixed point with
+/// ACCRUAL_SCALE units per whole XP or resource; the fraction that doesn't make a whole unit
+/// is carried to the next accrual instead of being rounded away
+pub const ACCRUAL_SCALE: u64 = SECONDS_PER_MINUTE as u64;
+
+/// Seconds since `last_timestamp` (0 if the clock is behind it)
+pub fn seconds_elapsed(current_time: i64, last_timestamp: i64) -> u64 {
+    current_time.saturating_sub(last_timestamp).max(0) as u64
+}
+
+/// Accrue `rate_per_minute` over `elapsed_seconds`, on top of a carried `remainder`
+/// Returns the whole units earned and the new remainder (always below ACCRUAL_SCALE)
+/// Exact, so splitting an interval into several accruals never changes the total
+pub fn accrue_fixed_point(rate_per_minute: u32, elapsed_seconds: u64, remainder: u32) -> (u64, u32) {
+    let scaled = (rate_per_minute as u128) * (elapsed_seconds as u128) + (remainder as u128);
+    let whole = scaled / ACCRUAL_SCALE as u128;
+    let remainder = (scaled % ACCRUAL_SCALE as u128) as u32;
+    (whole.min(u64::MAX as u128) as u64, remainder)
+}
+
+/// XP rates copied out of Game, so slots can be accrued while Game is mutably borrowed
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct XpRates {
+    pub xp_per_minute_per_tile: u32,
+    pub tier_bonus_xp_per_min: [u8; 4], // gold, silver, bronze, iron (same order as tier_tile_counts)
+}
+
+impl XpRates {
+    pub fn from_game(game: &Game) -> Self {
+        XpRates {
+            xp_per_minute_per_tile: game.xp_per_minute_per_tile,
+            tier_bonus_xp_per_min: [
+                game.gold_tier_bonus_xp_per_min,
+                game.silver_tier_bonus_xp_per_min,
+                game.bronze_tier_bonus_xp_per_min,
+                game.iron_tier_bonus_xp_per_min,
+            ],
+        }
+    }
+
+    /// XP a player earns per minute: every tile earns the base rate, tier tiles add their bonus
+    /// Saturates at u32::MAX instead of failing the instruction
+    pub fn xp_per_minute(&self, slot: &PlayerSlot) -> u32 {
+        let tier_bonus = slot
+            .tier_tile_counts
+            .iter()
+            .zip(self.tier_bonus_xp_per_min.iter())
+            .fold(0u32, |total, (count, bonus)| {
+                total.saturating_add((*count as u32).saturating_mul(*bonus as u32))
+            });
+        slot.tile_count
+            .saturating_mul(self.xp_per_minute_per_tile)
+            .saturating_add(tier_bonus)
+    }
+}
+
+/// XP a player has earned since their last accrual, and the remainder to carry afterwards
+/// Players who haven't started accruing (xp_timestamp 0) earn nothing
+pub fn pending_xp(slot: &PlayerSlot, rates: &XpRates, current_time: i64) -> (u32, u32) {
+    if slot.xp_timestamp <= 0 {
+        return (0, slot.xp_remainder as u32);
+    }
+    let (xp_earned, remainder) = accrue_fixed_point(
+        rates.xp_per_minute(slot),
+        seconds_elapsed(current_time, slot.xp_timestamp),
+        slot.xp_remainder as u32,
+    );
+    (xp_earned.min(u32::MAX as u64) as u32, remainder)
+}
+
+/// First second at which XP accruing at `rate_per_minute` from `xp` (plus `remainder`) at
+/// `timestamp` reaches `limit`, and how far past the limit it is at that second, in
+/// 1/ACCRUAL_SCALE units. A player who is already at the limit reaches it at `timestamp`;
+/// one with no XP rate never does
+pub fn limit_crossing(
+    xp: u32,
+    remainder: u32,
+    timestamp: i64,
+    rate_per_minute: u32,
+    limit: u64,
+) -> Option<(i64, u32)> {
+    let scale = ACCRUAL_SCALE as u128;
+    let have = (xp as u128) * scale + (remainder as u128);
+    let need = (limit as u128) * scale;
+    if have >= need {
+        return Some((timestamp, (have - need).min(u32::MAX as u128) as u32));
+    }
+    if rate_per_minute == 0 {
+        return None;
+    }
+    let rate = rate_per_minute as u128;
+    let seconds = (need - have).div_ceil(rate);
+    let past_limit = (have + rate * seconds - need) as u32;
+    let reached_at = timestamp.saturating_add(seconds.min(i64::MAX as u128) as i64);
+    Some((reached_at, past_limit))
+}
+
+/// Credit a player's pending XP and move their timestamp up to now
+/// Records the exact second they reached `winning_xp_limit` if it happened in this interval
+/// Eliminated players (including those who resigned) are frozen at the XP they had when they went out
+pub fn accrue_player_xp(slot: &mut PlayerSlot, rates: &XpRates, winning_xp_limit: u64, current_time: i64) {
+    if !slot.in_play() || slot.xp_timestamp <= 0 || current_time <= slot.xp_timestamp {
+        return;
+    }
+    if slot.limit_reached_at == 0 {
+        let crossing = limit_crossing(
+            slot.xp,
+            slot.xp_remainder as u32,
+            slot.xp_timestamp,
+            rates.xp_per_minute(slot),
+            winning_xp_limit,
+        );
+        if let Some((reached_at, past_limit)) = crossing {
+            if reached_at <= current_time {
+                slot.limit_reached_at = reached_at;
+                slot.xp_past_limit = past_limit;
+            }
+        }
+    }
+    let (xp_earned, remainder) = pending_xp(slot, rates, current_time);
+    slot.xp = slot.xp.saturating_add(xp_earned);
+    slot.xp_remainder = remainder as u8;
+    slot.xp_timestamp = current_time;
+}
+
+/// Accrue XP for every seated player and emit XpAccrued for each one that earned some
+pub fn accrue_xp(game: &mut Game, current_time: i64) {
+    let rates = XpRates::from_game(game);
+    let winning_xp_limit = game.winning_xp_limit;
+    let xp_before = game.player_xp();
+    let max_players = game.max_players as usize;
+    for slot in game.players[..max_players].iter_mut() {
+        accrue_player_xp(slot, &rates, winning_xp_limit, current_time);
+    }
+    game.emit_xp_accrued(xp_before, current_time);
+}
+
+/// Grow the shared per-player resource allocation by resources_per_minute, prorated to the second
+pub fn accrue_resources(game: &mut Game, current_time: i64) {
+    if game.available_resources_timestamp <= 0 || current_time <= game.available_resources_timestamp {
+        return;
+    }
+    let (resources_earned, remainder) = accrue_fixed_point(
+        game.resources_per_minute,
+        seconds_elapsed(current_time, game.available_resources_timestamp),
+        game.resources_remainder,
+    );
+    game.total_resources_available = game
+        .total_resources_available
+        .saturating_add(resources_earned.min(u32::MAX as u64) as u32);
+    game.resources_remainder = remainder;
+    game.available_resources_timestamp = current_time;
+}
+
+/// Platform fee on a prize pool of `pool` lamports, rounded down
+pub fn platform_fee(pool: u64, fee_bps: u16) -> u64 {
+    ((pool as u128) * (fee_bps.min(PAYOUT_BPS_DENOMINATOR) as u128) / PAYOUT_BPS_DENOMINATOR as u128) as u64
+}
+
+/// Split a prize pool between the first `places` finishing places by a payout table in basis points
+/// Shares round down and first place takes the dust, along with any share for a place nobody
+/// finished in, so the whole pool is always paid out
+pub fn split_prize_pool(prize_pool: u64, payout_bps: &[u16; MAX_PLAYERS], places: usize) -> [u64; MAX_PLAYERS] {
+    let mut payouts = [0u64; MAX_PLAYERS];
+    if places == 0 {
+        return payouts;
+    }
+    for place in 1..places.min(MAX_PLAYERS) {
+        payouts[place] =
+            ((prize_pool as u128) * (payout_bps[place] as u128) / PAYOUT_BPS_DENOMINATOR as u128) as u64;
+    }
+    payouts[0] = prize_pool - payouts.iter().sum::<u64>();
+    payouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::prelude::Pubkey;
+    use bytemuck::Zeroable;
+
+    const START: i64 = 1_700_000_000;
+
+    fn game() -> Game {
+        let mut game = Game::zeroed();
+        game.max_players = 2;
+        game.resources_per_minute = 10;
+        game.xp_per_minute_per_tile = 1;
+        game.gold_tier_bonus_xp_per_min = 100;
+        game.silver_tier_bonus_xp_per_min = 50;
+        game.bronze_tier_bonus_xp_per_min = 10;
+        game.iron_tier_bonus_xp_per_min = 5;
+        game.winning_xp_limit = 10_000;
+        game.available_resources_timestamp = START;
+        for slot in game.players[..2].iter_mut() {
+            slot.wallet = Pubkey::new_unique();
+            slot.xp_timestamp = START;
+            slot.tile_count = 3;
+        }
+        game
+    }
+
+    /// Small deterministic generator so the property tests don't need an extra dependency
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+    }
+
+    #[test]
+    fn partial_minutes_accrue_to_the_second() {
+        // 3 XP a minute is one XP every 20 seconds
+        assert_eq!(accrue_fixed_point(3, 20, 0), (1, 0));
+        assert_eq!(accrue_fixed_point(3, 30, 0), (1, 30));
+        // Exactly a minute earns the full rate
+        assert_eq!(accrue_fixed_point(3, 60, 0), (3, 0));
+        assert_eq!(accrue_fixed_point(10, 60, 0), (10, 0));
+        // The carried remainder counts towards the next unit
+        assert_eq!(accrue_fixed_point(3, 10, 30), (1, 0));
+        assert_eq!(accrue_fixed_point(0, 600, 59), (0, 59));
+        // Clock behind the timestamp
+        assert_eq!(seconds_elapsed(START - 600, START), 0);
+    }
+
+    #[test]
+    fn xp_accrues_every_second_and_carries_the_fraction() {
+        let mut game = game();
+        // 3 tiles earn 3 XP a minute: 50 seconds is 2.5 XP
+        accrue_xp(&mut game, START + 50);
+        assert_eq!(game.players[0].xp, 2);
+        assert_eq!(game.players[0].xp_remainder, 30);
+        assert_eq!(game.players[0].xp_timestamp, START + 50);
+        assert_eq!(game.players[1].xp, 2);
+
+        // Another 10 seconds makes exactly a minute, and exactly 3 XP
+        accrue_xp(&mut game, START + 60);
+        assert_eq!(game.players[0].xp, 3);
+        assert_eq!(game.players[0].xp_remainder, 0);
+    }
+
+    #[test]
+    fn players_who_havent_started_dont_accrue() {
+        let mut game = game();
+        game.players[1].xp_timestamp = 0;
+        accrue_xp(&mut game, START + 600);
+        assert_eq!(game.players[0].xp, 30);
+        assert_eq!(game.players[1].xp, 0);
+        assert_eq!(game.players[1].xp_timestamp, 0);
+    }
+
+    #[test]
+    fn eliminated_players_are_frozen() {
+        let mut game = game();
+        accrue_xp(&mut game, START + 60);
+        game.players[1].eliminated_order = 1;
+        accrue_xp(&mut game, START + 600);
+        assert_eq!(game.players[0].xp, 30);
+        assert_eq!(game.players[1].xp, 3);
+        assert_eq!(game.players[1].xp_timestamp, START + 60);
+    }
+
+    #[test]
+    fn only_seated_colours_accrue() {
+        let mut game = game();
+        game.players[2].xp_timestamp = START;
+        game.players[2].tile_count = 3;
+        accrue_xp(&mut game, START + 600);
+        assert_eq!(game.players[2].xp, 0);
+    }
+
+    #[test]
+    fn tier_bonuses_add_to_the_base_rate() {
+        let mut game = game();
+        // One gold, two silver, no bronze, one iron tile on top of the 3 tiles' base XP
+        game.players[0].tier_tile_counts = [1, 2, 0, 1];
+        let rates = XpRates::from_game(&game);
+        assert_eq!(rates.xp_per_minute(&game.players[0]), 3 + 100 + 2 * 50 + 5);
+
+        accrue_xp(&mut game, START + 90);
+        assert_eq!(game.players[0].xp, 208 * 90 / 60);
+        assert_eq!(game.players[1].xp, 3 * 90 / 60);
+    }
+
+    #[test]
+    fn xp_saturates_instead_of_overflowing() {
+        let mut game = game();
+        game.xp_per_minute_per_tile = u32::MAX / 2;
+        accrue_xp(&mut game, START + 181);
+        assert_eq!(game.players[0].xp, u32::MAX);
+
+        // Already at the cap, more time changes nothing but the timestamp
+        accrue_xp(&mut game, START + 600);
+        assert_eq!(game.players[0].xp, u32::MAX);
+        assert_eq!(game.players[0].xp_timestamp, START + 600);
+    }
+
+    #[test]
+    fn resources_accrue_every_second() {
+        let mut game = game();
+        // 10 a minute is one every 6 seconds
+        accrue_resources(&mut game, START + 59);
+        assert_eq!(game.total_resources_available, 9);
+        assert_eq!(game.resources_remainder, 50);
+
+        accrue_resources(&mut game, START + 60);
+        assert_eq!(game.total_resources_available, 10);
+        assert_eq!(game.resources_remainder, 0);
+        assert_eq!(game.available_resources_timestamp, START + 60);
+    }
+
+    #[test]
+    fn resources_saturate_and_wait_for_the_game_to_start() {
+        let mut game = game();
+        game.resources_per_minute = u32::MAX;
+        accrue_resources(&mut game, START + 181);
+        assert_eq!(game.total_resources_available, u32::MAX);
+
+        let mut waiting = self::game();
+        waiting.available_resources_timestamp = 0;
+        accrue_resources(&mut waiting, START);
+        assert_eq!(waiting.total_resources_available, 0);
+        assert_eq!(waiting.available_resources_timestamp, 0);
+    }
+
+    #[test]
+    fn limit_crossing_finds_the_exact_second() {
+        // 3 XP a minute from 0: 10 XP takes 200 seconds
+        assert_eq!(limit_crossing(0, 0, START, 3, 10), Some((START + 200, 0)));
+        // 7 XP a minute: 10 XP needs 600/7 = 85.7 seconds, so the 86th second, 2/60 XP past
+        assert_eq!(limit_crossing(0, 0, START, 7, 10), Some((START + 86, 2)));
+        // The carried remainder counts
+        assert_eq!(limit_crossing(9, 59, START, 1, 10), Some((START + 1, 0)));
+        // Already there, or never getting there
+        assert_eq!(limit_crossing(12, 0, START, 3, 10), Some((START, 120)));
+        assert_eq!(limit_crossing(5, 0, START, 0, 10), None);
+    }
+
+    #[test]
+    fn accrual_records_when_the_limit_was_reached() {
+        let mut game = game();
+        game.winning_xp_limit = 10;
+        game.players[1].tile_count = 7;
+
+        // Red (3 a minute) isn't there yet, yellow (7 a minute) got there at the 86th second
+        accrue_xp(&mut game, START + 150);
+        assert_eq!(game.players[0].limit_reached_at, 0);
+        assert_eq!((game.players[1].limit_reached_at, game.players[1].xp_past_limit), (START + 86, 2));
+
+        // Red reaches it at 200 seconds; yellow's crossing doesn't move
+        accrue_xp(&mut game, START + 400);
+        assert_eq!(game.players[0].limit_reached_at, START + 200);
+        assert_eq!(game.players[1].limit_reached_at, START + 86);
+    }
+
+    #[test]
+    fn limit_crossing_doesnt_depend_on_accrual_frequency() {
+        let mut rng = XorShift(0x6a09_e667_f3bc_c908);
+        for _ in 0..100 {
+            let mut once = game();
+            once.winning_xp_limit = 1 + rng.below(500);
+            once.players[0].tile_count = 1 + rng.below(20) as u32;
+            let mut often = once;
+
+            let end = START + 3_600;
+            let mut now = START;
+            while now < end {
+                now = (now + 1 + rng.below(120) as i64).min(end);
+                accrue_xp(&mut often, now);
+            }
+            accrue_xp(&mut once, end);
+
+            assert_eq!(often.players[0].limit_reached_at, once.players[0].limit_reached_at);
+            assert_eq!(often.players[0].xp_past_limit, once.players[0].xp_past_limit);
+        }
+    }
+
+    #[test]
+    fn splitting_an_interval_never_changes_the_total() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let rate = rng.below(2_000) as u32;
+            let interval = rng.below(100_000);
+
+            let (whole, remainder) = accrue_fixed_point(rate, interval, 0);
+
+            let mut split_whole = 0;
+            let mut split_remainder = 0;
+            let mut left = interval;
+            while left > 0 {
+                let step = 1 + rng.below(left.min(600));
+                let (earned, carried) = accrue_fixed_point(rate, step, split_remainder);
+                split_whole += earned;
+                split_remainder = carried;
+                left -= step;
+            }
+
+            assert_eq!((split_whole, split_remainder), (whole, remainder), "rate {} over {}s", rate, interval);
+        }
+    }
+
+    #[test]
+    fn accrual_frequency_doesnt_change_xp_or_resources() {
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        for _ in 0..100 {
+            let mut once = game();
+            once.xp_per_minute_per_tile = 1 + rng.below(50) as u32;
+            once.resources_per_minute = 1 + rng.below(1_000) as u32;
+            once.players[0].tier_tile_counts = [rng.below(2) as u8, rng.below(7) as u8, 0, 0];
+            let mut often = once;
+
+            let end = START + rng.below(7_200) as i64;
+            let mut now = START;
+            while now < end {
+                now = (now + 1 + rng.below(90) as i64).min(end);
+                accrue_xp(&mut often, now);
+                accrue_resources(&mut often, now);
+            }
+            accrue_xp(&mut once, end);
+            accrue_resources(&mut once, end);
+
+            for color in 0..2 {
+                assert_eq!(often.players[color].xp, once.players[color].xp);
+                assert_eq!(often.players[color].xp_remainder, once.players[color].xp_remainder);
+            }
+            assert_eq!(often.total_resources_available, once.total_resources_available);
+            assert_eq!(often.resources_remainder, once.resources_remainder);
+        }
+    }
+
+    #[test]
+    fn payout_table_splits_the_pool_by_place() {
+        let podium = [7_000, 2_000, 1_000, 0, 0, 0];
+        assert_eq!(split_prize_pool(1_000_000, &podium, 4), [700_000, 200_000, 100_000, 0, 0, 0]);
+        // First place keeps the rounding dust
+        assert_eq!(split_prize_pool(1_001, &podium, 4), [701, 200, 100, 0, 0, 0]);
+        // ...and the share of a place nobody finished in
+        assert_eq!(split_prize_pool(1_000, &podium, 2), [800, 200, 0, 0, 0, 0]);
+        assert_eq!(split_prize_pool(1_000, &podium, 0), [0; MAX_PLAYERS]);
+    }
+
+    #[test]
+    fn platform_fee_rounds_down() {
+        assert_eq!(platform_fee(1_000_000, 250), 25_000);
+        assert_eq!(platform_fee(399, 250), 9);
+        assert_eq!(platform_fee(u64::MAX, 0), 0);
+        assert_eq!(platform_fee(u64::MAX, PAYOUT_BPS_DENOMINATOR), u64::MAX);
+    }
+
+    #[test]
+    fn payouts_always_add_up_to_the_pool() {
+        let mut rng = XorShift(0x5eed_0019);
+        for _ in 0..1_000 {
+            let mut bps = [0u16; MAX_PLAYERS];
+            let mut left = PAYOUT_BPS_DENOMINATOR;
+            for share in bps.iter_mut().skip(1) {
+                *share = rng.below(left as u64 + 1) as u16;
+                left -= *share;
+            }
+            bps[0] = left;
+            let pool = rng.next() >> rng.below(64);
+            let places = rng.below(MAX_PLAYERS as u64 + 1) as usize;
+
+            let payouts = split_prize_pool(pool, &bps, places);
+            let paid: u128 = payouts.iter().map(|payout| *payout as u128).sum();
+            assert_eq!(paid, if places == 0 { 0 } else { pool as u128 });
+            assert!(payouts[places.max(1)..].iter().all(|payout| *payout == 0));
+        }
+    }
+}
@@ -1,71 +1,47 @@
 use anchor_lang::prelude::*;
 use bytemuck::{Pod, Zeroable};
+use std::cell::{Ref, RefMut};
+use std::cmp::Reverse;
+use std::ops::{Deref, DerefMut};
 
 use crate::constants::*;
 use crate::error::HexoneError;
+use crate::combat::CombatSettings;
+use crate::events::{PlayerEliminated, WinnerDetermined, XpAccrued};
+use crate::state::economy::{platform_fee, split_prize_pool};
+use crate::state::game_v1::GameV1;
+use crate::state::defender::Defender;
 
 pub const GAME_STATE_WAITING: u8 = 0;
 pub const GAME_STATE_IN_PROGRESS: u8 = 1;
 pub const GAME_STATE_COMPLETED: u8 = 2; // Also used for winner found and paid
 pub const GAME_STATE_WINNER_FOUND_NOT_PAID_OUT: u8 = 3;
+pub const GAME_STATE_CANCELLED: u8 = 4; // Never filled; entry costs refunded by cancel_game
 
+// How the winner won (0 = no winner yet)
+pub const WIN_CONDITION_XP_LIMIT: u8 = 1;      // First to reach winning_xp_limit
+pub const WIN_CONDITION_LAST_STANDING: u8 = 2; // Everyone else was eliminated or resigned
+
+/// Layout version written by create_game; v1 accounts (see game_v1.rs) must go through migrate_game
+pub const GAME_VERSION: u8 = 2;
+
+/// Game header; its tile data (rows * columns TileData) trails it in the account, see GameBoard
 #[account(zero_copy)]
 #[repr(C)]
 pub struct Game {
     // 32-byte aligned fields
     pub admin: Pubkey,
-    pub player1: Pubkey,
-    pub player2: Pubkey,
-    pub player3: Pubkey,
-    pub player4: Pubkey,
+    // One slot per colour, indexed by colour - 1 (index 0 = red); only the first max_players are used
+    pub players: [PlayerSlot; MAX_PLAYERS],
     // 8-byte aligned fields
     pub game_id: u64,
     pub available_resources_timestamp: i64,
-    pub xp_timestamp_player1: i64,
-    pub xp_timestamp_player2: i64,
-    pub xp_timestamp_player3: i64,
-    pub xp_timestamp_player4: i64,
     // 4-byte aligned fields
     pub resources_per_minute: u32,
     pub total_resources_available: u32,
-    pub resources_spent_player1: u32,
-    pub resources_spent_player2: u32,
-    pub resources_spent_player3: u32,
-    pub resources_spent_player4: u32,
     pub xp_per_minute_per_tile: u32,
-    pub xp_player1: u32,
-    pub xp_player2: u32,
-    pub xp_player3: u32,
-    pub xp_player4: u32,
-    pub tile_count_color1: u32,
-    pub tile_count_color2: u32,
-    pub tile_count_color3: u32,
-    pub tile_count_color4: u32,
-    // 4 bytes of padding to align to 8 bytes before array
-    pub _padding_u32: [u8; 4],
-    // Array of TileData (4-byte aligned)
-    pub tile_data: [TileData; 144],
-    // Tier tracking for each player (u8 counts: iron, bronze, silver, gold)
-    // Player 1 tiers
-    pub iron_tile_count_player1: u8,
-    pub bronze_tile_count_player1: u8,
-    pub silver_tile_count_player1: u8,
-    pub gold_tile_count_player1: u8,
-    // Player 2 tiers
-    pub iron_tile_count_player2: u8,
-    pub bronze_tile_count_player2: u8,
-    pub silver_tile_count_player2: u8,
-    pub gold_tile_count_player2: u8,
-    // Player 3 tiers
-    pub iron_tile_count_player3: u8,
-    pub bronze_tile_count_player3: u8,
-    pub silver_tile_count_player3: u8,
-    pub gold_tile_count_player3: u8,
-    // Player 4 tiers
-    pub iron_tile_count_player4: u8,
-    pub bronze_tile_count_player4: u8,
-    pub silver_tile_count_player4: u8,
-    pub gold_tile_count_player4: u8,
+    // Fraction of a resource carried between accruals, in 1/ACCRUAL_SCALE units (see economy.rs)
+    pub resources_remainder: u32,
     // Tier bonus XP per minute (u8 values)
     pub gold_tier_bonus_xp_per_min: u8,
     pub silver_tier_bonus_xp_per_min: u8,
@@ -76,6 +52,10 @@ pub struct Game {
     // Winning player and XP limit
     pub winning_player_pubkey: Pubkey,
     pub winning_xp_limit: u64,
+    // Lamports each player pays into the treasury to join
+    pub entry_cost: u64,
+    // Oracle authority allowed to fulfil VRF randomness (only used with RANDOMNESS_SOURCE_VRF)
+    pub vrf_authority: Pubkey,
     // 1-byte fields grouped together at the end
     pub game_state: u8,
     pub rows: u8,
@@ -87,30 +67,135 @@ pub struct Game {
     // Attack hit calculation parameters
     pub max_hit_threshold: u16,        // Difference threshold (default 500)
     pub max_hit_resource_count: u8,    // Max resources lost (default 5)
-    // 7 bytes of padding to align to 8 bytes
-    pub _padding: [u8; 7]
+    // Attacks use commit-reveal rolls between attacker and defender (0 = off, 1 = on)
+    pub commit_reveal_enabled: u8,
+    // Randomness source used to roll attacks (see randomness.rs)
+    pub randomness_source: u8,
+    // Resources taken from the attacker's tile when an attack expires unresolved (0 = none)
+    pub expired_attack_penalty: u8,
+    // How resource counts weigh into attack odds (see combat.rs)
+    pub combat_model: u8,
+    pub max_combat_dice: u8,           // Dice model: max dice per side
+    pub resources_per_combat_die: u16, // Dice model: resources per extra die
+    // Attack cooldowns: a tile that just attacked or was just captured can't attack for this long
+    pub tile_attack_cooldown_seconds: u32,
+    // Max unresolved attacks a player can have open at once (0 = no cap)
+    pub max_open_attacks_per_player: u8,
+    // Players needed to start the game (2-6), and how many have joined
+    pub max_players: u8,
+    pub player_count: u8,
+    // Resigning players may hand their tiles to another colour instead of leaving them neutral
+    pub resign_beneficiary_enabled: u8,
+    // Resources on each player's starting tile
+    pub starting_resources: u16,
+    // Seconds the defender can reinforce an attacked tile; resolution opens once it closes
+    pub reinforce_window_seconds: u16,
+    // 4 bytes of padding to align to 8 bytes
+    pub _padding_config: [u8; 4],
+    // Lamports paid from the treasury to whoever runs tick_game (0 = none)
+    pub crank_reward_lamports: u64,
+    // Second the winner reached winning_xp_limit (0 until a winner is determined)
+    pub winner_reached_limit_at: i64,
+    // Treasury balance split by payout_bps, locked in by the first claim_payout (after the platform fee)
+    pub prize_pool: u64,
+    // Platform fee taken from the treasury when the prize pool was locked in
+    pub platform_fee: u64,
+    // Unclaimed payouts expire after this (set when the winner is determined)
+    pub payout_deadline: i64,
+    // Final ranking, best first, recorded when the winner is determined (see rank_players)
+    pub standings: [FinalStanding; MAX_PLAYERS],
+    // Share of the prize pool for each finishing place, in basis points
+    pub payout_bps: [u16; MAX_PLAYERS],
+    // Platform fee on the prize pool, in basis points (the platform's fee_bps when the game was created)
+    pub fee_bps: u16,
+    // WIN_CONDITION_* the winner won by, set with the winner
+    pub win_condition: u8,
+    pub _padding_payouts: [u8; 1],
+    // Anyone can cancel the game if it hasn't filled by this time
+    pub join_deadline: i64,
+    // Lamports paid out by tick_game so far (capped, see crank_reward_due)
+    pub crank_rewards_paid: u64,
+}
+
+/// Everything Game tracks per player
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct PlayerSlot {
+    pub wallet: Pubkey,                 // Default pubkey while the seat is empty
+    pub xp_timestamp: i64,              // Last time XP was accrued (0 until the game starts)
+    pub resources_spent: u32,           // Resources added from the shared allocation
+    pub xp: u32,
+    pub tile_count: u32,                // Tiles owned
+    pub tier_tile_counts: [u8; 4],      // Tiles owned per tier: gold, silver, bronze, iron
+    pub open_attacks: u8,               // Unresolved attacks launched by this player
+    pub xp_remainder: u8,               // Fraction of an XP carried between accruals, in 1/ACCRUAL_SCALE units
+    pub eliminated_order: u8,           // 1 = first colour to lose its last tile (0 = still in)
+    pub resigned: u8,                   // 1 once the player has conceded (see resign)
+    pub xp_past_limit: u32,             // How far past winning_xp_limit they were that second, in 1/ACCRUAL_SCALE units
+    pub limit_reached_at: i64,          // Second XP reached winning_xp_limit (0 = not yet)
+    pub eliminated_at: i64,             // Second the colour was eliminated (0 = still in)
+}
+
+/// One place in a finished game's ranking, with what it's paid
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+#[repr(C)]
+pub struct FinalStanding {
+    pub color: u8,                      // 0 = place not used (fewer players than places)
+    pub eliminated_order: u8,           // As in PlayerSlot (0 = still in at the finish)
+    pub payout_claimed: u8,             // 1 once claim_payout has paid this place
+    pub _padding: u8,
+    pub tile_count: u32,                // Tiles held at the finish
+    pub xp: u32,                        // XP at the finish
+    pub _padding_xp: [u8; 4],
+    pub payout: u64,                    // Lamports for this place (set by the first claim_payout)
+}
+
+impl FinalStanding {
+    pub const LEN: usize = 1 + 1 + 1 + 1 + 4 + 4 + 4 + 8;
+}
+
+impl PlayerSlot {
+    pub const LEN: usize = 32 + 8 + 4 + 4 + 4 + 4 + 1 + 1 + 1 + 1 + 4 + 8 + 8;
+
+    /// Seat taken by a player who hasn't been eliminated (resigning counts)
+    pub fn in_play(&self) -> bool {
+        self.wallet != Pubkey::default() && self.eliminated_order == 0
+    }
+}
+
+/// Name of a colour for logging (1 = red ... 6 = orange)
+pub fn color_name(color: u8) -> &'static str {
+    match color {
+        1 => "Red",
+        2 => "Yellow",
+        3 => "Green",
+        4 => "Blue",
+        5 => "Purple",
+        6 => "Orange",
+        _ => "Unknown",
+    }
 }
 
 /// Calculate the tier (ring distance) of a tile from the center
-/// Center is at row 5, col 6 (0-indexed)
+/// Center is the middle tile of the board, e.g. row 5, col 6 (0-indexed) on 11x13
 /// Returns: 0 = gold (center), 1 = silver, 2 = bronze, 3 = iron (ring 3), 4 = base tile (ring 4+, not tracked)
 /// Uses a simple distance calculation instead of BFS for efficiency
-pub fn get_tile_tier(tile_index: u16, _rows: u8, columns: u8) -> u8 {
-    const CENTER_ROW: i32 = 5;
-    const CENTER_COL: i32 = 6;
+pub fn get_tile_tier(tile_index: u16, rows: u8, columns: u8) -> u8 {
+    let center_row = (rows as i32 - 1) / 2;
+    let center_col = (columns as i32 - 1) / 2;
     
     let tile_row = (tile_index as usize) / (columns as usize);
     let tile_col = (tile_index as usize) % (columns as usize);
     
     // Calculate Manhattan distance in hexagonal grid
     // For odd-r offset hexagonal grid, use axial coordinates conversion
-    let _row_diff = tile_row as i32 - CENTER_ROW;
-    let _col_diff = tile_col as i32 - CENTER_COL;
+    let _row_diff = tile_row as i32 - center_row;
+    let _col_diff = tile_col as i32 - center_col;
     
     // Convert to axial coordinates for hexagonal distance
     // In odd-r offset: q = col, r = row - (col - (col & 1)) / 2
-    let center_q = CENTER_COL;
-    let center_r = CENTER_ROW - (CENTER_COL - (CENTER_COL & 1)) / 2;
+    let center_q = center_col;
+    let center_r = center_row - (center_col - (center_col & 1)) / 2;
     
     let tile_q = tile_col as i32;
     let tile_r = tile_row as i32 - (tile_col as i32 - (tile_col as i32 & 1)) / 2;
@@ -141,96 +226,18 @@ pub fn get_tile_tier(tile_index: u16, _rows: u8, columns: u8) -> u8 {
 /// tier: 0 = gold, 1 = silver, 2 = bronze, 3 = iron, 4 = base tile (not tracked, returns Ok)
 pub fn update_tier_count_on_gain(
     game: &mut Game,
-    player_index: usize,
+    color: u8,
     tier: u8,
 ) -> Result<()> {
     // Base tiles (tier 4) are not tracked, just return Ok
     if tier == 4 {
         return Ok(());
     }
-    match (player_index, tier) {
-        (1, 0) => {
-            game.gold_tile_count_player1 = game.gold_tile_count_player1
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (1, 1) => {
-            game.silver_tile_count_player1 = game.silver_tile_count_player1
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (1, 2) => {
-            game.bronze_tile_count_player1 = game.bronze_tile_count_player1
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (1, 3) => {
-            game.iron_tile_count_player1 = game.iron_tile_count_player1
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 0) => {
-            game.gold_tile_count_player2 = game.gold_tile_count_player2
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 1) => {
-            game.silver_tile_count_player2 = game.silver_tile_count_player2
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 2) => {
-            game.bronze_tile_count_player2 = game.bronze_tile_count_player2
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 3) => {
-            game.iron_tile_count_player2 = game.iron_tile_count_player2
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 0) => {
-            game.gold_tile_count_player3 = game.gold_tile_count_player3
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 1) => {
-            game.silver_tile_count_player3 = game.silver_tile_count_player3
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 2) => {
-            game.bronze_tile_count_player3 = game.bronze_tile_count_player3
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 3) => {
-            game.iron_tile_count_player3 = game.iron_tile_count_player3
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 0) => {
-            game.gold_tile_count_player4 = game.gold_tile_count_player4
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 1) => {
-            game.silver_tile_count_player4 = game.silver_tile_count_player4
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 2) => {
-            game.bronze_tile_count_player4 = game.bronze_tile_count_player4
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 3) => {
-            game.iron_tile_count_player4 = game.iron_tile_count_player4
-                .checked_add(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        _ => return Err(HexoneError::Invalid.into()),
-    }
+    let count = game.slot_mut(color)?
+        .tier_tile_counts
+        .get_mut(tier as usize)
+        .ok_or(HexoneError::Invalid)?;
+    *count = count.checked_add(1).ok_or(HexoneError::Invalid)?;
     Ok(())
 }
 
@@ -238,369 +245,872 @@ pub fn update_tier_count_on_gain(
 /// tier: 0 = gold, 1 = silver, 2 = bronze, 3 = iron, 4 = base tile (not tracked, returns Ok)
 pub fn update_tier_count_on_loss(
     game: &mut Game,
-    player_index: usize,
+    color: u8,
     tier: u8,
 ) -> Result<()> {
     // Base tiles (tier 4) are not tracked, just return Ok
     if tier == 4 {
         return Ok(());
     }
-    match (player_index, tier) {
-        (1, 0) => {
-            game.gold_tile_count_player1 = game.gold_tile_count_player1
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (1, 1) => {
-            game.silver_tile_count_player1 = game.silver_tile_count_player1
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (1, 2) => {
-            game.bronze_tile_count_player1 = game.bronze_tile_count_player1
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (1, 3) => {
-            game.iron_tile_count_player1 = game.iron_tile_count_player1
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 0) => {
-            game.gold_tile_count_player2 = game.gold_tile_count_player2
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 1) => {
-            game.silver_tile_count_player2 = game.silver_tile_count_player2
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 2) => {
-            game.bronze_tile_count_player2 = game.bronze_tile_count_player2
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (2, 3) => {
-            game.iron_tile_count_player2 = game.iron_tile_count_player2
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 0) => {
-            game.gold_tile_count_player3 = game.gold_tile_count_player3
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 1) => {
-            game.silver_tile_count_player3 = game.silver_tile_count_player3
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 2) => {
-            game.bronze_tile_count_player3 = game.bronze_tile_count_player3
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (3, 3) => {
-            game.iron_tile_count_player3 = game.iron_tile_count_player3
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 0) => {
-            game.gold_tile_count_player4 = game.gold_tile_count_player4
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 1) => {
-            game.silver_tile_count_player4 = game.silver_tile_count_player4
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
-        }
-        (4, 2) => {
-            game.bronze_tile_count_player4 = game.bronze_tile_count_player4
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
+    let count = game.slot_mut(color)?
+        .tier_tile_counts
+        .get_mut(tier as usize)
+        .ok_or(HexoneError::Invalid)?;
+    *count = count.checked_sub(1).ok_or(HexoneError::Invalid)?;
+    Ok(())
+}
+
+/// Take a resigning colour off the board
+/// Its tiles go neutral, emptied like tiles nobody has claimed yet, or to `beneficiary` (a colour
+/// still in play, if the game allows it) with their resources. The colour is then eliminated
+/// at `current_time`. Returns how many tiles it held
+pub fn resign_player(
+    game: &mut Game,
+    tiles: &mut [TileData],
+    color: u8,
+    beneficiary: u8,
+    current_time: i64,
+) -> Result<u32> {
+    let resigning = *game.slot(color)?;
+    require!(resigning.in_play(), HexoneError::PlayerNotInPlay);
+    // Attacks in flight would otherwise resolve for a colour that's no longer on the board
+    require!(resigning.open_attacks == 0, HexoneError::OpenAttacksPending);
+    if beneficiary != 0 {
+        require!(
+            game.resign_beneficiary_enabled != 0
+                && beneficiary != color
+                && game.slot(beneficiary).is_ok_and(|slot| slot.in_play()),
+            HexoneError::InvalidResignBeneficiary
+        );
+    }
+
+    let mut tiles_held: u32 = 0;
+    for tile in tiles.iter_mut().filter(|tile| tile.color == color) {
+        if beneficiary == 0 {
+            *tile = TileData::zeroed();
+        } else {
+            tile.color = beneficiary;
         }
-        (4, 3) => {
-            game.iron_tile_count_player4 = game.iron_tile_count_player4
-                .checked_sub(1)
-                .ok_or(HexoneError::Invalid)?;
+        tiles_held += 1;
+    }
+
+    if beneficiary != 0 {
+        let slot = game.slot_mut(beneficiary)?;
+        slot.tile_count = slot.tile_count
+            .checked_add(resigning.tile_count)
+            .ok_or(HexoneError::Invalid)?;
+        for (count, gained) in slot.tier_tile_counts.iter_mut().zip(resigning.tier_tile_counts) {
+            *count = count.checked_add(gained).ok_or(HexoneError::Invalid)?;
         }
-        _ => return Err(HexoneError::Invalid.into()),
     }
-    Ok(())
+
+    let slot = game.slot_mut(color)?;
+    slot.tile_count = 0;
+    slot.tier_tile_counts = [0; 4];
+    slot.resigned = 1;
+    game.record_elimination(color, current_time)?;
+    Ok(tiles_held)
 }
 
-/// Calculate tier bonus XP for a player
-/// Returns: minutes_elapsed * (gold_count * gold_xp + silver_count * silver_xp + bronze_count * bronze_xp + iron_count * iron_xp)
-pub fn calculate_tier_bonus_xp(
-    minutes_elapsed: u32,
-    gold_count: u8,
-    silver_count: u8,
-    bronze_count: u8,
-    iron_count: u8,
-    gold_xp_per_min: u8,
-    silver_xp_per_min: u8,
-    bronze_xp_per_min: u8,
-    iron_xp_per_min: u8,
-) -> Result<u32> {
-    // Calculate XP per minute for each tier
-    let gold_xp = (gold_count as u32)
-        .checked_mul(gold_xp_per_min as u32)
-        .ok_or(HexoneError::Invalid)?;
-    
-    let silver_xp = (silver_count as u32)
-        .checked_mul(silver_xp_per_min as u32)
-        .ok_or(HexoneError::Invalid)?;
-    
-    let bronze_xp = (bronze_count as u32)
-        .checked_mul(bronze_xp_per_min as u32)
-        .ok_or(HexoneError::Invalid)?;
-    
-    let iron_xp = (iron_count as u32)
-        .checked_mul(iron_xp_per_min as u32)
-        .ok_or(HexoneError::Invalid)?;
-    
-    // Sum all tier XP per minute
-    let total_xp_per_min = gold_xp
-        .checked_add(silver_xp)
-        .and_then(|x| x.checked_add(bronze_xp))
-        .and_then(|x| x.checked_add(iron_xp))
-        .ok_or(HexoneError::Invalid)?;
-    
-    // Multiply by minutes elapsed
-    let total_bonus_xp = total_xp_per_min
-        .checked_mul(minutes_elapsed)
-        .ok_or(HexoneError::Invalid)?;
-    
-    Ok(total_bonus_xp)
+/// Void an attack whose colour no longer holds any of the tiles it attacked from
+/// They were captured while the attack was pending, or the colour was eliminated since (an
+/// eliminated colour holds no tiles, so all its open attacks end up here). The tiles are
+/// unlocked for their new owners and the attack comes off the colour's open attacks without
+/// a roll. Returns whether the attack was void
+pub fn void_lost_attack(game: &mut Game, tiles: &mut [TileData], defender: &mut Defender) -> Result<bool> {
+    let color = defender.attacker_tile_color;
+    if defender
+        .contributor_tiles()
+        .iter()
+        .any(|tile_index| tiles[*tile_index as usize].color == color)
+    {
+        return Ok(false);
+    }
+
+    for tile_index in defender.contributor_tiles() {
+        let tile = &mut tiles[*tile_index as usize];
+        tile.pending_attacks = tile.pending_attacks.saturating_sub(1);
+    }
+    let open_attacks = game.open_attacks_mut(color)?;
+    *open_attacks = open_attacks.saturating_sub(1);
+    defender.is_attack_resolved = true;
+    defender.attacker_won = false;
+    Ok(true)
+}
+
+/// Finishing order of the seated players, best first
+/// 1. Players who reached winning_xp_limit, earliest second first
+/// 2. Then whoever was further past the limit in that second (crossed earlier within it)
+/// 3. Then players still on the board, ahead of eliminated ones (last out first)
+/// 4. Then most XP, then most tiles held
+/// 5. Then lowest colour (earliest seat)
+///
+/// The crossing keys are fixed the second a player reaches the limit, so who wins doesn't
+/// depend on when the check happens to run
+pub fn rank_players(players: &[PlayerSlot]) -> Vec<u8> {
+    let mut colors: Vec<u8> = (1..=players.len() as u8)
+        .filter(|color| players[(*color - 1) as usize].wallet != Pubkey::default())
+        .collect();
+    colors.sort_by_key(|color| {
+        let slot = &players[(*color - 1) as usize];
+        let reached = slot.limit_reached_at != 0;
+        (
+            !reached,
+            slot.limit_reached_at,
+            Reverse(slot.xp_past_limit),
+            slot.eliminated_order != 0,
+            Reverse(slot.eliminated_order),
+            Reverse(slot.xp),
+            Reverse(slot.tile_count),
+            *color,
+        )
+    });
+    colors
 }
 
-/// Check if any player has exceeded the winning XP limit and update game state accordingly
-/// When limit is reached, calculates totals for all players and sets the highest as winner
+/// Determine the winner once any player has reached the winning XP limit, or only one is left
+/// in play (last standing). XP must already be accrued to current_time (see economy::accrue_xp),
+/// which records the exact second each player reached the limit. Records the winner, how they
+/// won, that second, the final standings and opens the payout claim window
 pub fn check_for_winner(game: &mut Game, current_time: i64) -> Result<()> {
     // Only check if game is still in progress
-    if game.game_state != GAME_STATE_IN_PROGRESS {
+    if game.game_state != GAME_STATE_IN_PROGRESS || game.winner_calculation_flag != 0 {
         return Ok(());
     }
-    
-    // Check each player's XP against the limit
-    let players = [
-        (game.player1, game.xp_player1),
-        (game.player2, game.xp_player2),
-        (game.player3, game.xp_player3),
-        (game.player4, game.xp_player4),
-    ];
-    
-    // Check if any player has reached the limit (trigger flag)
-    let mut limit_reached = false;
-    for (player_pubkey, xp) in players.iter() {
-        // Skip default/empty pubkeys
-        if *player_pubkey == Pubkey::default() {
-            continue;
+
+    let limit_reached = game
+        .seated_players()
+        .iter()
+        .any(|slot| slot.wallet != Pubkey::default() && slot.limit_reached_at != 0);
+    let players_in_play = game.seated_players().iter().filter(|slot| slot.in_play()).count();
+    if !limit_reached && players_in_play > 1 {
+        return Ok(());
+    }
+
+    let standings = rank_players(game.seated_players());
+    let winner = *game.slot(standings[0])?;
+
+    game.winner_calculation_flag = 1;
+    game.winning_player_pubkey = winner.wallet;
+    game.winner_reached_limit_at = winner.limit_reached_at;
+    game.win_condition = if limit_reached {
+        WIN_CONDITION_XP_LIMIT
+    } else {
+        WIN_CONDITION_LAST_STANDING
+    };
+    game.record_standings(&standings)?;
+    game.payout_deadline = current_time.saturating_add(PAYOUT_CLAIM_WINDOW_SECONDS);
+    game.game_state = GAME_STATE_WINNER_FOUND_NOT_PAID_OUT;
+
+    emit!(WinnerDetermined {
+        game_id: game.game_id,
+        winner: winner.wallet,
+        color: standings[0],
+        xp: winner.xp as u64,
+        reached_limit_at: winner.limit_reached_at,
+        win_condition: game.win_condition,
+        standings,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+impl Game {
+    /// Tile each colour starts on
+    /// Four players take the corners; two take opposite corners; three, five and six
+    /// add the middle of the bottom and top edges
+    pub fn starting_tile_index(&self, color: u8) -> Result<u16> {
+        let rows = self.rows as u16;
+        let columns = self.columns as u16;
+        let top_left = 0;
+        let top_right = columns - 1;
+        let bottom_left = (rows - 1) * columns;
+        let bottom_right = rows * columns - 1;
+        let top_middle = (columns - 1) / 2;
+        let bottom_middle = bottom_left + (columns - 1) / 2;
+
+        let layout: &[u16] = match self.max_players {
+            2 => &[top_left, bottom_right],
+            3 => &[top_left, top_right, bottom_middle],
+            4 => &[top_left, top_right, bottom_left, bottom_right],
+            5 => &[top_left, top_right, bottom_left, bottom_right, top_middle],
+            6 => &[top_left, top_right, bottom_left, bottom_right, top_middle, bottom_middle],
+            _ => return Err(HexoneError::Invalid.into()),
+        };
+        let i = self.color_index(color)?;
+        Ok(layout[i])
+    }
+
+    /// Array index for a player colour (1..=max_players)
+    pub fn color_index(&self, color: u8) -> Result<usize> {
+        require!(color >= 1 && color <= self.max_players, HexoneError::Invalid);
+        Ok((color - 1) as usize)
+    }
+
+    /// Slot for a player colour (1..=max_players)
+    pub fn slot(&self, color: u8) -> Result<&PlayerSlot> {
+        let i = self.color_index(color)?;
+        Ok(&self.players[i])
+    }
+
+    pub fn slot_mut(&mut self, color: u8) -> Result<&mut PlayerSlot> {
+        let i = self.color_index(color)?;
+        Ok(&mut self.players[i])
+    }
+
+    /// Slots in play for this game (colours 1..=max_players), including seats not yet taken
+    pub fn seated_players(&self) -> &[PlayerSlot] {
+        &self.players[..self.max_players as usize]
+    }
+
+    /// Wallet playing a colour, or the default pubkey for an empty or unknown colour
+    pub fn player_wallet(&self, color: u8) -> Pubkey {
+        self.slot(color).map_or(Pubkey::default(), |slot| slot.wallet)
+    }
+
+    /// Colour (1..=max_players) of a player in this game, or 0 if they aren't in it
+    pub fn player_color(&self, wallet: &Pubkey) -> u8 {
+        if *wallet == Pubkey::default() {
+            return 0;
         }
-        
-        // Check if XP exceeds limit
-        if (*xp as u64) >= game.winning_xp_limit {
-            limit_reached = true;
-            break;
+        self.seated_players()
+            .iter()
+            .position(|slot| slot.wallet == *wallet)
+            .map_or(0, |i| (i + 1) as u8)
+    }
+
+    /// XP totals per colour (index 0 = red)
+    pub fn player_xp(&self) -> [u32; MAX_PLAYERS] {
+        let mut xp = [0; MAX_PLAYERS];
+        for (i, slot) in self.players.iter().enumerate() {
+            xp[i] = slot.xp;
         }
+        xp
     }
-    
-    // If limit reached and flag not set, calculate all totals and find highest
-    if limit_reached && game.winner_calculation_flag == 0 {
-        game.winner_calculation_flag = 1;
-        
-        // Calculate total XP for each player (including simulated XP from current time)
-        let mut player_totals: Vec<(Pubkey, u64)> = Vec::new();
-        
-        // Player 1
-        if game.player1 != Pubkey::default() && game.xp_timestamp_player1 > 0 {
-            let time_diff = current_time - game.xp_timestamp_player1;
-            let mut total_xp = game.xp_player1 as u64;
-            
-            if time_diff > 60 {
-                let minutes_elapsed = (time_diff / 60) as u32;
-                // Calculate base XP
-                let base_xp = (minutes_elapsed as u64)
-                    .checked_mul(game.xp_per_minute_per_tile as u64)
-                    .and_then(|x| x.checked_mul(game.tile_count_color1 as u64))
-                    .ok_or(HexoneError::Invalid)?;
-                
-                // Calculate tier bonus XP
-                let tier_bonus_xp = calculate_tier_bonus_xp(
-                    minutes_elapsed,
-                    game.gold_tile_count_player1,
-                    game.silver_tile_count_player1,
-                    game.bronze_tile_count_player1,
-                    game.iron_tile_count_player1,
-                    game.gold_tier_bonus_xp_per_min,
-                    game.silver_tier_bonus_xp_per_min,
-                    game.bronze_tier_bonus_xp_per_min,
-                    game.iron_tier_bonus_xp_per_min,
-                )?;
-                
-                total_xp = total_xp
-                    .checked_add(base_xp)
-                    .and_then(|x| x.checked_add(tier_bonus_xp as u64))
-                    .ok_or(HexoneError::Invalid)?;
+
+    /// Emit XpAccrued for every colour whose XP went up since xp_before
+    pub fn emit_xp_accrued(&self, xp_before: [u32; MAX_PLAYERS], timestamp: i64) {
+        for (i, total_xp) in self.player_xp().iter().enumerate() {
+            if *total_xp > xp_before[i] {
+                emit!(XpAccrued {
+                    game_id: self.game_id,
+                    color: (i + 1) as u8,
+                    xp_added: total_xp - xp_before[i],
+                    total_xp: *total_xp,
+                    timestamp,
+                });
             }
-            player_totals.push((game.player1, total_xp));
         }
-        
-        // Player 2
-        if game.player2 != Pubkey::default() && game.xp_timestamp_player2 > 0 {
-            let time_diff = current_time - game.xp_timestamp_player2;
-            let mut total_xp = game.xp_player2 as u64;
-            
-            if time_diff > 60 {
-                let minutes_elapsed = (time_diff / 60) as u32;
-                let base_xp = (minutes_elapsed as u64)
-                    .checked_mul(game.xp_per_minute_per_tile as u64)
-                    .and_then(|x| x.checked_mul(game.tile_count_color2 as u64))
-                    .ok_or(HexoneError::Invalid)?;
-                
-                let tier_bonus_xp = calculate_tier_bonus_xp(
-                    minutes_elapsed,
-                    game.gold_tile_count_player2,
-                    game.silver_tile_count_player2,
-                    game.bronze_tile_count_player2,
-                    game.iron_tile_count_player2,
-                    game.gold_tier_bonus_xp_per_min,
-                    game.silver_tier_bonus_xp_per_min,
-                    game.bronze_tier_bonus_xp_per_min,
-                    game.iron_tier_bonus_xp_per_min,
-                )?;
-                
-                total_xp = total_xp
-                    .checked_add(base_xp)
-                    .and_then(|x| x.checked_add(tier_bonus_xp as u64))
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            player_totals.push((game.player2, total_xp));
+    }
+
+    /// Record a colour as eliminated once it has lost its last tile
+    /// eliminated_order counts up from 1 in the order colours are knocked out. The colour stops
+    /// accruing from here; check_for_winner afterwards to end the game if one colour is left
+    pub fn record_elimination(&mut self, color: u8, current_time: i64) -> Result<()> {
+        let slot = self.slot(color)?;
+        if slot.tile_count != 0 || slot.eliminated_order != 0 {
+            return Ok(());
         }
-        
-        // Player 3
-        if game.player3 != Pubkey::default() && game.xp_timestamp_player3 > 0 {
-            let time_diff = current_time - game.xp_timestamp_player3;
-            let mut total_xp = game.xp_player3 as u64;
-            
-            if time_diff > 60 {
-                let minutes_elapsed = (time_diff / 60) as u32;
-                let base_xp = (minutes_elapsed as u64)
-                    .checked_mul(game.xp_per_minute_per_tile as u64)
-                    .and_then(|x| x.checked_mul(game.tile_count_color3 as u64))
-                    .ok_or(HexoneError::Invalid)?;
-                
-                let tier_bonus_xp = calculate_tier_bonus_xp(
-                    minutes_elapsed,
-                    game.gold_tile_count_player3,
-                    game.silver_tile_count_player3,
-                    game.bronze_tile_count_player3,
-                    game.iron_tile_count_player3,
-                    game.gold_tier_bonus_xp_per_min,
-                    game.silver_tier_bonus_xp_per_min,
-                    game.bronze_tier_bonus_xp_per_min,
-                    game.iron_tier_bonus_xp_per_min,
-                )?;
-                
-                total_xp = total_xp
-                    .checked_add(base_xp)
-                    .and_then(|x| x.checked_add(tier_bonus_xp as u64))
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            player_totals.push((game.player3, total_xp));
+        let already_out = self
+            .seated_players()
+            .iter()
+            .filter(|slot| slot.eliminated_order != 0)
+            .count() as u8;
+        let slot = self.slot_mut(color)?;
+        slot.eliminated_order = already_out + 1;
+        slot.eliminated_at = current_time;
+        let (wallet, eliminated_order) = (slot.wallet, slot.eliminated_order);
+
+        emit!(PlayerEliminated {
+            game_id: self.game_id,
+            wallet,
+            color,
+            eliminated_order,
+            players_left: self.seated_players().iter().filter(|slot| slot.in_play()).count() as u8,
+            timestamp: current_time,
+        });
+        Ok(())
+    }
+
+    /// Record the final standings, best first, from each colour's slot as it is now
+    pub fn record_standings(&mut self, colors: &[u8]) -> Result<()> {
+        self.standings = [FinalStanding::zeroed(); MAX_PLAYERS];
+        for (place, color) in colors.iter().enumerate() {
+            let slot = *self.slot(*color)?;
+            self.standings[place] = FinalStanding {
+                color: *color,
+                eliminated_order: slot.eliminated_order,
+                tile_count: slot.tile_count,
+                xp: slot.xp,
+                ..FinalStanding::zeroed()
+            };
         }
-        
-        // Player 4
-        if game.player4 != Pubkey::default() && game.xp_timestamp_player4 > 0 {
-            let time_diff = current_time - game.xp_timestamp_player4;
-            let mut total_xp = game.xp_player4 as u64;
-            
-            if time_diff > 60 {
-                let minutes_elapsed = (time_diff / 60) as u32;
-                let base_xp = (minutes_elapsed as u64)
-                    .checked_mul(game.xp_per_minute_per_tile as u64)
-                    .and_then(|x| x.checked_mul(game.tile_count_color4 as u64))
-                    .ok_or(HexoneError::Invalid)?;
-                
-                let tier_bonus_xp = calculate_tier_bonus_xp(
-                    minutes_elapsed,
-                    game.gold_tile_count_player4,
-                    game.silver_tile_count_player4,
-                    game.bronze_tile_count_player4,
-                    game.iron_tile_count_player4,
-                    game.gold_tier_bonus_xp_per_min,
-                    game.silver_tier_bonus_xp_per_min,
-                    game.bronze_tier_bonus_xp_per_min,
-                    game.iron_tier_bonus_xp_per_min,
-                )?;
-                
-                total_xp = total_xp
-                    .checked_add(base_xp)
-                    .and_then(|x| x.checked_add(tier_bonus_xp as u64))
-                    .ok_or(HexoneError::Invalid)?;
-            }
-            player_totals.push((game.player4, total_xp));
+        Ok(())
+    }
+
+    /// Finishing place (0 = first) of a colour in the final standings
+    pub fn standing_place(&self, color: u8) -> Option<usize> {
+        if color == 0 {
+            return None;
         }
-        
-        // Find player with highest total XP
-        if let Some((winner_pubkey, _)) = player_totals.iter().max_by_key(|(_, xp)| xp) {
-            game.winning_player_pubkey = *winner_pubkey;
-            game.game_state = GAME_STATE_WINNER_FOUND_NOT_PAID_OUT;
+        self.standings.iter().position(|standing| standing.color == color)
+    }
+
+    /// Lock in the prize pool: take the platform fee out of `pool`, then split the rest between
+    /// the finishing places by payout_bps. Returns the platform fee
+    pub fn lock_payouts(&mut self, pool: u64) -> u64 {
+        let platform_fee = platform_fee(pool, self.fee_bps);
+        let prize_pool = pool - platform_fee;
+        let places = self.standings.iter().filter(|standing| standing.color != 0).count();
+        let payouts = split_prize_pool(prize_pool, &self.payout_bps, places);
+        self.platform_fee = platform_fee;
+        self.prize_pool = prize_pool;
+        for (standing, payout) in self.standings.iter_mut().zip(payouts) {
+            standing.payout = payout;
+        }
+        platform_fee
+    }
+
+    /// Crank reward for the next tick: crank_reward_lamports, until the rewards paid reach
+    /// MAX_CRANK_REWARDS_BPS of the entry fees, so keepers can't drain the prize pool
+    pub fn crank_reward_due(&self) -> u64 {
+        let entry_fees = self.entry_cost.saturating_mul(self.player_count as u64);
+        let cap = entry_fees.saturating_mul(MAX_CRANK_REWARDS_BPS) / PAYOUT_BPS_DENOMINATOR as u64;
+        self.crank_reward_lamports
+            .min(cap.saturating_sub(self.crank_rewards_paid))
+    }
+
+    /// Whether any place still has to claim: first place always does, other places only
+    /// when they're owed something
+    pub fn payouts_outstanding(&self) -> bool {
+        self.standings.iter().enumerate().any(|(place, standing)| {
+            standing.color != 0 && standing.payout_claimed == 0 && (place == 0 || standing.payout > 0)
+        })
+    }
+
+    /// Open attack counter for a player colour
+    pub fn open_attacks_mut(&mut self, color: u8) -> Result<&mut u8> {
+        Ok(&mut self.slot_mut(color)?.open_attacks)
+    }
+
+    /// Seconds the defender can reinforce an attacked tile (games from before the setting get the minimum)
+    pub fn reinforce_window(&self) -> i64 {
+        (self.reinforce_window_seconds as i64).max(MIN_ATTACK_DURATION)
+    }
+
+    pub fn combat_settings(&self) -> CombatSettings {
+        CombatSettings {
+            combat_model: self.combat_model,
+            resources_per_combat_die: self.resources_per_combat_die,
+            max_combat_dice: self.max_combat_dice,
         }
     }
-    
-    Ok(())
-}
 
-impl Game {
     pub const LEN: usize = 8     // discriminator
         + 32                     // admin
-        + 32                     // player1
-        + 32                     // player2
-        + 32                     // player3
-        + 32                     // player4
+        + (PlayerSlot::LEN * MAX_PLAYERS) // players
         + 8                      // game_id
         + 8                      // available_resources_timestamp
-        + 8                      // xp_timestamp_player1
-        + 8                      // xp_timestamp_player2
-        + 8                      // xp_timestamp_player3
-        + 8                      // xp_timestamp_player4
         + 4                      // resources_per_minute
         + 4                      // total_resources_available
-        + 4                      // resources_spent_player1
-        + 4                      // resources_spent_player2
-        + 4                      // resources_spent_player3
-        + 4                      // resources_spent_player4
         + 4                      // xp_per_minute_per_tile
-        + 4                      // xp_player1
-        + 4                      // xp_player2
-        + 4                      // xp_player3
-        + 4                      // xp_player4
-        + 4                      // tile_count_color1
-        + 4                      // tile_count_color2
-        + 4                      // tile_count_color3
-        + 4                      // tile_count_color4
-        + 4                      // padding to align to 8 bytes
-        + (144 * 4)              // tile_data (144 * 4)
-        + 16                     // tier counts (4 players * 4 tiers = 16 u8)
+        + 4                      // resources_remainder
         + 4                      // tier bonus XP per minute (4 u8)
         + 4                      // padding to align to 8 bytes after tier bonus XP
         + 32                     // winning_player_pubkey
         + 8                      // winning_xp_limit
+        + 8                      // entry_cost
+        + 32                     // vrf_authority
         + 6                      // game_state + rows + columns + version + bump + winner_calculation_flag
         + 2                      // max_hit_threshold (u16)
         + 1                      // max_hit_resource_count (u8)
-        + 7;                     // padding to align to 8 bytes
+        + 1                      // commit_reveal_enabled (u8)
+        + 1                      // randomness_source (u8)
+        + 1                      // expired_attack_penalty (u8)
+        + 1                      // combat_model (u8)
+        + 1                      // max_combat_dice (u8)
+        + 2                      // resources_per_combat_die (u16)
+        + 4                      // tile_attack_cooldown_seconds (u32)
+        + 1                      // max_open_attacks_per_player (u8)
+        + 2                      // max_players + player_count
+        + 1                      // resign_beneficiary_enabled (u8)
+        + 2                      // starting_resources (u16)
+        + 2                      // reinforce_window_seconds (u16)
+        + 4                      // padding to align to 8 bytes
+        + 8                      // crank_reward_lamports
+        + 8                      // winner_reached_limit_at
+        + 8                      // prize_pool
+        + 8                      // platform_fee
+        + 8                      // payout_deadline
+        + (FinalStanding::LEN * MAX_PLAYERS) // standings
+        + (2 * MAX_PLAYERS)      // payout_bps
+        + 2                      // fee_bps
+        + 1                      // win_condition
+        + 1                      // padding to align to 8 bytes
+        + 8                      // join_deadline
+        + 8;                     // crank_rewards_paid
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 #[repr(C)]
 pub struct TileData {
-    pub color: u8,              // 1-4 for red, yellow, blue, green
-    pub _pad: u8,              // padding for alignment
+    pub color: u8,              // 0 = empty, else the owning player's colour (1..=max_players)
+    pub pending_attacks: u8,    // unresolved attacks launched from this tile (locks withdrawals)
     pub resource_count: u16,    // resources on this tile
+    pub _padding: [u8; 4],      // padding to align to 8 bytes
+    pub attack_cooldown_until: i64, // this tile can't attack before this timestamp
+}
+
+impl TileData {
+    pub const LEN: usize = 16;
+}
+
+/// Game header together with the tiles that trail it in the same account
+/// Derefs to Game, so `game.rows` and `game.tile_data[i]` both work on a loaded board
+pub struct GameBoard<'a> {
+    game: RefMut<'a, Game>,
+    pub tile_data: RefMut<'a, [TileData]>,
+}
+
+impl<'a> GameBoard<'a> {
+    /// Take a resigning colour off the board, see resign_player
+    pub fn resign(&mut self, color: u8, beneficiary: u8, current_time: i64) -> Result<u32> {
+        resign_player(&mut self.game, &mut self.tile_data, color, beneficiary, current_time)
+    }
+
+    /// Void an attack that lost all its attacking tiles, see void_lost_attack
+    pub fn void_if_lost(&mut self, defender: &mut Defender) -> Result<bool> {
+        void_lost_attack(&mut self.game, &mut self.tile_data, defender)
+    }
+}
+
+impl<'a> Deref for GameBoard<'a> {
+    type Target = Game;
+
+    fn deref(&self) -> &Game {
+        &self.game
+    }
+}
+
+impl<'a> DerefMut for GameBoard<'a> {
+    fn deref_mut(&mut self) -> &mut Game {
+        &mut self.game
+    }
+}
+
+impl Game {
+    /// Account size for a board of rows * columns tiles
+    pub fn space(rows: u8, columns: u8) -> usize {
+        Game::LEN + (rows as usize) * (columns as usize) * TileData::LEN
+    }
+
+    /// Number of tiles on this board, all tile indices must be below this
+    pub fn tile_count(&self) -> u16 {
+        (self.rows as u16) * (self.columns as u16)
+    }
+
+    /// Load the game and its tiles for writing (like AccountLoader::load_mut)
+    pub fn load_board_mut<'a>(loader: &'a AccountLoader<'_, Game>) -> Result<GameBoard<'a>> {
+        let info = loader.as_ref();
+        require!(info.is_writable, ErrorCode::AccountNotMutable);
+
+        let data = info.try_borrow_mut_data()?;
+        let tile_count = Game::check_header(&data)?.tile_count() as usize;
+
+        Game::split_board(data, tile_count)
+    }
+
+    /// Load the game without its tiles for reading (like AccountLoader::load)
+    pub fn load_header<'a>(loader: &'a AccountLoader<'_, Game>) -> Result<Ref<'a, Game>> {
+        let data = loader.as_ref().try_borrow_data()?;
+        Game::check_header(&data)?;
+
+        Ok(Ref::map(data, |data| {
+            bytemuck::from_bytes(&data[Game::DISCRIMINATOR.len()..Game::LEN])
+        }))
+    }
+
+    /// Check a Game account is in the current layout and return its header
+    fn check_header(data: &[u8]) -> Result<&Game> {
+        require!(data.len() >= Game::LEN, ErrorCode::AccountDiscriminatorNotFound);
+        require!(
+            &data[..Game::DISCRIMINATOR.len()] == Game::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require!(data.len() != GameV1::LEN, HexoneError::GameNeedsMigration);
+        let header: &Game = bytemuck::from_bytes(&data[Game::DISCRIMINATOR.len()..Game::LEN]);
+        require!(header.version == GAME_VERSION, HexoneError::GameNeedsMigration);
+        Ok(header)
+    }
+
+    /// Load a freshly created game and its tiles (like AccountLoader::load_init)
+    pub fn load_board_init<'a>(
+        loader: &'a AccountLoader<'_, Game>,
+        rows: u8,
+        columns: u8,
+    ) -> Result<GameBoard<'a>> {
+        let info = loader.as_ref();
+        require!(info.is_writable, ErrorCode::AccountNotMutable);
+
+        let data = info.try_borrow_mut_data()?;
+        require!(
+            data[..Game::DISCRIMINATOR.len()].iter().all(|b| *b == 0),
+            ErrorCode::AccountDiscriminatorAlreadySet
+        );
+
+        Game::split_board(data, (rows as usize) * (columns as usize))
+    }
+
+    fn split_board<'a>(data: RefMut<'a, &mut [u8]>, tile_count: usize) -> Result<GameBoard<'a>> {
+        require!(
+            data.len() >= Game::LEN + tile_count * TileData::LEN,
+            ErrorCode::AccountDidNotDeserialize
+        );
+
+        let (game, tile_data) = RefMut::map_split(data, |data| {
+            let (header, tiles) = data.split_at_mut(Game::LEN);
+            (
+                bytemuck::from_bytes_mut(&mut header[Game::DISCRIMINATOR.len()..]),
+                bytemuck::cast_slice_mut(&mut tiles[..tile_count * TileData::LEN]),
+            )
+        });
+
+        Ok(GameBoard { game, tile_data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tier_centre_follows_the_board_size() {
+        // 11x13: centre at row 5, col 6
+        assert_eq!(get_tile_tier(5 * 13 + 6, 11, 13), 0);
+        assert_eq!(get_tile_tier(5 * 13 + 7, 11, 13), 1);
+        assert_eq!(get_tile_tier(0, 11, 13), 4);
+
+        // 7x7: centre at row 3, col 3
+        assert_eq!(get_tile_tier(3 * 7 + 3, 7, 7), 0);
+        assert_eq!(get_tile_tier(3 * 7 + 4, 7, 7), 1);
+
+        // 15x15: centre at row 7, col 7
+        assert_eq!(get_tile_tier(7 * 15 + 7, 15, 15), 0);
+        assert_eq!(get_tile_tier(5 * 13 + 6, 15, 15), 4);
+    }
+
+    fn board(rows: u8, columns: u8, max_players: u8) -> Game {
+        let mut game = Game::zeroed();
+        game.rows = rows;
+        game.columns = columns;
+        game.max_players = max_players;
+        game
+    }
+
+    #[test]
+    fn starting_tiles_cover_every_player_count() {
+        let starts = |game: &Game| -> Vec<u16> {
+            (1..=game.max_players).map(|color| game.starting_tile_index(color).unwrap()).collect()
+        };
+
+        // Classic corners for four players on 11x13
+        assert_eq!(starts(&board(11, 13, 4)), vec![0, 12, 130, 142]);
+        // Duels start in opposite corners
+        assert_eq!(starts(&board(11, 13, 2)), vec![0, 142]);
+        assert_eq!(starts(&board(11, 13, 3)), vec![0, 12, 136]);
+        assert_eq!(starts(&board(11, 13, 6)), vec![0, 12, 130, 142, 6, 136]);
+
+        // Every start is distinct even on the smallest board
+        for max_players in MIN_PLAYERS..=MAX_PLAYERS as u8 {
+            let mut tiles = starts(&board(5, 5, max_players));
+            tiles.sort();
+            tiles.dedup();
+            assert_eq!(tiles.len(), max_players as usize);
+        }
+
+        // Colours beyond max_players have no seat
+        assert!(board(11, 13, 2).starting_tile_index(3).is_err());
+    }
+
+    #[test]
+    fn player_color_only_sees_seated_players() {
+        let mut game = board(11, 13, 2);
+        let wallet = Pubkey::new_unique();
+        game.players[1].wallet = wallet;
+        assert_eq!(game.player_color(&wallet), 2);
+        assert_eq!(game.player_wallet(2), wallet);
+        assert_eq!(game.player_color(&Pubkey::default()), 0);
+
+        // A stale wallet outside max_players is ignored
+        game.players[4].wallet = Pubkey::new_unique();
+        assert_eq!(game.player_color(&game.players[4].wallet.clone()), 0);
+    }
+
+    #[test]
+    fn space_grows_with_the_board() {
+        assert_eq!(Game::space(11, 13), Game::LEN + 143 * TileData::LEN);
+        assert_eq!(Game::space(7, 7), Game::LEN + 49 * TileData::LEN);
+        assert_eq!(std::mem::size_of::<TileData>(), TileData::LEN);
+    }
+
+    fn seated(max_players: u8) -> Game {
+        let mut game = board(11, 13, max_players);
+        game.game_state = GAME_STATE_IN_PROGRESS;
+        game.winning_xp_limit = 100;
+        for slot in game.players[..max_players as usize].iter_mut() {
+            slot.wallet = Pubkey::new_unique();
+            slot.tile_count = 1;
+        }
+        game
+    }
+
+    #[test]
+    fn earliest_crossing_wins_regardless_of_xp_or_seat() {
+        let mut game = seated(4);
+        game.players[0].xp = 100;
+        game.players[0].limit_reached_at = 1_000;
+        // Yellow got there a second earlier, even though red has since earned more
+        game.players[1].xp = 101;
+        game.players[1].limit_reached_at = 999;
+        game.players[2].xp = 90;
+        game.players[3].xp = 95;
+
+        assert_eq!(rank_players(game.seated_players()), vec![2, 1, 4, 3]);
+    }
+
+    #[test]
+    fn ties_in_the_same_second_are_broken_in_order() {
+        let mut game = seated(4);
+        for slot in game.players[..4].iter_mut() {
+            slot.limit_reached_at = 1_000;
+            slot.xp = 100;
+        }
+        // Further past the limit in that second wins
+        game.players[3].xp_past_limit = 30;
+        // Then most XP, then most tiles, then lowest colour
+        game.players[2].xp = 101;
+        game.players[1].tile_count = 2;
+        assert_eq!(rank_players(game.seated_players()), vec![4, 3, 2, 1]);
+
+        // Empty seats are left out
+        game.players[1].wallet = Pubkey::default();
+        assert_eq!(rank_players(game.seated_players()), vec![4, 3, 1]);
+    }
+
+    #[test]
+    fn winner_and_standings_are_recorded_once() {
+        let mut game = seated(3);
+        check_for_winner(&mut game, 2_000).unwrap();
+        assert_eq!(game.game_state, GAME_STATE_IN_PROGRESS);
+
+        game.players[2].limit_reached_at = 1_500;
+        game.players[0].xp = 80;
+        check_for_winner(&mut game, 2_000).unwrap();
+        assert_eq!(game.game_state, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+        assert_eq!(game.winning_player_pubkey, game.players[2].wallet);
+        assert_eq!(game.winner_reached_limit_at, 1_500);
+        assert_eq!(game.win_condition, WIN_CONDITION_XP_LIMIT);
+        let colors: Vec<u8> = game.standings.iter().map(|standing| standing.color).collect();
+        assert_eq!(colors, vec![3, 1, 2, 0, 0, 0]);
+        assert_eq!(game.standings[1].xp, 80);
+        assert_eq!(game.standings[1].tile_count, 1);
+        assert_eq!(game.payout_deadline, 2_000 + PAYOUT_CLAIM_WINDOW_SECONDS);
+
+        // Later changes don't rewrite the standings
+        game.players[0].limit_reached_at = 1_400;
+        check_for_winner(&mut game, 2_100).unwrap();
+        assert_eq!(game.standings[0].color, 3);
+    }
+
+    #[test]
+    fn eliminated_players_rank_last_out_first() {
+        let mut game = seated(4);
+        game.players[0].limit_reached_at = 1_000;
+        // Yellow went out first with more XP, then green
+        game.players[1].xp = 90;
+        game.players[1].tile_count = 0;
+        game.record_elimination(2, 1_100).unwrap();
+        game.players[2].tile_count = 0;
+        game.record_elimination(2, 1_200).unwrap();
+        game.record_elimination(3, 1_200).unwrap();
+        assert_eq!((game.players[1].eliminated_order, game.players[2].eliminated_order), (1, 2));
+        assert_eq!((game.players[1].eliminated_at, game.players[2].eliminated_at), (1_100, 1_200));
+        // Blue still holds a tile, so it isn't eliminated
+        game.record_elimination(4, 1_300).unwrap();
+        assert_eq!(game.players[3].eliminated_order, 0);
+
+        assert_eq!(rank_players(game.seated_players()), vec![1, 4, 3, 2]);
+    }
+
+    #[test]
+    fn the_last_colour_standing_wins() {
+        let mut game = seated(3);
+        game.players[0].xp = 95;
+        for color in [1, 3] {
+            game.slot_mut(color).unwrap().tile_count = 0;
+            game.record_elimination(color, 1_000).unwrap();
+            check_for_winner(&mut game, 1_000).unwrap();
+        }
+        assert_eq!(game.game_state, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+        assert_eq!(game.win_condition, WIN_CONDITION_LAST_STANDING);
+        assert_eq!(game.winning_player_pubkey, game.players[1].wallet);
+        let colors: Vec<u8> = game.standings.iter().map(|standing| standing.color).collect();
+        assert_eq!(colors, vec![2, 3, 1, 0, 0, 0]);
+        assert_eq!(game.standings[2].eliminated_order, 1);
+    }
+
+    /// Every seated colour on its starting tile, with `resources` on it
+    fn starting_tiles(game: &Game, resources: u16) -> Vec<TileData> {
+        let mut tiles = vec![TileData::zeroed(); game.tile_count() as usize];
+        for color in 1..=game.max_players {
+            let tile = &mut tiles[game.starting_tile_index(color).unwrap() as usize];
+            tile.color = color;
+            tile.resource_count = resources;
+        }
+        tiles
+    }
+
+    #[test]
+    fn resigned_tiles_go_neutral_and_the_last_player_in_wins() {
+        let mut game = seated(3);
+        let mut tiles = starting_tiles(&game, 40);
+        game.players[1].xp = 70;
+
+        assert_eq!(resign_player(&mut game, &mut tiles, 2, 0, 2_000).unwrap(), 1);
+        assert_eq!(tiles[12], TileData::zeroed());
+        assert_eq!(tiles.iter().filter(|tile| tile.color != 0).count(), 2);
+        assert_eq!(game.players[1].tile_count, 0);
+        assert_eq!((game.players[1].eliminated_order, game.players[1].eliminated_at), (1, 2_000));
+        assert!(!game.players[1].in_play());
+        assert!(resign_player(&mut game, &mut tiles, 2, 0, 2_000).is_err());
+
+        check_for_winner(&mut game, 2_000).unwrap();
+        assert_eq!(game.game_state, GAME_STATE_IN_PROGRESS);
+
+        resign_player(&mut game, &mut tiles, 1, 0, 2_000).unwrap();
+        check_for_winner(&mut game, 2_000).unwrap();
+        assert_eq!(game.game_state, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+        assert_eq!(game.winning_player_pubkey, game.players[2].wallet);
+        assert_eq!(game.winner_reached_limit_at, 0);
+        assert_eq!(game.win_condition, WIN_CONDITION_LAST_STANDING);
+        // Red resigned last, so ranks ahead of yellow despite less XP
+        let colors: Vec<u8> = game.standings.iter().map(|standing| standing.color).collect();
+        assert_eq!(colors, vec![3, 1, 2, 0, 0, 0]);
     }
 
+    #[test]
+    fn resigned_tiles_can_go_to_a_beneficiary() {
+        let mut game = seated(4);
+        let mut tiles = starting_tiles(&game, 40);
+        game.players[0].tier_tile_counts = [0, 1, 0, 2];
+        game.players[0].tile_count = 4;
 
+        // Only in games that allow it
+        assert!(resign_player(&mut game, &mut tiles, 1, 3, 2_000).is_err());
+        game.resign_beneficiary_enabled = 1;
+        // Not to themselves, an empty seat or a colour that's out
+        assert!(resign_player(&mut game, &mut tiles, 1, 1, 2_000).is_err());
+        assert!(resign_player(&mut game, &mut tiles, 1, 5, 2_000).is_err());
+        resign_player(&mut game, &mut tiles, 4, 0, 2_000).unwrap();
+        assert!(resign_player(&mut game, &mut tiles, 1, 4, 2_000).is_err());
+
+        // Not with attacks still open
+        game.players[0].open_attacks = 1;
+        assert!(resign_player(&mut game, &mut tiles, 1, 3, 2_000).is_err());
+        game.players[0].open_attacks = 0;
+
+        resign_player(&mut game, &mut tiles, 1, 3, 2_000).unwrap();
+        assert_eq!(tiles[0].color, 3);
+        assert_eq!(tiles[0].resource_count, 40);
+        assert_eq!(game.players[2].tile_count, 5);
+        assert_eq!(game.players[2].tier_tile_counts, [0, 1, 0, 2]);
+        assert_eq!((game.players[0].tile_count, game.players[0].tier_tile_counts), (0, [0; 4]));
+        assert_eq!(game.players[0].eliminated_order, 2);
+    }
+
+    #[test]
+    fn attacks_from_captured_tiles_are_void() {
+        let mut game = seated(4);
+        let mut tiles = starting_tiles(&game, 40);
+        // Red attacks from its corner and from the tile next to it
+        tiles[1] = TileData { color: 1, resource_count: 10, ..TileData::zeroed() };
+        let mut defender = Defender {
+            attacker_tile_index: 0,
+            attacker_tile_color: 1,
+            contributor_count: 2,
+            ..Defender::default()
+        };
+        defender.contributor_tile_indices[..2].copy_from_slice(&[0, 1]);
+        tiles[0].pending_attacks = 1;
+        tiles[1].pending_attacks = 1;
+        game.players[0].open_attacks = 1;
+
+        // Losing one of the attacking tiles doesn't void the attack
+        tiles[0].color = 2;
+        assert!(!void_lost_attack(&mut game, &mut tiles, &mut defender).unwrap());
+        assert_eq!((tiles[0].pending_attacks, game.players[0].open_attacks), (1, 1));
+        assert!(!defender.is_attack_resolved);
+
+        // Once yellow has captured both, it's void and the tiles are unlocked for yellow
+        tiles[1].color = 2;
+        assert!(void_lost_attack(&mut game, &mut tiles, &mut defender).unwrap());
+        assert_eq!((tiles[0].pending_attacks, tiles[1].pending_attacks), (0, 0));
+        assert_eq!(game.players[0].open_attacks, 0);
+        assert!(defender.is_attack_resolved && !defender.attacker_won);
+        // Nothing changed hands
+        assert_eq!((tiles[0].color, tiles[0].resource_count), (2, 40));
+
+        // A single-tile attack is void as soon as its tile is captured
+        let mut defender = Defender {
+            attacker_tile_index: 12,
+            attacker_tile_color: 2,
+            contributor_count: 1,
+            ..Defender::default()
+        };
+        defender.contributor_tile_indices[0] = 12;
+        tiles[12].pending_attacks = 1;
+        game.players[1].open_attacks = 1;
+        assert!(!void_lost_attack(&mut game, &mut tiles, &mut defender).unwrap());
+        tiles[12].color = 3;
+        assert!(void_lost_attack(&mut game, &mut tiles, &mut defender).unwrap());
+        assert_eq!((tiles[12].pending_attacks, game.players[1].open_attacks), (0, 0));
+    }
+
+    #[test]
+    fn payouts_are_owed_until_every_paid_place_claims() {
+        let mut game = seated(4);
+        game.payout_bps = [7_000, 3_000, 0, 0, 0, 0];
+        game.fee_bps = 500;
+        game.players[3].limit_reached_at = 1_000;
+        check_for_winner(&mut game, 1_000).unwrap();
+        // The platform takes its 5% before the pool is split
+        assert_eq!(game.lock_payouts(1_000), 50);
+        assert_eq!((game.prize_pool, game.platform_fee), (950, 50));
+
+        assert_eq!(game.standing_place(4), Some(0));
+        assert_eq!(game.standing_place(0), None);
+        let payouts: Vec<u64> = game.standings.iter().map(|standing| standing.payout).collect();
+        assert_eq!(payouts, vec![665, 285, 0, 0, 0, 0]);
+
+        game.standings[0].payout_claimed = 1;
+        assert!(game.payouts_outstanding());
+        // Third and fourth place aren't owed anything
+        game.standings[1].payout_claimed = 1;
+        assert!(!game.payouts_outstanding());
+    }
+
+    #[test]
+    fn crank_rewards_stop_at_a_share_of_the_entry_fees() {
+        let mut game = seated(4);
+        game.player_count = 4;
+        game.entry_cost = 100_000_000;
+        game.crank_reward_lamports = 1_000_000;
+        // 1% of 0.4 SOL in entries covers four full rewards
+        assert_eq!(game.crank_reward_due(), 1_000_000);
+        game.crank_rewards_paid = 3_500_000;
+        assert_eq!(game.crank_reward_due(), 500_000);
+        game.crank_rewards_paid = 4_000_000;
+        assert_eq!(game.crank_reward_due(), 0);
+    }
+}
@@ -0,0 +1,273 @@
+use anchor_lang::prelude::*;
+
+use crate::combat::{CombatSettings, COMBAT_MODEL_FLAT};
+use crate::constants::*;
+use crate::error::HexoneError;
+use crate::randomness::{RandomnessSource, RANDOMNESS_SOURCE_SLOT_HASHES, RANDOMNESS_SOURCE_VRF};
+
+// Sane bounds for per-game rules
+pub const MIN_BOARD_SIZE: u8 = 5;                 // Min rows and columns
+pub const MAX_BOARD_TILES: usize = 576;           // rows * columns, keeps Game::space within one CPI allocation
+pub const MAX_STARTING_RESOURCES: u16 = 1_000;
+pub const MAX_ENTRY_COST: u64 = 100_000_000_000;  // 100 SOL in lamports
+pub const MAX_RESOURCES_PER_MINUTE: u32 = 1_000;
+pub const MAX_XP_PER_MINUTE_PER_TILE: u32 = 1_000;
+pub const MAX_WINNING_XP_LIMIT: u64 = 100_000_000;
+pub const MAX_HIT_RESOURCE_COUNT: u8 = 100;
+pub const MAX_TILE_ATTACK_COOLDOWN_SECONDS: u32 = 3_600;
+pub const MAX_CRANK_REWARD_LAMPORTS: u64 = 1_000_000; // 0.001 SOL per tick
+pub const MIN_JOIN_WINDOW_SECONDS: u32 = 60;
+pub const MAX_REINFORCE_WINDOW_SECONDS: u16 = 60;
+pub const MAX_JOIN_WINDOW_SECONDS: u32 = 7 * 24 * 60 * 60;
+
+// An attack's roll only starts after its reinforcement window, so the window must close
+// long before the attack expires
+const _: () = assert!((MAX_REINFORCE_WINDOW_SECONDS as i64) < ATTACK_EXPIRY_SECONDS / 2);
+
+/// Payout table that pays the whole prize pool to first place
+pub const WINNER_TAKES_ALL: [u16; MAX_PLAYERS] = [PAYOUT_BPS_DENOMINATOR, 0, 0, 0, 0, 0];
+
+/// Rules for a single game, passed to create_game and stored in Game
+/// Lets one deployment host casual, blitz and high-stakes games side by side
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct GameConfig {
+    // Board
+    pub rows: u8,
+    pub columns: u8,
+    pub max_players: u8,                  // Game starts once this many have joined (2-6)
+    pub starting_resources: u16,          // Resources on each player's starting tile
+    pub join_window_seconds: u32,         // Anyone can cancel the game if it hasn't filled by then
+    // Economy
+    pub entry_cost: u64,                  // Lamports each player pays into the treasury to join
+    pub resources_per_minute: u32,
+    pub xp_per_minute_per_tile: u32,
+    pub winning_xp_limit: u64,
+    pub gold_tier_bonus_xp_per_min: u8,
+    pub silver_tier_bonus_xp_per_min: u8,
+    pub bronze_tier_bonus_xp_per_min: u8,
+    pub iron_tier_bonus_xp_per_min: u8,
+    pub crank_reward_lamports: u64,       // Paid from the treasury to whoever runs tick_game (0 = none), up to MAX_CRANK_REWARDS_BPS of entries
+    pub payout_bps: [u16; MAX_PLAYERS],   // Prize pool share per finishing place, e.g. 7000/2000/1000
+    // Combat
+    pub max_hit_threshold: u16,           // Difference threshold for max hit (1-999)
+    pub max_hit_resource_count: u8,       // Max resources lost on a hit
+    pub combat_model: u8,                 // See combat.rs
+    pub resources_per_combat_die: u16,
+    pub max_combat_dice: u8,
+    pub expired_attack_penalty: u8,       // Lost by attackers who never resolve (0 = none)
+    pub tile_attack_cooldown_seconds: u32,
+    pub max_open_attacks_per_player: u8,  // 0 = no cap
+    pub reinforce_window_seconds: u16,    // Defender can reinforce for this long after an attack starts
+    pub resign_beneficiary_enabled: bool, // Resigning players may hand their tiles to another colour
+    // Randomness
+    pub commit_reveal_enabled: bool,
+    pub randomness_source: u8,            // See randomness.rs
+    pub vrf_authority: Pubkey,            // Oracle allowed to fulfil VRF requests
+}
+
+impl Default for GameConfig {
+    /// The original 11x13, 4-player rules
+    fn default() -> Self {
+        GameConfig {
+            rows: 11,
+            columns: 13,
+            max_players: DEFAULT_MAX_PLAYERS,
+            starting_resources: 100,
+            join_window_seconds: DEFAULT_JOIN_WINDOW_SECONDS,
+            entry_cost: DEFAULT_GAME_COST,
+            resources_per_minute: RESOURCES_PER_MINUTE,
+            xp_per_minute_per_tile: XP_PER_MINUTE_PER_TILE,
+            winning_xp_limit: 10_000,
+            gold_tier_bonus_xp_per_min: GOLD_TIER_BONUS_XP_PER_MIN,
+            silver_tier_bonus_xp_per_min: SILVER_TIER_BONUS_XP_PER_MIN,
+            bronze_tier_bonus_xp_per_min: BRONZE_TIER_BONUS_XP_PER_MIN,
+            iron_tier_bonus_xp_per_min: IRON_TIER_BONUS_XP_PER_MIN,
+            crank_reward_lamports: 0,
+            payout_bps: WINNER_TAKES_ALL,
+            max_hit_threshold: 500,
+            max_hit_resource_count: 5,
+            combat_model: COMBAT_MODEL_FLAT,
+            resources_per_combat_die: RESOURCES_PER_COMBAT_DIE,
+            max_combat_dice: MAX_COMBAT_DICE,
+            expired_attack_penalty: EXPIRED_ATTACK_PENALTY,
+            tile_attack_cooldown_seconds: 0,
+            max_open_attacks_per_player: 0,
+            reinforce_window_seconds: MIN_ATTACK_DURATION as u16,
+            resign_beneficiary_enabled: false,
+            commit_reveal_enabled: false,
+            randomness_source: RANDOMNESS_SOURCE_SLOT_HASHES,
+            vrf_authority: Pubkey::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn combat_settings(&self) -> CombatSettings {
+        CombatSettings {
+            combat_model: self.combat_model,
+            resources_per_combat_die: self.resources_per_combat_die,
+            max_combat_dice: self.max_combat_dice,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        // Board must fit in the account and leave room between the corners
+        require!(
+            self.rows >= MIN_BOARD_SIZE
+                && self.columns >= MIN_BOARD_SIZE
+                && (self.rows as usize) * (self.columns as usize) <= MAX_BOARD_TILES,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.max_players >= MIN_PLAYERS && self.max_players as usize <= MAX_PLAYERS,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.starting_resources >= 1 && self.starting_resources <= MAX_STARTING_RESOURCES,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.join_window_seconds >= MIN_JOIN_WINDOW_SECONDS
+                && self.join_window_seconds <= MAX_JOIN_WINDOW_SECONDS,
+            HexoneError::InvalidGameConfig
+        );
+
+        // Economy
+        require!(self.entry_cost <= MAX_ENTRY_COST, HexoneError::InvalidGameConfig);
+        require!(
+            self.resources_per_minute >= 1 && self.resources_per_minute <= MAX_RESOURCES_PER_MINUTE,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.xp_per_minute_per_tile >= 1
+                && self.xp_per_minute_per_tile <= MAX_XP_PER_MINUTE_PER_TILE,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.winning_xp_limit >= 1 && self.winning_xp_limit <= MAX_WINNING_XP_LIMIT,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.crank_reward_lamports <= MAX_CRANK_REWARD_LAMPORTS,
+            HexoneError::InvalidGameConfig
+        );
+        // Payout table covers the whole pool, and only places that can be filled
+        let payout_total: u32 = self.payout_bps.iter().map(|bps| *bps as u32).sum();
+        require!(
+            payout_total == PAYOUT_BPS_DENOMINATOR as u32
+                && self.payout_bps[self.max_players as usize..].iter().all(|bps| *bps == 0),
+            HexoneError::InvalidGameConfig
+        );
+
+        // Combat
+        require!(
+            self.max_hit_threshold >= 1 && self.max_hit_threshold <= 999,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.max_hit_resource_count >= 1 && self.max_hit_resource_count <= MAX_HIT_RESOURCE_COUNT,
+            HexoneError::InvalidGameConfig
+        );
+        self.combat_settings().validate()?;
+        require!(
+            self.tile_attack_cooldown_seconds <= MAX_TILE_ATTACK_COOLDOWN_SECONDS,
+            HexoneError::InvalidGameConfig
+        );
+        require!(
+            self.reinforce_window_seconds as i64 >= MIN_ATTACK_DURATION
+                && self.reinforce_window_seconds <= MAX_REINFORCE_WINDOW_SECONDS,
+            HexoneError::InvalidGameConfig
+        );
+
+        // Randomness (VRF games need an oracle)
+        RandomnessSource::from_u8(self.randomness_source)?;
+        if self.randomness_source == RANDOMNESS_SOURCE_VRF {
+            require!(
+                self.vrf_authority != Pubkey::default(),
+                HexoneError::InvalidRandomnessSource
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::game::{Game, TileData};
+
+    #[test]
+    fn default_config_is_valid() {
+        assert!(GameConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn board_must_fit_the_account() {
+        let too_big = GameConfig { rows: 25, columns: 25, ..GameConfig::default() };
+        assert!(too_big.validate().is_err());
+
+        let too_small = GameConfig { rows: 4, ..GameConfig::default() };
+        assert!(too_small.validate().is_err());
+
+        let blitz = GameConfig { rows: 7, columns: 7, ..GameConfig::default() };
+        assert!(blitz.validate().is_ok());
+    }
+
+    #[test]
+    fn largest_board_fits_one_allocation() {
+        // Accounts created through CPI can't exceed MAX_PERMITTED_DATA_INCREASE
+        assert!(Game::LEN + MAX_BOARD_TILES * TileData::LEN <= 10_240);
+        assert_eq!(Game::space(24, 24), Game::LEN + MAX_BOARD_TILES * TileData::LEN);
+    }
+
+    #[test]
+    fn out_of_range_rules_are_rejected() {
+        let invalid = [
+            GameConfig { max_players: 1, ..GameConfig::default() },
+            GameConfig { max_players: 7, ..GameConfig::default() },
+            GameConfig { starting_resources: 0, ..GameConfig::default() },
+            GameConfig { join_window_seconds: 59, ..GameConfig::default() },
+            GameConfig { join_window_seconds: MAX_JOIN_WINDOW_SECONDS + 1, ..GameConfig::default() },
+            GameConfig { entry_cost: MAX_ENTRY_COST + 1, ..GameConfig::default() },
+            GameConfig { resources_per_minute: 0, ..GameConfig::default() },
+            GameConfig { xp_per_minute_per_tile: 0, ..GameConfig::default() },
+            GameConfig { winning_xp_limit: 0, ..GameConfig::default() },
+            GameConfig { crank_reward_lamports: MAX_CRANK_REWARD_LAMPORTS + 1, ..GameConfig::default() },
+            GameConfig { payout_bps: [7_000, 2_000, 0, 0, 0, 0], ..GameConfig::default() },
+            GameConfig { payout_bps: [7_000, 2_000, 1_000, 1_000, 0, 0], ..GameConfig::default() },
+            GameConfig { max_players: 2, payout_bps: [7_000, 2_000, 1_000, 0, 0, 0], ..GameConfig::default() },
+            GameConfig { max_hit_threshold: 1_000, ..GameConfig::default() },
+            GameConfig { max_hit_resource_count: 0, ..GameConfig::default() },
+            GameConfig { combat_model: 9, ..GameConfig::default() },
+            GameConfig { tile_attack_cooldown_seconds: 3_601, ..GameConfig::default() },
+            GameConfig { reinforce_window_seconds: 2, ..GameConfig::default() },
+            GameConfig { reinforce_window_seconds: MAX_REINFORCE_WINDOW_SECONDS + 1, ..GameConfig::default() },
+            GameConfig { randomness_source: 9, ..GameConfig::default() },
+            GameConfig { randomness_source: RANDOMNESS_SOURCE_VRF, ..GameConfig::default() },
+        ];
+        for config in invalid.iter() {
+            assert!(config.validate().is_err(), "{:?} should be rejected", config);
+        }
+    }
+
+    #[test]
+    fn duels_through_six_player_games_are_allowed() {
+        for max_players in MIN_PLAYERS..=MAX_PLAYERS as u8 {
+            let config = GameConfig { max_players, ..GameConfig::default() };
+            assert!(config.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn payout_tables_can_split_the_pool() {
+        let podium = GameConfig { payout_bps: [7_000, 2_000, 1_000, 0, 0, 0], ..GameConfig::default() };
+        assert!(podium.validate().is_ok());
+    }
+
+    #[test]
+    fn free_casual_games_are_allowed() {
+        let casual = GameConfig { entry_cost: 0, ..GameConfig::default() };
+        assert!(casual.validate().is_ok());
+    }
+}
@@ -0,0 +1,266 @@
+use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+
+use crate::constants::*;
+use crate::state::game::{
+    rank_players, Game, PlayerSlot, TileData, GAME_STATE_IN_PROGRESS,
+    GAME_STATE_WINNER_FOUND_NOT_PAID_OUT, GAME_VERSION, WIN_CONDITION_XP_LIMIT,
+};
+use crate::state::game_config::GameConfig;
+
+/// Version stored in Game accounts created before PlayerSlot
+pub const GAME_VERSION_V1: u8 = 1;
+
+/// Read-only view of a version 1 Game account: four fixed players and a 144-tile board inline
+/// Only used by migrate_game, which rewrites the account into the current Game layout
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct GameV1 {
+    // 32-byte aligned fields
+    pub admin: Pubkey,
+    pub players: [Pubkey; 4],
+    // 8-byte aligned fields
+    pub game_id: u64,
+    pub available_resources_timestamp: i64,
+    pub xp_timestamps: [i64; 4],
+    // 4-byte aligned fields
+    pub resources_per_minute: u32,
+    pub total_resources_available: u32,
+    pub resources_spent: [u32; 4],
+    pub xp_per_minute_per_tile: u32,
+    pub xp: [u32; 4],
+    pub tile_counts: [u32; 4],
+    pub _padding_u32: [u8; 4],
+    pub tile_data: [TileDataV1; 144],
+    // Tier counts per player in the order iron, bronze, silver, gold
+    pub tier_tile_counts: [[u8; 4]; 4],
+    pub gold_tier_bonus_xp_per_min: u8,
+    pub silver_tier_bonus_xp_per_min: u8,
+    pub bronze_tier_bonus_xp_per_min: u8,
+    pub iron_tier_bonus_xp_per_min: u8,
+    pub _padding_tier_bonus: [u8; 4],
+    pub winning_player_pubkey: Pubkey,
+    pub winning_xp_limit: u64,
+    pub game_state: u8,
+    pub rows: u8,
+    pub columns: u8,
+    pub version: u8,
+    pub bump: u8,
+    pub winner_calculation_flag: u8,
+    pub max_hit_threshold: u16,
+    pub max_hit_resource_count: u8,
+    pub _padding: [u8; 7],
+}
+
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct TileDataV1 {
+    pub color: u8,
+    pub _padding: u8,
+    pub resource_count: u16,
+}
+
+impl GameV1 {
+    pub const LEN: usize = 8 + std::mem::size_of::<GameV1>();
+
+    /// Write this game into the current layout
+    /// Rules that didn't exist in v1 take their GameConfig defaults, except the entry cost,
+    /// which v1 games charged from the platform. A winner found in v1 is kept, with the
+    /// payout claim window opening at `current_time`
+    pub fn upgrade(
+        &self,
+        entry_cost: u64,
+        game: &mut Game,
+        tile_data: &mut [TileData],
+        current_time: i64,
+    ) -> Result<()> {
+        let defaults = GameConfig::default();
+
+        game.admin = self.admin;
+        for i in 0..4 {
+            let [iron, bronze, silver, gold] = self.tier_tile_counts[i];
+            game.players[i] = PlayerSlot {
+                wallet: self.players[i],
+                xp_timestamp: self.xp_timestamps[i],
+                resources_spent: self.resources_spent[i],
+                xp: self.xp[i],
+                tile_count: self.tile_counts[i],
+                tier_tile_counts: [gold, silver, bronze, iron],
+                open_attacks: 0,
+                xp_remainder: 0,
+                eliminated_order: 0,
+                resigned: 0,
+                xp_past_limit: 0,
+                limit_reached_at: 0,
+                eliminated_at: 0,
+            };
+        }
+        game.game_id = self.game_id;
+        game.available_resources_timestamp = self.available_resources_timestamp;
+        game.resources_per_minute = self.resources_per_minute;
+        game.total_resources_available = self.total_resources_available;
+        game.xp_per_minute_per_tile = self.xp_per_minute_per_tile;
+        game.gold_tier_bonus_xp_per_min = self.gold_tier_bonus_xp_per_min;
+        game.silver_tier_bonus_xp_per_min = self.silver_tier_bonus_xp_per_min;
+        game.bronze_tier_bonus_xp_per_min = self.bronze_tier_bonus_xp_per_min;
+        game.iron_tier_bonus_xp_per_min = self.iron_tier_bonus_xp_per_min;
+        game.winning_player_pubkey = self.winning_player_pubkey;
+        game.winning_xp_limit = self.winning_xp_limit;
+        game.entry_cost = entry_cost;
+        game.vrf_authority = defaults.vrf_authority;
+        game.game_state = self.game_state;
+        game.rows = self.rows;
+        game.columns = self.columns;
+        game.version = GAME_VERSION;
+        game.bump = self.bump;
+        game.winner_calculation_flag = self.winner_calculation_flag;
+        game.max_hit_threshold = self.max_hit_threshold;
+        game.max_hit_resource_count = self.max_hit_resource_count;
+        game.commit_reveal_enabled = defaults.commit_reveal_enabled as u8;
+        game.randomness_source = defaults.randomness_source;
+        game.expired_attack_penalty = defaults.expired_attack_penalty;
+        game.combat_model = defaults.combat_model;
+        game.max_combat_dice = defaults.max_combat_dice;
+        game.resources_per_combat_die = defaults.resources_per_combat_die;
+        game.tile_attack_cooldown_seconds = defaults.tile_attack_cooldown_seconds;
+        game.max_open_attacks_per_player = defaults.max_open_attacks_per_player;
+        game.reinforce_window_seconds = defaults.reinforce_window_seconds;
+        game.max_players = DEFAULT_MAX_PLAYERS;
+        game.player_count = self
+            .players
+            .iter()
+            .filter(|wallet| **wallet != Pubkey::default())
+            .count() as u8;
+        game.starting_resources = defaults.starting_resources;
+        game.crank_reward_lamports = defaults.crank_reward_lamports;
+        game.payout_bps = defaults.payout_bps;
+        game.fee_bps = 0; // v1 games were joined without a platform fee
+
+        // v1 only recorded the winner, so they take first place and the rest rank as usual
+        // Games v1 flagged without a winner get the flag cleared, so check_for_winner runs again
+        let winner_found = self.game_state == GAME_STATE_WINNER_FOUND_NOT_PAID_OUT
+            || (self.game_state == GAME_STATE_IN_PROGRESS && self.winner_calculation_flag != 0);
+        if winner_found {
+            let winner = game.player_color(&self.winning_player_pubkey);
+            if winner != 0 {
+                let mut standings = rank_players(game.seated_players());
+                standings.retain(|color| *color != winner);
+                standings.insert(0, winner);
+                game.record_standings(&standings)?;
+                game.winner_calculation_flag = 1;
+                game.win_condition = WIN_CONDITION_XP_LIMIT;
+                game.payout_deadline = current_time.saturating_add(PAYOUT_CLAIM_WINDOW_SECONDS);
+                game.game_state = GAME_STATE_WINNER_FOUND_NOT_PAID_OUT;
+            } else {
+                game.winner_calculation_flag = 0;
+            }
+        }
+
+        for (tile, old) in tile_data.iter_mut().zip(self.tile_data.iter()) {
+            *tile = TileData {
+                color: old.color,
+                pending_attacks: 0,
+                resource_count: old.resource_count,
+                _padding: [0; 4],
+                attack_cooldown_until: 0,
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_game() -> GameV1 {
+        let mut old = GameV1::zeroed();
+        old.game_id = 7;
+        old.rows = 11;
+        old.columns = 13;
+        old.version = GAME_VERSION_V1;
+        old.game_state = 1;
+        old.players[0] = Pubkey::new_unique();
+        old.players[1] = Pubkey::new_unique();
+        old.players[2] = Pubkey::new_unique();
+        old.xp = [10, 20, 30, 0];
+        old.resources_spent = [5, 0, 0, 0];
+        old.tile_counts = [2, 1, 1, 0];
+        old.tier_tile_counts[0] = [1, 2, 3, 4];
+        old.tile_data[0] = TileDataV1 { color: 1, _padding: 0, resource_count: 100 };
+        old.tile_data[142] = TileDataV1 { color: 3, _padding: 0, resource_count: 42 };
+        old
+    }
+
+    #[test]
+    fn v1_layout_matches_the_original_account_size() {
+        assert_eq!(GameV1::LEN, 936);
+        // v1 accounts can't be mistaken for a current board, the smallest of which is 5x5
+        assert!(Game::space(5, 5) > GameV1::LEN);
+    }
+
+    #[test]
+    fn upgrade_moves_players_into_slots() {
+        let old = v1_game();
+        let mut game = Game::zeroed();
+        let mut tiles = vec![TileData::zeroed(); 143];
+        old.upgrade(DEFAULT_GAME_COST, &mut game, &mut tiles, 0).unwrap();
+
+        assert_eq!(game.version, GAME_VERSION);
+        assert_eq!(game.max_players, 4);
+        assert_eq!(game.player_count, 3);
+        assert_eq!(game.entry_cost, DEFAULT_GAME_COST);
+        assert_eq!(game.players[0].wallet, old.players[0]);
+        assert_eq!(game.players[2].xp, 30);
+        assert_eq!(game.players[0].resources_spent, 5);
+        assert_eq!(game.players[0].tile_count, 2);
+        // v1 stored iron first, PlayerSlot stores gold first
+        assert_eq!(game.players[0].tier_tile_counts, [4, 3, 2, 1]);
+        assert_eq!(game.players[3].wallet, Pubkey::default());
+    }
+
+    #[test]
+    fn upgrade_copies_the_board() {
+        let old = v1_game();
+        let mut game = Game::zeroed();
+        let mut tiles = vec![TileData::zeroed(); 143];
+        old.upgrade(DEFAULT_GAME_COST, &mut game, &mut tiles, 0).unwrap();
+
+        assert_eq!(game.tile_count(), 143);
+        assert_eq!((tiles[0].color, tiles[0].resource_count), (1, 100));
+        assert_eq!((tiles[142].color, tiles[142].resource_count), (3, 42));
+        assert!(tiles.iter().all(|t| t.pending_attacks == 0 && t.attack_cooldown_until == 0));
+    }
+
+    #[test]
+    fn upgrade_keeps_a_v1_winner_and_opens_the_claim_window() {
+        let mut old = v1_game();
+        old.game_state = GAME_STATE_WINNER_FOUND_NOT_PAID_OUT;
+        old.winner_calculation_flag = 1;
+        old.winning_player_pubkey = old.players[1];
+        let mut game = Game::zeroed();
+        let mut tiles = vec![TileData::zeroed(); 143];
+        old.upgrade(DEFAULT_GAME_COST, &mut game, &mut tiles, 5_000).unwrap();
+
+        assert_eq!(game.game_state, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+        assert_eq!(game.payout_deadline, 5_000 + PAYOUT_CLAIM_WINDOW_SECONDS);
+        // Yellow won in v1; the others follow by XP
+        let colors: Vec<u8> = game.standings.iter().map(|standing| standing.color).collect();
+        assert_eq!(colors, vec![2, 3, 1, 0, 0, 0]);
+        assert_eq!(game.standings[0].xp, 20);
+        assert!(game.payouts_outstanding());
+    }
+
+    #[test]
+    fn upgrade_clears_a_v1_winner_flag_without_a_winner() {
+        let mut old = v1_game();
+        old.winner_calculation_flag = 1;
+        let mut game = Game::zeroed();
+        let mut tiles = vec![TileData::zeroed(); 143];
+        old.upgrade(DEFAULT_GAME_COST, &mut game, &mut tiles, 5_000).unwrap();
+
+        assert_eq!(game.game_state, GAME_STATE_IN_PROGRESS);
+        assert_eq!(game.winner_calculation_flag, 0);
+        assert_eq!(game.standings[0].color, 0);
+    }
+}
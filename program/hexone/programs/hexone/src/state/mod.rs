@@ -1,9 +1,15 @@
+pub mod economy;
 pub mod game;
+pub mod game_config;
+pub mod game_v1;
 pub mod platform;
 pub mod player;
 pub mod defender;
 
+pub use economy::*;
 pub use game::*;
+pub use game_config::*;
+pub use game_v1::*;
 pub use platform::*;
 pub use player::*;
 pub use defender::*;
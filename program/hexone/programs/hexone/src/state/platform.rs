@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+/// Version 2 added the platform fee and its revenue counters
+pub const PLATFORM_VERSION: u8 = 2;
+/// Version stored in Platform accounts created before the platform fee
+pub const PLATFORM_VERSION_V1: u8 = 1;
+
 #[account]
 #[repr(C)]
 pub struct Platform {
@@ -11,10 +16,19 @@ pub struct Platform {
     pub version: u8,
     pub bump: u8,
     pub _padding: [u8; 6],  // padding for alignment
+    // Added in version 2, v1 accounts get them from migrate_platform
+    // Revenue counters, in lamports
+    pub fees_collected: u64,       // Rake taken from prize pools into the fee vault
+    pub unclaimed_collected: u64,  // Expired payouts swept into the fee vault
+    pub fees_withdrawn: u64,       // Moved out of the fee vault by withdraw_fees
+    pub fee_bps: u16,    // Rake on each game's prize pool, in basis points
+    pub fee_vault_bump: u8,
+    pub _padding_fees: [u8; 5],  // padding for alignment
 }
 
 impl Platform {
-    pub const LEN: usize = 8     // discriminator
+    /// Size of a v1 Platform account, which ends after `_padding`
+    pub const V1_LEN: usize = 8  // discriminator
         + 32                     // admin
         + 8                      // game_count
         + 8                      // games_completed
@@ -23,4 +37,44 @@ impl Platform {
         + 1                      // version
         + 1                      // bump
         + 6;                     // padding
-} 
\ No newline at end of file
+
+    pub const LEN: usize = Self::V1_LEN
+        + 8                      // fees_collected
+        + 8                      // unclaimed_collected
+        + 8                      // fees_withdrawn
+        + 2                      // fee_bps
+        + 1                      // fee_vault_bump
+        + 5;                     // padding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_fields_keep_their_offsets() {
+        let platform = Platform {
+            admin: Pubkey::new_unique(),
+            game_count: 0,
+            games_completed: 0,
+            total_players: 0,
+            game_cost: 0,
+            version: PLATFORM_VERSION,
+            bump: 254,
+            _padding: [0; 6],
+            fees_collected: 0,
+            unclaimed_collected: 0,
+            fees_withdrawn: 0,
+            fee_bps: 0,
+            fee_vault_bump: 0,
+            _padding_fees: [0; 5],
+        };
+        let mut data = Vec::new();
+        platform.try_serialize(&mut data).unwrap();
+
+        assert_eq!(Platform::V1_LEN, 80);
+        assert_eq!(data.len(), Platform::LEN);
+        // version and bump sit where v1 accounts have them
+        assert_eq!(&data[72..74], &[PLATFORM_VERSION, 254]);
+    }
+}
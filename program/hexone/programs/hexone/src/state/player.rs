@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::error::HexoneError;
+
 pub const PLAYER_STATUS_READY: u8 = 1;
 pub const PLAYER_STATUS_PLAYING: u8 = 2;
 
@@ -32,4 +34,63 @@ impl Player {
         + 1                      // bump
         + 32                     // hotwallet
         + 4;                     // padding
-} 
\ No newline at end of file
+
+    /// READY -> PLAYING: the player takes a seat in `game`
+    pub fn join(&mut self, game: Pubkey) -> Result<()> {
+        require!(self.player_status == PLAYER_STATUS_READY, HexoneError::PlayerNotReady);
+        self.player_status = PLAYER_STATUS_PLAYING;
+        self.last_game = Some(game);
+        Ok(())
+    }
+
+    /// PLAYING -> READY once `game` is over for this player (or never started), so they can
+    /// join another. Does nothing if the player has already moved on from `game`
+    pub fn release(&mut self, game: Pubkey) -> bool {
+        if self.player_status != PLAYER_STATUS_PLAYING || self.last_game != Some(game) {
+            return false;
+        }
+        self.player_status = PLAYER_STATUS_READY;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player() -> Player {
+        Player {
+            wallet: Pubkey::new_unique(),
+            name: [0; 32],
+            games_played: 0,
+            games_won: 0,
+            last_game: None,
+            created_at: 0,
+            player_status: PLAYER_STATUS_READY,
+            version: 1,
+            bump: 255,
+            hotwallet: Pubkey::default(),
+            _padding: [0; 4],
+        }
+    }
+
+    #[test]
+    fn players_play_one_game_at_a_time() {
+        let mut player = player();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        player.join(first).unwrap();
+        assert_eq!(player.player_status, PLAYER_STATUS_PLAYING);
+        assert_eq!(player.last_game, Some(first));
+        assert!(player.join(second).is_err());
+
+        // Finishing some other game doesn't free them
+        assert!(!player.release(second));
+        assert_eq!(player.player_status, PLAYER_STATUS_PLAYING);
+
+        assert!(player.release(first));
+        assert_eq!(player.player_status, PLAYER_STATUS_READY);
+        // ...and only once
+        assert!(!player.release(first));
+        player.join(second).unwrap();
+    }
+}
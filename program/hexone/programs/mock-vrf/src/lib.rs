@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use hexone::cpi::accounts::FulfillRandomness;
+use hexone::program::Hexone;
+
+declare_id!("4wuPF7wDhw2jrx3fUqPadUrDtoirRZVWpv7vaGYZJkhZ");
+
+/// Seed for the PDA that signs fulfil callbacks (set as the game's vrf_authority)
+pub const VRF_AUTHORITY_SEED: &[u8] = b"vrf-authority";
+
+/// Stand-in for an external VRF oracle so the request/fulfil/resolve flow can run
+/// on a local validator. Anyone can fulfil with any randomness; a real oracle
+/// would verify a VRF proof before calling back into hexone.
+#[program]
+pub mod mock_vrf {
+    use super::*;
+
+    pub fn fulfill(ctx: Context<Fulfill>, randomness: [u8; 32]) -> Result<()> {
+        let seeds: &[&[u8]] = &[VRF_AUTHORITY_SEED, &[ctx.bumps.vrf_authority]];
+        let signer_seeds = &[seeds];
+
+        hexone::cpi::fulfill_randomness(
+            CpiContext::new_with_signer(
+                ctx.accounts.hexone_program.to_account_info(),
+                FulfillRandomness {
+                    vrf_authority: ctx.accounts.vrf_authority.to_account_info(),
+                    game: ctx.accounts.game.to_account_info(),
+                    defender: ctx.accounts.defender.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            randomness,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct Fulfill<'info> {
+    pub payer: Signer<'info>,
+
+    /// CHECK: PDA that signs the callback into hexone
+    #[account(
+        seeds = [VRF_AUTHORITY_SEED],
+        bump,
+    )]
+    pub vrf_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by hexone
+    pub game: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by hexone
+    #[account(mut)]
+    pub defender: UncheckedAccount<'info>,
+
+    pub hexone_program: Program<'info, Hexone>,
+}
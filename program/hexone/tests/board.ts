@@ -0,0 +1,31 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Hexone } from "../target/types/hexone";
+
+// TileData is 16 bytes: color u8, pending_attacks u8, resource_count u16, 4 bytes padding, attack_cooldown_until i64
+const TILE_DATA_LEN = 16;
+
+// Tiles trail the Game struct in the account (rows * columns of them), so they aren't in the IDL.
+// Fetch the game and decode its board into `tileData`.
+export const fetchGame = async (program: Program<Hexone>, gamePDA: PublicKey) => {
+  const accountInfo = await program.account.game.getAccountInfo(gamePDA);
+  if (!accountInfo) {
+    throw new Error(`Game account ${gamePDA.toBase58()} not found`);
+  }
+  const game = program.coder.accounts.decode("game", accountInfo.data);
+
+  const tileCount = game.rows * game.columns;
+  const tiles = accountInfo.data.subarray(accountInfo.data.length - tileCount * TILE_DATA_LEN);
+  const tileData = Array.from({ length: tileCount }, (_, index) => {
+    const offset = index * TILE_DATA_LEN;
+    return {
+      color: tiles.readUInt8(offset),
+      pendingAttacks: tiles.readUInt8(offset + 1),
+      resourceCount: tiles.readUInt16LE(offset + 2),
+      attackCooldownUntil: new anchor.BN(tiles.readBigInt64LE(offset + 8).toString()),
+    };
+  });
+
+  return { ...game, tileData };
+};
@@ -0,0 +1,238 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { Hexone } from "../target/types/hexone";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_SLOT_HASHES_PUBKEY, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { fetchGame } from "./board";
+
+const IDL = require("../target/idl/hexone.json");
+
+// Legacy hash rolls don't need SlotHashes history, which bankrun doesn't build up
+const RANDOMNESS_SOURCE_HASH = 0;
+const COMBAT_MODEL_FLAT = 0;
+
+const TILE_ATTACK_COOLDOWN_SECONDS = 30;
+const MAX_OPEN_ATTACKS_PER_PLAYER = 1;
+const REINFORCE_WINDOW_SECONDS = 3;
+
+// Cooldowns are time based, so these tests run in bankrun where the clock can be warped
+describe("attack cooldowns", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let gamePDA: PublicKey;
+
+  const players = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const [red] = players;
+
+  const findPDA = (seeds: Buffer[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const playerPDA = (wallet: Keypair) => findPDA([Buffer.from("player"), wallet.publicKey.toBuffer()]);
+
+  const defenderPDA = (defenderTileIndex: number) => {
+    const defenderTileBuffer = Buffer.alloc(2);
+    defenderTileBuffer.writeUInt16LE(defenderTileIndex, 0);
+    return findPDA([Buffer.from("defender"), gamePDA.toBuffer(), defenderTileBuffer]);
+  };
+
+  // Move the clock (and slot, so transactions get a fresh blockhash) forward
+  const warpSeconds = async (seconds: number) => {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(Math.max(1, Math.ceil(seconds / 0.4)));
+    context.warpToSlot(slot);
+    context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  };
+
+  const moveResources = (from: number, to: number, resources: number) =>
+    program.methods
+      .moveResources(from, to, resources)
+      .accounts({
+        playerWallet: red.publicKey,
+        signerWallet: red.publicKey,
+        player: playerPDA(red),
+        game: gamePDA,
+      })
+      .signers([red])
+      .rpc();
+
+  const attack = (attackerTileIndex: number, defenderTileIndex: number) =>
+    program.methods
+      .attackTile(attackerTileIndex, defenderTileIndex)
+      .accounts({
+        playerWallet: red.publicKey,
+        signerWallet: red.publicKey,
+        player: playerPDA(red),
+        game: gamePDA,
+        defender: defenderPDA(defenderTileIndex),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([red])
+      .rpc();
+
+  const resolve = (defenderTileIndex: number) =>
+    program.methods
+      .resolveAttack()
+      .accounts({
+        caller: red.publicKey,
+        game: gamePDA,
+        defender: defenderPDA(defenderTileIndex),
+        payer: red.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([red])
+      .rpc();
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      players.map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    const admin = context.payer;
+    const platformPDA = findPDA([Buffer.from("platform")]);
+    await program.methods
+      .createPlatform()
+      .accounts({ admin: admin.publicKey, platform: platformPDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt(0), 0);
+    gamePDA = findPDA([Buffer.from("GAME-"), gameIdBuffer]);
+    await program.methods
+      .createGame({
+        rows: 11,
+        columns: 13,
+        maxPlayers: 4,
+        startingResources: 100,
+        joinWindowSeconds: 24 * 60 * 60,
+        entryCost: new anchor.BN(LAMPORTS_PER_SOL / 10),
+        resourcesPerMinute: 10,
+        xpPerMinutePerTile: 1,
+        winningXpLimit: new anchor.BN(10_000),
+        goldTierBonusXpPerMin: 100,
+        silverTierBonusXpPerMin: 50,
+        bronzeTierBonusXpPerMin: 10,
+        ironTierBonusXpPerMin: 5,
+        crankRewardLamports: new anchor.BN(0),
+        payoutBps: [10000, 0, 0, 0, 0, 0],
+        maxHitThreshold: 500,
+        maxHitResourceCount: 5,
+        combatModel: COMBAT_MODEL_FLAT,
+        resourcesPerCombatDie: 10,
+        maxCombatDice: 5,
+        expiredAttackPenalty: 5,
+        tileAttackCooldownSeconds: TILE_ATTACK_COOLDOWN_SECONDS,
+        maxOpenAttacksPerPlayer: MAX_OPEN_ATTACKS_PER_PLAYER,
+        reinforceWindowSeconds: REINFORCE_WINDOW_SECONDS,
+        resignBeneficiaryEnabled: false,
+        commitRevealEnabled: false,
+        randomnessSource: RANDOMNESS_SOURCE_HASH,
+        vrfAuthority: PublicKey.default,
+      })
+      .accounts({ admin: admin.publicKey, platform: platformPDA, game: gamePDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    for (const [index, wallet] of players.entries()) {
+      const name = Buffer.alloc(32);
+      Buffer.from(`Player ${index + 1}`).copy(name);
+      await program.methods
+        .createPlayer(Array.from(name), wallet.publicKey)
+        .accounts({
+          wallet: wallet.publicKey,
+          platform: platformPDA,
+          player: playerPDA(wallet),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+      await program.methods
+        .joinGame(new anchor.BN(0))
+        .accounts({
+          wallet: wallet.publicKey,
+          player: playerPDA(wallet),
+          game: gamePDA,
+          gameTreasury: findPDA([Buffer.from("game_treasury"), gamePDA.toBuffer()]),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+    }
+
+    // Red sends half its corner down column 0 to 117 (next to green's corner at 130)
+    // and the rest along row 0 to 11 (next to yellow's corner at 12)
+    let game = await fetchGame(program, gamePDA);
+    await moveResources(0, 13, Math.floor(game.tileData[0].resourceCount / 2));
+    for (let tile = 13; tile < 117; tile += 13) {
+      game = await fetchGame(program, gamePDA);
+      await moveResources(tile, tile + 13, game.tileData[tile].resourceCount - 1);
+    }
+    for (let tile = 0; tile < 11; tile++) {
+      game = await fetchGame(program, gamePDA);
+      await moveResources(tile, tile + 1, game.tileData[tile].resourceCount - 1);
+    }
+  });
+
+  it("caps open attacks per player and cools down tiles that just attacked", async () => {
+    // One open attack at a time
+    await attack(11, 12);
+    let game = await fetchGame(program, gamePDA);
+    expect(game.players[0].openAttacks).to.equal(1);
+    await expectFailure(() => attack(117, 130), "TooManyOpenAttacks");
+
+    // Resolving frees the slot and restarts tile 11's cooldown
+    await warpSeconds(5);
+    await resolve(12);
+    game = await fetchGame(program, gamePDA);
+    expect(game.players[0].openAttacks).to.equal(0);
+    await attack(117, 130);
+
+    await warpSeconds(5);
+    await resolve(130);
+
+    // Tile 11 attacked 5 seconds ago, so it is still cooling down
+    await expectFailure(() => attack(11, 12), "AttackCooldownActive");
+
+    // Once the cooldown has passed it can attack again
+    await warpSeconds(TILE_ATTACK_COOLDOWN_SECONDS);
+    await attack(11, 12);
+    game = await fetchGame(program, gamePDA);
+    expect(game.tileData[11].pendingAttacks).to.equal(1);
+    expect(game.tileData[11].attackCooldownUntil.toNumber()).to.be.greaterThan(0);
+  });
+});
@@ -0,0 +1,348 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { Hexone } from "../target/types/hexone";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { fetchGame } from "./board";
+
+const IDL = require("../target/idl/hexone.json");
+
+const RANDOMNESS_SOURCE_HASH = 0;
+const COMBAT_MODEL_FLAT = 0;
+const GAME_STATE_WAITING = 0;
+const GAME_STATE_IN_PROGRESS = 1;
+const GAME_STATE_CANCELLED = 4;
+const PLAYER_STATUS_READY = 1;
+const PLAYER_STATUS_PLAYING = 2;
+
+const ENTRY_COST = LAMPORTS_PER_SOL / 10;
+const JOIN_WINDOW_SECONDS = 60;
+
+// Join deadlines are time based, so these tests run in bankrun where the clock can be warped
+describe("lobby", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let platformPDA: PublicKey;
+  let nextGameId = 0;
+
+  const admin = Keypair.generate();
+  const stranger = Keypair.generate();
+  const players = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+  const findPDA = (seeds: Buffer[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const playerPDA = (wallet: Keypair) => findPDA([Buffer.from("player"), wallet.publicKey.toBuffer()]);
+
+  const gamePDA = (gameId: number) => {
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt(gameId), 0);
+    return findPDA([Buffer.from("GAME-"), gameIdBuffer]);
+  };
+
+  const treasuryPDA = (game: PublicKey) => findPDA([Buffer.from("game_treasury"), game.toBuffer()]);
+
+  const balance = async (address: PublicKey) => Number(await context.banksClient.getBalance(address));
+
+  // Move the clock (and slot, so transactions get a fresh blockhash) forward
+  const warpSeconds = async (seconds: number) => {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(Math.max(1, Math.ceil(seconds / 0.4)));
+    context.warpToSlot(slot);
+    context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  };
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  const createGame = async () => {
+    const gameId = nextGameId++;
+    const game = gamePDA(gameId);
+    await program.methods
+      .createGame({
+        rows: 11,
+        columns: 13,
+        maxPlayers: 4,
+        startingResources: 100,
+        joinWindowSeconds: JOIN_WINDOW_SECONDS,
+        entryCost: new anchor.BN(ENTRY_COST),
+        resourcesPerMinute: 10,
+        xpPerMinutePerTile: 1,
+        winningXpLimit: new anchor.BN(10_000),
+        goldTierBonusXpPerMin: 100,
+        silverTierBonusXpPerMin: 50,
+        bronzeTierBonusXpPerMin: 10,
+        ironTierBonusXpPerMin: 5,
+        crankRewardLamports: new anchor.BN(0),
+        payoutBps: [10000, 0, 0, 0, 0, 0],
+        maxHitThreshold: 500,
+        maxHitResourceCount: 5,
+        combatModel: COMBAT_MODEL_FLAT,
+        resourcesPerCombatDie: 10,
+        maxCombatDice: 5,
+        expiredAttackPenalty: 5,
+        tileAttackCooldownSeconds: 0,
+        maxOpenAttacksPerPlayer: 0,
+        reinforceWindowSeconds: 3,
+        resignBeneficiaryEnabled: false,
+        commitRevealEnabled: false,
+        randomnessSource: RANDOMNESS_SOURCE_HASH,
+        vrfAuthority: PublicKey.default,
+      })
+      .accounts({ admin: admin.publicKey, platform: platformPDA, game, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+    return { gameId, game };
+  };
+
+  const join = (gameId: number, wallet: Keypair) =>
+    program.methods
+      .joinGame(new anchor.BN(gameId))
+      .accounts({
+        wallet: wallet.publicKey,
+        player: playerPDA(wallet),
+        game: gamePDA(gameId),
+        gameTreasury: treasuryPDA(gamePDA(gameId)),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const leave = (gameId: number, wallet: Keypair) =>
+    program.methods
+      .leaveGame()
+      .accounts({
+        wallet: wallet.publicKey,
+        player: playerPDA(wallet),
+        game: gamePDA(gameId),
+        gameTreasury: treasuryPDA(gamePDA(gameId)),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const cancel = (game: PublicKey, caller: Keypair, joined: Keypair[]) =>
+    program.methods
+      .cancelGame()
+      .accounts({
+        caller: caller.publicKey,
+        game,
+        gameTreasury: treasuryPDA(game),
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        joined.flatMap((wallet) => [
+          { pubkey: wallet.publicKey, isSigner: false, isWritable: true },
+          { pubkey: playerPDA(wallet), isSigner: false, isWritable: true },
+        ])
+      )
+      .signers([caller])
+      .rpc();
+
+  const finish = (game: PublicKey, wallet: Keypair) =>
+    program.methods
+      .finishGameForPlayer()
+      .accounts({ wallet: wallet.publicKey, player: playerPDA(wallet), game })
+      .signers([wallet])
+      .rpc();
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      [admin, stranger, ...players].map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    platformPDA = findPDA([Buffer.from("platform")]);
+    await program.methods
+      .createPlatform()
+      .accounts({
+        admin: admin.publicKey,
+        platform: platformPDA,
+        feeVault: findPDA([Buffer.from("fee_vault")]),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    for (const [index, wallet] of players.entries()) {
+      const name = Buffer.alloc(32);
+      Buffer.from(`Player ${index + 1}`).copy(name);
+      await program.methods
+        .createPlayer(Array.from(name), wallet.publicKey)
+        .accounts({
+          wallet: wallet.publicKey,
+          platform: platformPDA,
+          player: playerPDA(wallet),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+    }
+  });
+
+  describe("cancel_game", () => {
+    let gameId: number;
+    let game: PublicKey;
+    const joined = players.slice(0, 2);
+
+    before(async () => {
+      ({ gameId, game } = await createGame());
+      for (const wallet of joined) {
+        await join(gameId, wallet);
+      }
+    });
+
+    it("is admin only until the join deadline", async () => {
+      await expectFailure(() => cancel(game, stranger, joined), "JoinDeadlineNotReached");
+    });
+
+    it("needs every joined player's accounts", async () => {
+      await warpSeconds(JOIN_WINDOW_SECONDS + 1);
+      await expectFailure(() => cancel(game, stranger, joined.slice(0, 1)), "MissingPlayerAccounts");
+      await expectFailure(() => cancel(game, stranger, [...joined].reverse()), "MissingPlayerAccounts");
+    });
+
+    it("stops taking players after the join deadline", async () => {
+      await expectFailure(() => join(gameId, players[2]), "JoinDeadlinePassed");
+    });
+
+    it("refunds everyone who joined and frees their players", async () => {
+      const before = await Promise.all(joined.map((wallet) => balance(wallet.publicKey)));
+      const playing = await program.account.player.fetch(playerPDA(joined[0]));
+      expect(playing.playerStatus).to.equal(PLAYER_STATUS_PLAYING);
+
+      await cancel(game, stranger, joined);
+
+      for (const [i, wallet] of joined.entries()) {
+        expect(await balance(wallet.publicKey)).to.equal(before[i] + ENTRY_COST);
+        const player = await program.account.player.fetch(playerPDA(wallet));
+        expect(player.playerStatus).to.equal(PLAYER_STATUS_READY);
+        expect(player.gamesPlayed).to.equal(0);
+      }
+      expect(await balance(treasuryPDA(game))).to.equal(0);
+      const state = await fetchGame(program, game);
+      expect(state.gameState).to.equal(GAME_STATE_CANCELLED);
+
+      // Cancelled games can't be joined or cancelled again
+      await expectFailure(() => join(gameId, players[2]), "GameNotWaiting");
+      await expectFailure(() => cancel(game, admin, joined), "GameNotWaiting");
+    });
+
+    it("lets the admin cancel an empty lobby straight away", async () => {
+      const { game: empty } = await createGame();
+      await cancel(empty, admin, []);
+      const state = await fetchGame(program, empty);
+      expect(state.gameState).to.equal(GAME_STATE_CANCELLED);
+    });
+
+    it("leaves lobbies that are still open alone", async () => {
+      const { game: open } = await createGame();
+      const state = await fetchGame(program, open);
+      expect(state.gameState).to.equal(GAME_STATE_WAITING);
+      await expectFailure(() => cancel(open, stranger, []), "JoinDeadlineNotReached");
+    });
+  });
+
+  describe("player status", () => {
+    const [red, yellow] = players;
+
+    it("takes one seat in one game at a time", async () => {
+      const { gameId, game } = await createGame();
+      const { gameId: otherGameId } = await createGame();
+      await join(gameId, red);
+
+      await expectFailure(() => join(gameId, red), "AlreadyJoined");
+      await expectFailure(() => join(otherGameId, red), "PlayerNotReady");
+
+      const state = await fetchGame(program, game);
+      expect(state.playerCount).to.equal(1);
+    });
+
+    it("only finishes games that are over for the player", async () => {
+      const game = gamePDA(nextGameId - 2);
+      await expectFailure(() => finish(game, red), "GameNotFinished");
+      await expectFailure(() => finish(game, yellow), "PlayerNotAuthorized");
+
+      await leave(nextGameId - 2, red);
+      const player = await program.account.player.fetch(playerPDA(red));
+      expect(player.playerStatus).to.equal(PLAYER_STATUS_READY);
+    });
+  });
+
+  describe("leave_game", () => {
+    let gameId: number;
+    let game: PublicKey;
+    const [red, yellow] = players;
+
+    before(async () => {
+      ({ gameId, game } = await createGame());
+      await join(gameId, red);
+    });
+
+    it("only lets players in the game leave", async () => {
+      await expectFailure(() => leave(gameId, yellow), "PlayerNotAuthorized");
+    });
+
+    it("refunds the entry cost and frees the seat", async () => {
+      const before = await balance(red.publicKey);
+      const gamesPlayed = (await program.account.player.fetch(playerPDA(red))).gamesPlayed;
+
+      await leave(gameId, red);
+
+      expect(await balance(red.publicKey)).to.equal(before + ENTRY_COST);
+      const player = await program.account.player.fetch(playerPDA(red));
+      expect(player.playerStatus).to.equal(PLAYER_STATUS_READY);
+      expect(player.gamesPlayed).to.equal(gamesPlayed - 1);
+
+      const state = await fetchGame(program, game);
+      expect(state.playerCount).to.equal(0);
+      expect(state.players[0].wallet.toBase58()).to.equal(PublicKey.default.toBase58());
+    });
+
+    it("gives the freed seat to the next player", async () => {
+      await join(gameId, yellow);
+      const state = await fetchGame(program, game);
+      expect(state.players[0].wallet.toBase58()).to.equal(yellow.publicKey.toBase58());
+    });
+
+    it("can't leave once the game has started", async () => {
+      for (const wallet of [red, players[2], players[3]]) {
+        await join(gameId, wallet);
+      }
+      const state = await fetchGame(program, game);
+      expect(state.gameState).to.equal(GAME_STATE_IN_PROGRESS);
+      await expectFailure(() => leave(gameId, yellow), "GameNotWaiting");
+    });
+  });
+});
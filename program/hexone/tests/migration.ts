@@ -0,0 +1,345 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { startAnchor, ProgramTestContext } from "solana-bankrun";
+import { Hexone } from "../target/types/hexone";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { fetchGame } from "./board";
+
+const IDL = require("../target/idl/hexone.json");
+
+// Size of a v1 Game account: discriminator + four fixed players + 144 inline 4-byte tiles
+const GAME_V1_LEN = 936;
+const GAME_VERSION = 2;
+const GAME_STATE_IN_PROGRESS = 1;
+const GAME_STATE_WINNER_FOUND_NOT_PAID_OUT = 3;
+const PAYOUT_CLAIM_WINDOW_SECONDS = 7 * 24 * 60 * 60;
+
+// v1 accounts can't be created by the current program, so these tests write one directly in bankrun
+describe("game migration", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let platformPDA: PublicKey;
+  let gamePDA: PublicKey;
+
+  const admin = Keypair.generate();
+  const players = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const [red] = players;
+
+  const findPDA = (seeds: Buffer[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const playerPDA = (wallet: Keypair) => findPDA([Buffer.from("player"), wallet.publicKey.toBuffer()]);
+
+  // A game three players joined: red owns tiles 0 and 13, yellow owns 12 and green owns 130
+  const v1GameData = (gameId: number, bump: number, now: bigint, gameState: number, winner?: Keypair) => {
+    const data = Buffer.alloc(GAME_V1_LEN);
+    Buffer.from(IDL.accounts.find((account: any) => account.name === "Game").discriminator).copy(data, 0);
+    admin.publicKey.toBuffer().copy(data, 8);
+    players.forEach((wallet, i) => wallet.publicKey.toBuffer().copy(data, 40 + i * 32));
+    data.writeBigUInt64LE(BigInt(gameId), 168); // game_id
+    data.writeBigInt64LE(now, 176); // available_resources_timestamp
+    players.forEach((_, i) => data.writeBigInt64LE(now, 184 + i * 8)); // xp timestamps
+    data.writeUInt32LE(10, 216); // resources_per_minute
+    data.writeUInt32LE(1, 240); // xp_per_minute_per_tile
+    [120, 45, 30].forEach((xp, i) => data.writeUInt32LE(xp, 244 + i * 4));
+    [2, 1, 1].forEach((count, i) => data.writeUInt32LE(count, 260 + i * 4));
+    const setTile = (index: number, color: number, resources: number) => {
+      data.writeUInt8(color, 280 + index * 4);
+      data.writeUInt16LE(resources, 280 + index * 4 + 2);
+    };
+    setTile(0, 1, 60);
+    setTile(13, 1, 40);
+    setTile(12, 2, 100);
+    setTile(130, 3, 100);
+    data.set([1, 0, 0, 0], 856); // red tier counts: iron, bronze, silver, gold
+    winner?.publicKey.toBuffer().copy(data, 880); // winning_player_pubkey
+    data.writeBigUInt64LE(BigInt(10_000), 912); // winning_xp_limit
+    data.set([gameState, 11, 13, 1, bump, winner ? 1 : 0], 920); // game_state, rows, columns, version, bump, winner flag
+    data.writeUInt16LE(500, 926); // max_hit_threshold
+    data.writeUInt8(5, 928); // max_hit_resource_count
+    return data;
+  };
+
+  // Write a v1 game straight into bankrun
+  const setV1Game = async (gameId: number, gameState: number, winner?: Keypair) => {
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt(gameId), 0);
+    const [pda, bump] = PublicKey.findProgramAddressSync([Buffer.from("GAME-"), gameIdBuffer], program.programId);
+    const clock = await context.banksClient.getClock();
+    const data = v1GameData(gameId, bump, clock.unixTimestamp, gameState, winner);
+    context.setAccount(pda, {
+      lamports: await context.banksClient.getRent().then((rent) => Number(rent.minimumBalance(BigInt(data.length)))),
+      data,
+      owner: program.programId,
+      executable: false,
+    });
+    return pda;
+  };
+
+  const migrate = (gameId = 0, game = gamePDA) =>
+    program.methods
+      .migrateGame(new anchor.BN(gameId))
+      .accounts({
+        admin: admin.publicKey,
+        platform: platformPDA,
+        game,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      [admin, ...players].map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    platformPDA = findPDA([Buffer.from("platform")]);
+    await program.methods
+      .createPlatform()
+      .accounts({ admin: admin.publicKey, platform: platformPDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    const name = Buffer.alloc(32);
+    Buffer.from("Player 1").copy(name);
+    await program.methods
+      .createPlayer(Array.from(name), red.publicKey)
+      .accounts({
+        wallet: red.publicKey,
+        platform: platformPDA,
+        player: playerPDA(red),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([red])
+      .rpc();
+
+    gamePDA = await setV1Game(0, GAME_STATE_IN_PROGRESS);
+  });
+
+  it("rejects play on a v1 game until it is migrated", async () => {
+    await expectFailure(
+      () =>
+        program.methods
+          .moveResources(0, 1, 10)
+          .accounts({
+            playerWallet: red.publicKey,
+            signerWallet: red.publicKey,
+            player: playerPDA(red),
+            game: gamePDA,
+          })
+          .signers([red])
+          .rpc(),
+      "GameNeedsMigration"
+    );
+  });
+
+  it("only lets the game or platform admin migrate", async () => {
+    await expectFailure(
+      () =>
+        program.methods
+          .migrateGame(new anchor.BN(0))
+          .accounts({
+            admin: red.publicKey,
+            platform: platformPDA,
+            game: gamePDA,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([red])
+          .rpc(),
+      "PlayerNotAuthorized"
+    );
+  });
+
+  it("moves players into slots and keeps the board", async () => {
+    await migrate();
+
+    const game = await fetchGame(program, gamePDA);
+    expect(game.version).to.equal(GAME_VERSION);
+    expect(game.maxPlayers).to.equal(4);
+    expect(game.playerCount).to.equal(3);
+    players.forEach((wallet, i) => {
+      expect(game.players[i].wallet.toBase58()).to.equal(wallet.publicKey.toBase58());
+    });
+    expect(game.players[3].wallet.toBase58()).to.equal(PublicKey.default.toBase58());
+    expect(game.players.slice(0, 3).map((slot: any) => slot.xp)).to.deep.equal([120, 45, 30]);
+    expect(game.players[0].tileCount).to.equal(2);
+    expect(game.players[0].tierTileCounts).to.deep.equal([0, 0, 0, 1]);
+
+    expect(game.tileData).to.have.length(143);
+    expect(game.tileData[0]).to.include({ color: 1, resourceCount: 60 });
+    expect(game.tileData[13]).to.include({ color: 1, resourceCount: 40 });
+    expect(game.tileData[130]).to.include({ color: 3, resourceCount: 100 });
+    expect(game.tileData.every((tile) => tile.pendingAttacks === 0)).to.be.true;
+  });
+
+  it("plays on after migrating", async () => {
+    await program.methods
+      .moveResources(0, 1, 10)
+      .accounts({
+        playerWallet: red.publicKey,
+        signerWallet: red.publicKey,
+        player: playerPDA(red),
+        game: gamePDA,
+      })
+      .signers([red])
+      .rpc();
+
+    const game = await fetchGame(program, gamePDA);
+    expect(game.tileData[1]).to.include({ color: 1, resourceCount: 10 });
+    expect(game.players[0].tileCount).to.equal(3);
+  });
+
+  it("can't migrate a game twice", async () => {
+    await expectFailure(() => migrate(), "Invalid");
+  });
+
+  it("keeps a v1 winner and opens the payout claim window", async () => {
+    const finishedPDA = await setV1Game(1, GAME_STATE_WINNER_FOUND_NOT_PAID_OUT, players[1]);
+    await migrate(1, finishedPDA);
+
+    const game = await fetchGame(program, finishedPDA);
+    expect(game.gameState).to.equal(GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+    expect(game.winningPlayerPubkey.toBase58()).to.equal(players[1].publicKey.toBase58());
+    // Yellow won in v1, the others follow by XP
+    expect(game.standings.map((standing: any) => standing.color)).to.deep.equal([2, 1, 3, 0, 0, 0]);
+    const clock = await context.banksClient.getClock();
+    expect(game.payoutDeadline.toNumber()).to.equal(Number(clock.unixTimestamp) + PAYOUT_CLAIM_WINDOW_SECONDS);
+  });
+});
+
+// Platforms created before the platform fee are 80 bytes; migrate_platform grows them in place
+describe("platform migration", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let platformPDA: PublicKey;
+  let feeVaultPDA: PublicKey;
+
+  const PLATFORM_V1_LEN = 80;
+  const PLATFORM_VERSION = 2;
+
+  const admin = Keypair.generate();
+  const stranger = Keypair.generate();
+
+  const migratePlatform = (signer: Keypair) =>
+    program.methods
+      .migratePlatform()
+      .accounts({
+        admin: signer.publicKey,
+        platform: platformPDA,
+        feeVault: feeVaultPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      [admin, stranger].map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    const [pda, bump] = PublicKey.findProgramAddressSync([Buffer.from("platform")], program.programId);
+    platformPDA = pda;
+    feeVaultPDA = PublicKey.findProgramAddressSync([Buffer.from("fee_vault")], program.programId)[0];
+
+    // A platform that has created 3 games and 5 players
+    const data = Buffer.alloc(PLATFORM_V1_LEN);
+    Buffer.from(IDL.accounts.find((account: any) => account.name === "Platform").discriminator).copy(data, 0);
+    admin.publicKey.toBuffer().copy(data, 8);
+    data.writeBigUInt64LE(BigInt(3), 40); // game_count
+    data.writeBigUInt64LE(BigInt(1), 48); // games_completed
+    data.writeBigUInt64LE(BigInt(5), 56); // total_players
+    data.writeBigUInt64LE(BigInt(LAMPORTS_PER_SOL / 10), 64); // game_cost
+    data.set([1, bump], 72); // version, bump
+    context.setAccount(platformPDA, {
+      lamports: await context.banksClient.getRent().then((rent) => Number(rent.minimumBalance(BigInt(data.length)))),
+      data,
+      owner: program.programId,
+      executable: false,
+    });
+  });
+
+  it("only lets the platform admin migrate", async () => {
+    await expectFailure(() => migratePlatform(stranger), "PlayerNotAuthorized");
+  });
+
+  it("keeps the v1 counters and sets up the fee vault", async () => {
+    await migratePlatform(admin);
+
+    const platform = await program.account.platform.fetch(platformPDA);
+    expect(platform.version).to.equal(PLATFORM_VERSION);
+    expect(platform.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+    expect(platform.gameCount.toNumber()).to.equal(3);
+    expect(platform.gamesCompleted.toNumber()).to.equal(1);
+    expect(platform.totalPlayers.toNumber()).to.equal(5);
+    expect(platform.feeBps).to.equal(0);
+    expect(platform.feeVaultBump).to.equal(
+      PublicKey.findProgramAddressSync([Buffer.from("fee_vault")], program.programId)[1]
+    );
+    const rent = await context.banksClient.getRent();
+    expect(Number(await context.banksClient.getBalance(feeVaultPDA))).to.equal(Number(rent.minimumBalance(BigInt(0))));
+
+    // Fee instructions work on the migrated platform
+    await program.methods
+      .setPlatformFee(500)
+      .accounts({ admin: admin.publicKey, platform: platformPDA })
+      .signers([admin])
+      .rpc();
+    expect((await program.account.platform.fetch(platformPDA)).feeBps).to.equal(500);
+  });
+
+  it("can't migrate a platform twice", async () => {
+    await expectFailure(() => migratePlatform(admin), "Invalid");
+  });
+});
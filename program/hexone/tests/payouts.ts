@@ -0,0 +1,388 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { Hexone } from "../target/types/hexone";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { fetchGame } from "./board";
+
+const IDL = require("../target/idl/hexone.json");
+
+const RANDOMNESS_SOURCE_HASH = 0;
+const COMBAT_MODEL_FLAT = 0;
+const GAME_STATE_WINNER_FOUND_NOT_PAID_OUT = 3;
+const GAME_STATE_COMPLETED = 2;
+const PLAYER_STATUS_READY = 1;
+const PLAYER_STATUS_PLAYING = 2;
+
+const ENTRY_COST = LAMPORTS_PER_SOL / 10;
+const PAYOUT_BPS = [7000, 2000, 1000, 0, 0, 0];
+const PLATFORM_FEE_BPS = 500;
+const PAYOUT_CLAIM_WINDOW_SECONDS = 7 * 24 * 60 * 60;
+// Each player starts with one corner tile earning 1 XP a minute, so this takes ~5 minutes
+const WINNING_XP_LIMIT = 5;
+
+// Payouts depend on the winner check and the claim window, so these run in bankrun where the clock can be warped
+// The describe blocks share one platform and run in order
+describe("payouts", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let platformPDA: PublicKey;
+  let feeVaultPDA: PublicKey;
+
+  const admin = Keypair.generate();
+  const keeper = Keypair.generate();
+  // Everyone reaches the limit in the same second with the same XP, so places follow colour order
+  const podiumPlayers = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const [red, yellow, green, blue] = podiumPlayers;
+  const latePlayers = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+  const findPDA = (seeds: Buffer[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const playerPDA = (wallet: Keypair) => findPDA([Buffer.from("player"), wallet.publicKey.toBuffer()]);
+
+  const gamePDA = (gameId: number) => {
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt(gameId), 0);
+    return findPDA([Buffer.from("GAME-"), gameIdBuffer]);
+  };
+
+  const treasuryPDA = (game: PublicKey) => findPDA([Buffer.from("game_treasury"), game.toBuffer()]);
+
+  const balance = async (address: PublicKey) => Number(await context.banksClient.getBalance(address));
+
+  // Move the clock (and slot, so transactions get a fresh blockhash) forward
+  const warpSeconds = async (seconds: number) => {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(Math.max(1, Math.ceil(seconds / 0.4)));
+    context.warpToSlot(slot);
+    context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  };
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  const claim = (game: PublicKey, wallet: Keypair) =>
+    program.methods
+      .claimPayout()
+      .accounts({
+        wallet: wallet.publicKey,
+        game,
+        gameTreasury: treasuryPDA(game),
+        player: playerPDA(wallet),
+        platform: platformPDA,
+        feeVault: feeVaultPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const finish = (game: PublicKey, wallet: Keypair) =>
+    program.methods
+      .finishGameForPlayer()
+      .accounts({ wallet: wallet.publicKey, player: playerPDA(wallet), game })
+      .signers([wallet])
+      .rpc();
+
+  const expire = (game: PublicKey) =>
+    program.methods
+      .expirePayouts()
+      .accounts({
+        caller: keeper.publicKey,
+        game,
+        gameTreasury: treasuryPDA(game),
+        platform: platformPDA,
+        feeVault: feeVaultPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([keeper])
+      .rpc();
+
+  // Create a game, fill it and tick it until the winner is determined
+  const playToFinish = async (gameId: number, players: Keypair[]) => {
+    const game = gamePDA(gameId);
+    await program.methods
+      .createGame({
+        rows: 11,
+        columns: 13,
+        maxPlayers: 4,
+        startingResources: 100,
+        joinWindowSeconds: 24 * 60 * 60,
+        entryCost: new anchor.BN(ENTRY_COST),
+        resourcesPerMinute: 10,
+        xpPerMinutePerTile: 1,
+        winningXpLimit: new anchor.BN(WINNING_XP_LIMIT),
+        goldTierBonusXpPerMin: 100,
+        silverTierBonusXpPerMin: 50,
+        bronzeTierBonusXpPerMin: 10,
+        ironTierBonusXpPerMin: 5,
+        crankRewardLamports: new anchor.BN(0),
+        payoutBps: PAYOUT_BPS,
+        maxHitThreshold: 500,
+        maxHitResourceCount: 5,
+        combatModel: COMBAT_MODEL_FLAT,
+        resourcesPerCombatDie: 10,
+        maxCombatDice: 5,
+        expiredAttackPenalty: 5,
+        tileAttackCooldownSeconds: 0,
+        maxOpenAttacksPerPlayer: 0,
+        reinforceWindowSeconds: 3,
+        resignBeneficiaryEnabled: false,
+        commitRevealEnabled: false,
+        randomnessSource: RANDOMNESS_SOURCE_HASH,
+        vrfAuthority: PublicKey.default,
+      })
+      .accounts({ admin: admin.publicKey, platform: platformPDA, game, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    for (const [index, wallet] of players.entries()) {
+      const name = Buffer.alloc(32);
+      Buffer.from(`Player ${index + 1}`).copy(name);
+      await program.methods
+        .createPlayer(Array.from(name), wallet.publicKey)
+        .accounts({
+          wallet: wallet.publicKey,
+          platform: platformPDA,
+          player: playerPDA(wallet),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+      await program.methods
+        .joinGame(new anchor.BN(gameId))
+        .accounts({
+          wallet: wallet.publicKey,
+          player: playerPDA(wallet),
+          game,
+          gameTreasury: treasuryPDA(game),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+    }
+
+    await warpSeconds(6 * 60);
+    await program.methods
+      .tickGame()
+      .accounts({
+        cranker: keeper.publicKey,
+        game,
+        gameTreasury: treasuryPDA(game),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([keeper])
+      .rpc();
+    return game;
+  };
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      [admin, keeper, ...podiumPlayers, ...latePlayers].map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    platformPDA = findPDA([Buffer.from("platform")]);
+    feeVaultPDA = findPDA([Buffer.from("fee_vault")]);
+    await program.methods
+      .createPlatform()
+      .accounts({
+        admin: admin.publicKey,
+        platform: platformPDA,
+        feeVault: feeVaultPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+  describe("set_platform_fee", () => {
+    const setFee = (signer: Keypair, feeBps: number) =>
+      program.methods
+        .setPlatformFee(feeBps)
+        .accounts({ admin: signer.publicKey, platform: platformPDA })
+        .signers([signer])
+        .rpc();
+
+    it("is admin only and capped", async () => {
+      await expectFailure(() => setFee(keeper, PLATFORM_FEE_BPS), "PlayerNotAuthorized");
+      await expectFailure(() => setFee(admin, 2001), "InvalidPlatformFee");
+    });
+
+    it("applies to games created afterwards", async () => {
+      await setFee(admin, PLATFORM_FEE_BPS);
+      const platform = await program.account.platform.fetch(platformPDA);
+      expect(platform.feeBps).to.equal(PLATFORM_FEE_BPS);
+    });
+  });
+
+  describe("claim_payout", () => {
+    let game: PublicKey;
+    let rentReserve: number;
+    let platformFee: number;
+    let prizePool: number;
+    const share = (place: number) => Math.floor((prizePool * PAYOUT_BPS[place]) / 10000);
+
+    before(async () => {
+      game = await playToFinish(0, podiumPlayers);
+      // The treasury keeps its rent reserve out of the pool, and the platform takes its fee first
+      rentReserve = Number((await context.banksClient.getRent()).minimumBalance(BigInt(0)));
+      const pool = (await balance(treasuryPDA(game))) - rentReserve;
+      platformFee = Math.floor((pool * PLATFORM_FEE_BPS) / 10000);
+      prizePool = pool - platformFee;
+    });
+
+    it("records every colour in the final standings", async () => {
+      const state = await fetchGame(program, game);
+      expect(state.gameState).to.equal(GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+      expect(state.standings.slice(0, 4).map((standing: any) => standing.color)).to.deep.equal([1, 2, 3, 4]);
+      expect(state.standings.slice(0, 4).every((standing: any) => standing.xp >= WINNING_XP_LIMIT)).to.be.true;
+      expect(state.standings.slice(0, 4).every((standing: any) => standing.tileCount === 1)).to.be.true;
+    });
+
+    it("pays each place its share of the pool after the platform fee", async () => {
+      const before = await balance(yellow.publicKey);
+      const vaultBefore = await balance(feeVaultPDA);
+      await claim(game, yellow);
+      expect(await balance(yellow.publicKey)).to.equal(before + share(1));
+      expect(await balance(feeVaultPDA)).to.equal(vaultBefore + platformFee);
+
+      const state = await fetchGame(program, game);
+      expect(state.feeBps).to.equal(PLATFORM_FEE_BPS);
+      expect(state.platformFee.toNumber()).to.equal(platformFee);
+      expect(state.prizePool.toNumber()).to.equal(prizePool);
+      const platform = await program.account.platform.fetch(platformPDA);
+      expect(platform.feesCollected.toNumber()).to.equal(platformFee);
+      expect(state.standings[1].payoutClaimed).to.equal(1);
+      expect(state.gameState).to.equal(GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+
+      await expectFailure(() => claim(game, yellow), "NoPayoutDue");
+      await expectFailure(() => claim(game, blue), "NoPayoutDue");
+    });
+
+    it("completes the game on the last payout owed", async () => {
+      const redBefore = await balance(red.publicKey);
+      await claim(game, red);
+      expect(await balance(red.publicKey)).to.equal(redBefore + prizePool - share(1) - share(2));
+
+      // The last claim also takes the treasury's rent reserve
+      const greenBefore = await balance(green.publicKey);
+      await claim(game, green);
+      expect(await balance(green.publicKey)).to.equal(greenBefore + share(2) + rentReserve);
+      expect(await balance(treasuryPDA(game))).to.equal(0);
+
+      const state = await fetchGame(program, game);
+      expect(state.gameState).to.equal(GAME_STATE_COMPLETED);
+      const player = await program.account.player.fetch(playerPDA(red));
+      expect(player.gamesWon.toNumber()).to.equal(1);
+      const platform = await program.account.platform.fetch(platformPDA);
+      expect(platform.gamesCompleted.toNumber()).to.equal(1);
+    });
+
+    it("frees players as they claim, and the rest with finish_game_for_player", async () => {
+      for (const wallet of [red, yellow, green]) {
+        const player = await program.account.player.fetch(playerPDA(wallet));
+        expect(player.playerStatus).to.equal(PLAYER_STATUS_READY);
+      }
+      const unpaid = await program.account.player.fetch(playerPDA(blue));
+      expect(unpaid.playerStatus).to.equal(PLAYER_STATUS_PLAYING);
+
+      await finish(game, blue);
+      const player = await program.account.player.fetch(playerPDA(blue));
+      expect(player.playerStatus).to.equal(PLAYER_STATUS_READY);
+      expect(player.gamesPlayed).to.equal(1);
+    });
+  });
+
+  describe("expire_payouts", () => {
+    let game: PublicKey;
+
+    before(async () => {
+      game = await playToFinish(1, latePlayers);
+    });
+
+    it("waits for the claim window to close", async () => {
+      await expectFailure(() => expire(game), "PayoutClaimWindowOpen");
+    });
+
+    it("sends unclaimed payouts to the fee vault", async () => {
+      await warpSeconds(PAYOUT_CLAIM_WINDOW_SECONDS + 1);
+      await expectFailure(() => claim(game, latePlayers[0]), "PayoutClaimWindowClosed");
+
+      const unclaimed = await balance(treasuryPDA(game));
+      const vaultBefore = await balance(feeVaultPDA);
+      await expire(game);
+      expect(await balance(feeVaultPDA)).to.equal(vaultBefore + unclaimed);
+
+      const state = await fetchGame(program, game);
+      expect(state.gameState).to.equal(GAME_STATE_COMPLETED);
+      const platform = await program.account.platform.fetch(platformPDA);
+      expect(platform.unclaimedCollected.toNumber()).to.equal(unclaimed);
+      expect(platform.gamesCompleted.toNumber()).to.equal(2);
+    });
+  });
+
+  describe("withdraw_fees", () => {
+    const withdraw = (signer: Keypair, amount: number) =>
+      program.methods
+        .withdrawFees(new anchor.BN(amount))
+        .accounts({
+          admin: signer.publicKey,
+          platform: platformPDA,
+          feeVault: feeVaultPDA,
+          destination: signer.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+    it("moves fees out for the admin and keeps the vault's rent reserve", async () => {
+      const rentReserve = Number((await context.banksClient.getRent()).minimumBalance(BigInt(0)));
+      const available = (await balance(feeVaultPDA)) - rentReserve;
+      await expectFailure(() => withdraw(keeper, available), "PlayerNotAuthorized");
+      await expectFailure(() => withdraw(admin, available + 1), "InsufficientFees");
+
+      const adminBefore = await balance(admin.publicKey);
+      await withdraw(admin, available);
+      expect(await balance(admin.publicKey)).to.equal(adminBefore + available);
+      expect(await balance(feeVaultPDA)).to.equal(rentReserve);
+
+      // Everything collected has been withdrawn
+      const platform = await program.account.platform.fetch(platformPDA);
+      expect(platform.feesWithdrawn.toNumber()).to.equal(
+        platform.feesCollected.toNumber() + platform.unclaimedCollected.toNumber()
+      );
+    });
+  });
+});
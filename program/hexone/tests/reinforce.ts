@@ -0,0 +1,243 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { Hexone } from "../target/types/hexone";
+import { PublicKey, Keypair, SystemProgram, SYSVAR_SLOT_HASHES_PUBKEY, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { fetchGame } from "./board";
+
+const IDL = require("../target/idl/hexone.json");
+
+// Legacy hash rolls don't need SlotHashes history, which bankrun doesn't build up
+const RANDOMNESS_SOURCE_HASH = 0;
+const COMBAT_MODEL_FLAT = 0;
+
+const REINFORCE_WINDOW_SECONDS = 30;
+
+// The reinforcement window is time based, so these tests run in bankrun where the clock can be warped
+describe("reinforce_defense", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let gamePDA: PublicKey;
+
+  const players = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const [red, yellow] = players;
+
+  const attackerTileIndex = 11;
+  const defenderTileIndex = 12;  // Yellow's corner
+  const reinforcingTileIndex = 25; // Below yellow's corner
+
+  const findPDA = (seeds: Buffer[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const playerPDA = (wallet: Keypair) => findPDA([Buffer.from("player"), wallet.publicKey.toBuffer()]);
+
+  const defenderPDA = () => {
+    const defenderTileBuffer = Buffer.alloc(2);
+    defenderTileBuffer.writeUInt16LE(defenderTileIndex, 0);
+    return findPDA([Buffer.from("defender"), gamePDA.toBuffer(), defenderTileBuffer]);
+  };
+
+  // Move the clock (and slot, so transactions get a fresh blockhash) forward
+  const warpSeconds = async (seconds: number) => {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(Math.max(1, Math.ceil(seconds / 0.4)));
+    context.warpToSlot(slot);
+    context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  };
+
+  const moveResources = (wallet: Keypair, from: number, to: number, resources: number) =>
+    program.methods
+      .moveResources(from, to, resources)
+      .accounts({
+        playerWallet: wallet.publicKey,
+        signerWallet: wallet.publicKey,
+        player: playerPDA(wallet),
+        game: gamePDA,
+      })
+      .signers([wallet])
+      .rpc();
+
+  const reinforce = (resources: number) =>
+    program.methods
+      .reinforceDefense(reinforcingTileIndex, resources)
+      .accounts({
+        playerWallet: yellow.publicKey,
+        signerWallet: yellow.publicKey,
+        player: playerPDA(yellow),
+        game: gamePDA,
+        defender: defenderPDA(),
+      })
+      .signers([yellow])
+      .rpc();
+
+  const resolve = () =>
+    program.methods
+      .resolveAttack()
+      .accounts({
+        caller: red.publicKey,
+        game: gamePDA,
+        defender: defenderPDA(),
+        payer: red.publicKey,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+      })
+      .signers([red])
+      .rpc();
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      players.map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    const admin = context.payer;
+    const platformPDA = findPDA([Buffer.from("platform")]);
+    await program.methods
+      .createPlatform()
+      .accounts({ admin: admin.publicKey, platform: platformPDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt(0), 0);
+    gamePDA = findPDA([Buffer.from("GAME-"), gameIdBuffer]);
+    await program.methods
+      .createGame({
+        rows: 11,
+        columns: 13,
+        maxPlayers: 4,
+        startingResources: 100,
+        joinWindowSeconds: 24 * 60 * 60,
+        entryCost: new anchor.BN(LAMPORTS_PER_SOL / 10),
+        resourcesPerMinute: 10,
+        xpPerMinutePerTile: 1,
+        winningXpLimit: new anchor.BN(10_000),
+        goldTierBonusXpPerMin: 100,
+        silverTierBonusXpPerMin: 50,
+        bronzeTierBonusXpPerMin: 10,
+        ironTierBonusXpPerMin: 5,
+        crankRewardLamports: new anchor.BN(0),
+        payoutBps: [10000, 0, 0, 0, 0, 0],
+        maxHitThreshold: 500,
+        maxHitResourceCount: 5,
+        combatModel: COMBAT_MODEL_FLAT,
+        resourcesPerCombatDie: 10,
+        maxCombatDice: 5,
+        expiredAttackPenalty: 5,
+        tileAttackCooldownSeconds: 0,
+        maxOpenAttacksPerPlayer: 0,
+        reinforceWindowSeconds: REINFORCE_WINDOW_SECONDS,
+        resignBeneficiaryEnabled: false,
+        commitRevealEnabled: false,
+        randomnessSource: RANDOMNESS_SOURCE_HASH,
+        vrfAuthority: PublicKey.default,
+      })
+      .accounts({ admin: admin.publicKey, platform: platformPDA, game: gamePDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    for (const [index, wallet] of players.entries()) {
+      const name = Buffer.alloc(32);
+      Buffer.from(`Player ${index + 1}`).copy(name);
+      await program.methods
+        .createPlayer(Array.from(name), wallet.publicKey)
+        .accounts({
+          wallet: wallet.publicKey,
+          platform: platformPDA,
+          player: playerPDA(wallet),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+      await program.methods
+        .joinGame(new anchor.BN(0))
+        .accounts({
+          wallet: wallet.publicKey,
+          player: playerPDA(wallet),
+          game: gamePDA,
+          gameTreasury: findPDA([Buffer.from("game_treasury"), gamePDA.toBuffer()]),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+    }
+
+    // Yellow keeps half its corner below it to reinforce from, and red marches along row 0 to 11
+    let game = await fetchGame(program, gamePDA);
+    await moveResources(yellow, defenderTileIndex, reinforcingTileIndex, Math.floor(game.tileData[defenderTileIndex].resourceCount / 2));
+    for (let tile = 0; tile < attackerTileIndex; tile++) {
+      game = await fetchGame(program, gamePDA);
+      await moveResources(red, tile, tile + 1, game.tileData[tile].resourceCount - 1);
+    }
+  });
+
+  it("lets the defender reinforce for the whole window and resolves once it closes", async () => {
+    await program.methods
+      .attackTile(attackerTileIndex, defenderTileIndex)
+      .accounts({
+        playerWallet: red.publicKey,
+        signerWallet: red.publicKey,
+        player: playerPDA(red),
+        game: gamePDA,
+        defender: defenderPDA(),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([red])
+      .rpc();
+
+    // Near the start of the window
+    await warpSeconds(1);
+    await reinforce(5);
+    let defender = await program.account.defender.fetch(defenderPDA());
+    expect(defender.reinforcedResources).to.equal(5);
+
+    // The attack can't be resolved while the defender can still reinforce
+    await expectFailure(() => resolve(), "Invalid");
+
+    // Near the end of the window
+    await warpSeconds(REINFORCE_WINDOW_SECONDS - 2);
+    await reinforce(1);
+    defender = await program.account.defender.fetch(defenderPDA());
+    expect(defender.reinforcedResources).to.equal(6);
+
+    // Once it has closed, reinforcements are rejected and the attack resolves
+    await warpSeconds(2);
+    await expectFailure(() => reinforce(1), "ReinforcementWindowClosed");
+    await resolve();
+    const game = await fetchGame(program, gamePDA);
+    expect(game.tileData[attackerTileIndex].pendingAttacks).to.equal(0);
+  });
+});
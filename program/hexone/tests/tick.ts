@@ -0,0 +1,218 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { BankrunProvider } from "anchor-bankrun";
+import { startAnchor, Clock, ProgramTestContext } from "solana-bankrun";
+import { Hexone } from "../target/types/hexone";
+import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import { fetchGame } from "./board";
+
+const IDL = require("../target/idl/hexone.json");
+
+const RANDOMNESS_SOURCE_HASH = 0;
+const COMBAT_MODEL_FLAT = 0;
+const GAME_STATE_WINNER_FOUND_NOT_PAID_OUT = 3;
+
+const CRANK_REWARD_LAMPORTS = 1_000_000;
+// Crank rewards are capped at 1% of the entry fees: 4 x 0.0375 SOL covers one and a half rewards
+const ENTRY_COST_LAMPORTS = 37_500_000;
+const CRANK_REWARDS_CAP = 1_500_000;
+// Each player starts with one corner tile earning 1 XP a minute, so this takes ~5 minutes
+const WINNING_XP_LIMIT = 5;
+
+// Accrual is time based, so these tests run in bankrun where the clock can be warped
+describe("tick_game", () => {
+  let context: ProgramTestContext;
+  let program: Program<Hexone>;
+  let gamePDA: PublicKey;
+  let treasuryPDA: PublicKey;
+
+  const players = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+  const keeper = Keypair.generate();
+
+  const findPDA = (seeds: Buffer[]): PublicKey =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const playerPDA = (wallet: Keypair) => findPDA([Buffer.from("player"), wallet.publicKey.toBuffer()]);
+
+  // Move the clock (and slot, so transactions get a fresh blockhash) forward
+  const warpSeconds = async (seconds: number) => {
+    const clock = await context.banksClient.getClock();
+    const slot = clock.slot + BigInt(Math.max(1, Math.ceil(seconds / 0.4)));
+    context.warpToSlot(slot);
+    context.setClock(
+      new Clock(
+        slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  };
+
+  const tick = () =>
+    program.methods
+      .tickGame()
+      .accounts({
+        cranker: keeper.publicKey,
+        game: gamePDA,
+        gameTreasury: treasuryPDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([keeper])
+      .rpc();
+
+  const treasuryBalance = async () => Number(await context.banksClient.getBalance(treasuryPDA));
+
+  const expectFailure = async (action: () => Promise<string>, errorName: string) => {
+    try {
+      await action();
+      throw new Error(`Should have failed with ${errorName}!`);
+    } catch (e: any) {
+      if (e.message.includes("Should have failed")) {
+        throw e;
+      }
+      expect(e.toString()).to.include(errorName);
+    }
+  };
+
+  before(async () => {
+    context = await startAnchor(
+      "",
+      [],
+      [...players, keeper].map((wallet) => ({
+        address: wallet.publicKey,
+        info: {
+          lamports: 10 * LAMPORTS_PER_SOL,
+          data: Buffer.alloc(0),
+          owner: SystemProgram.programId,
+          executable: false,
+        },
+      }))
+    );
+    const provider = new BankrunProvider(context);
+    anchor.setProvider(provider);
+    program = new Program<Hexone>(IDL, provider);
+
+    const admin = context.payer;
+    const platformPDA = findPDA([Buffer.from("platform")]);
+    await program.methods
+      .createPlatform()
+      .accounts({ admin: admin.publicKey, platform: platformPDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt(0), 0);
+    gamePDA = findPDA([Buffer.from("GAME-"), gameIdBuffer]);
+    treasuryPDA = findPDA([Buffer.from("game_treasury"), gamePDA.toBuffer()]);
+    await program.methods
+      .createGame({
+        rows: 11,
+        columns: 13,
+        maxPlayers: 4,
+        startingResources: 100,
+        joinWindowSeconds: 24 * 60 * 60,
+        entryCost: new anchor.BN(ENTRY_COST_LAMPORTS),
+        resourcesPerMinute: 10,
+        xpPerMinutePerTile: 1,
+        winningXpLimit: new anchor.BN(WINNING_XP_LIMIT),
+        goldTierBonusXpPerMin: 100,
+        silverTierBonusXpPerMin: 50,
+        bronzeTierBonusXpPerMin: 10,
+        ironTierBonusXpPerMin: 5,
+        crankRewardLamports: new anchor.BN(CRANK_REWARD_LAMPORTS),
+        payoutBps: [10000, 0, 0, 0, 0, 0],
+        maxHitThreshold: 500,
+        maxHitResourceCount: 5,
+        combatModel: COMBAT_MODEL_FLAT,
+        resourcesPerCombatDie: 10,
+        maxCombatDice: 5,
+        expiredAttackPenalty: 5,
+        tileAttackCooldownSeconds: 0,
+        maxOpenAttacksPerPlayer: 0,
+        reinforceWindowSeconds: 3,
+        resignBeneficiaryEnabled: false,
+        commitRevealEnabled: false,
+        randomnessSource: RANDOMNESS_SOURCE_HASH,
+        vrfAuthority: PublicKey.default,
+      })
+      .accounts({ admin: admin.publicKey, platform: platformPDA, game: gamePDA, systemProgram: SystemProgram.programId })
+      .signers([admin])
+      .rpc();
+
+    for (const [index, wallet] of players.entries()) {
+      const name = Buffer.alloc(32);
+      Buffer.from(`Player ${index + 1}`).copy(name);
+      await program.methods
+        .createPlayer(Array.from(name), wallet.publicKey)
+        .accounts({
+          wallet: wallet.publicKey,
+          platform: platformPDA,
+          player: playerPDA(wallet),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+      await program.methods
+        .joinGame(new anchor.BN(0))
+        .accounts({
+          wallet: wallet.publicKey,
+          player: playerPDA(wallet),
+          game: gamePDA,
+          gameTreasury: treasuryPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+    }
+  });
+
+  it("does nothing until a full minute has passed", async () => {
+    await expectFailure(tick, "TickTooEarly");
+  });
+
+  it("accrues XP and resources and pays the keeper", async () => {
+    const treasuryBefore = await treasuryBalance();
+    await warpSeconds(61);
+    await tick();
+
+    const game = await fetchGame(program, gamePDA);
+    expect(game.players.slice(0, 4).map((slot: any) => slot.xp)).to.deep.equal([1, 1, 1, 1]);
+    expect(game.totalResourcesAvailable).to.equal(10);
+    expect(await treasuryBalance()).to.equal(treasuryBefore - CRANK_REWARD_LAMPORTS);
+
+    // The minute has been credited, so an immediate second tick has nothing to do
+    await expectFailure(tick, "TickTooEarly");
+  });
+
+  it("stops paying the keeper once the crank reward cap is reached", async () => {
+    // The second reward is cut down to what's left under the cap
+    let treasuryBefore = await treasuryBalance();
+    await warpSeconds(61);
+    await tick();
+    expect(await treasuryBalance()).to.equal(treasuryBefore - (CRANK_REWARDS_CAP - CRANK_REWARD_LAMPORTS));
+    const game = await fetchGame(program, gamePDA);
+    expect(game.crankRewardsPaid.toNumber()).to.equal(CRANK_REWARDS_CAP);
+
+    // After that, ticks still accrue but pay nothing
+    treasuryBefore = await treasuryBalance();
+    await warpSeconds(61);
+    await tick();
+    expect(await treasuryBalance()).to.equal(treasuryBefore);
+  });
+
+  it("finalizes the winner once the XP limit is reached", async () => {
+    await warpSeconds(5 * 60);
+    await tick();
+
+    const game = await fetchGame(program, gamePDA);
+    expect(game.gameState).to.equal(GAME_STATE_WINNER_FOUND_NOT_PAID_OUT);
+    expect(game.winningPlayerPubkey.toBase58()).to.not.equal(PublicKey.default.toBase58());
+
+    // Finished games can't be ticked
+    await warpSeconds(61);
+    await expectFailure(tick, "InvalidGameState");
+  });
+});
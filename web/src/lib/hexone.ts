@@ -1,5 +1,5 @@
-import { Connection, PublicKey, SystemProgram, Transaction, Keypair } from '@solana/web3.js';
-import { Program, AnchorProvider, Idl, Wallet as AnchorWallet, BN } from '@coral-xyz/anchor';
+import { Connection, PublicKey, SystemProgram, Transaction, Keypair, SYSVAR_SLOT_HASHES_PUBKEY } from '@solana/web3.js';
+import { Program, AnchorProvider, Idl, Wallet as AnchorWallet, BN, BorshAccountsCoder } from '@coral-xyz/anchor';
 import { WalletContextState } from '@solana/wallet-adapter-react';
 import { Hexone } from '../../../program/hexone/target/types/hexone';
 
@@ -10,15 +10,20 @@ export const IDL: Idl = {
   "name": "hexone",
   "instructions": [
     {
-      "name": "createGame",
+      "name": "addResources",
       "accounts": [
         {
-          "name": "admin",
+          "name": "playerWallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signerWallet",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "platform",
+          "name": "player",
           "isMut": true,
           "isSigner": false
         },
@@ -26,25 +31,44 @@ export const IDL: Idl = {
           "name": "game",
           "isMut": true,
           "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "tileIndex",
+          "type": "u16"
         },
         {
-          "name": "systemProgram",
-          "isMut": false,
-          "isSigner": false
+          "name": "resourcesToAdd",
+          "type": "u32"
         }
-      ],
-      "args": []
+      ]
     },
     {
-      "name": "createPlatform",
+      "name": "attackTile",
       "accounts": [
         {
-          "name": "admin",
+          "name": "playerWallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signerWallet",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "platform",
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
           "isMut": true,
           "isSigner": false
         },
@@ -54,23 +78,32 @@ export const IDL: Idl = {
           "isSigner": false
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "attackerTileIndex",
+          "type": "u16"
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16"
+        }
+      ]
     },
     {
-      "name": "createPlayer",
+      "name": "cancelGame",
       "accounts": [
         {
-          "name": "wallet",
-          "isMut": true,
+          "name": "caller",
+          "isMut": false,
           "isSigner": true
         },
         {
-          "name": "platform",
+          "name": "game",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "player",
+          "name": "gameTreasury",
           "isMut": true,
           "isSigner": false
         },
@@ -80,21 +113,10 @@ export const IDL: Idl = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "name",
-          "type": {
-            "array": ["u8", 32]
-          }
-        },
-        {
-          "name": "hotwallet",
-          "type": "publicKey"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "joinGame",
+      "name": "claimPayout",
       "accounts": [
         {
           "name": "wallet",
@@ -102,22 +124,27 @@ export const IDL: Idl = {
           "isSigner": true
         },
         {
-          "name": "player",
+          "name": "game",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "platform",
-          "isMut": false,
+          "name": "gameTreasury",
+          "isMut": true,
           "isSigner": false
         },
         {
-          "name": "game",
+          "name": "player",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "gameTreasury",
+          "name": "platform",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
           "isMut": true,
           "isSigner": false
         },
@@ -127,15 +154,10 @@ export const IDL: Idl = {
           "isSigner": false
         }
       ],
-      "args": [
-        {
-          "name": "gameId",
-          "type": "u64"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "moveResources",
+      "name": "commitRoll",
       "accounts": [
         {
           "name": "playerWallet",
@@ -149,32 +171,31 @@ export const IDL: Idl = {
         },
         {
           "name": "player",
-          "isMut": true,
+          "isMut": false,
           "isSigner": false
         },
         {
           "name": "game",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
           "isMut": true,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "sourceTileIndex",
-          "type": "u16"
-        },
-        {
-          "name": "destinationTileIndex",
-          "type": "u16"
-        },
-        {
-          "name": "resourcesToMove",
-          "type": "u16"
+          "name": "commitment",
+          "type": {
+            "array": ["u8", 32]
+          }
         }
       ]
     },
     {
-      "name": "attackTile",
+      "name": "coordinatedAttack",
       "accounts": [
         {
           "name": "playerWallet",
@@ -209,30 +230,33 @@ export const IDL: Idl = {
       ],
       "args": [
         {
-          "name": "attackerTileIndex",
+          "name": "defenderTileIndex",
           "type": "u16"
         },
         {
-          "name": "defenderTileIndex",
-          "type": "u16"
+          "name": "contributorTileIndices",
+          "type": {
+            "vec": "u16"
+          }
+        },
+        {
+          "name": "contributorAllocationBps",
+          "type": {
+            "vec": "u16"
+          }
         }
       ]
     },
     {
-      "name": "resolveAttack",
+      "name": "createGame",
       "accounts": [
         {
-          "name": "playerWallet",
-          "isMut": false,
-          "isSigner": false
-        },
-        {
-          "name": "signerWallet",
+          "name": "admin",
           "isMut": true,
           "isSigner": true
         },
         {
-          "name": "player",
+          "name": "platform",
           "isMut": true,
           "isSigner": false
         },
@@ -242,30 +266,58 @@ export const IDL: Idl = {
           "isSigner": false
         },
         {
-          "name": "defender",
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "config",
+          "type": {
+            "defined": "GameConfig"
+          }
+        }
+      ]
+    },
+    {
+      "name": "createPlatform",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "platform",
           "isMut": true,
           "isSigner": false
         },
         {
-          "name": "destination",
+          "name": "feeVault",
           "isMut": true,
           "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
         }
       ],
       "args": []
     },
     {
-      "name": "addResources",
+      "name": "createPlayer",
       "accounts": [
         {
-          "name": "playerWallet",
-          "isMut": false,
-          "isSigner": false
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
         },
         {
-          "name": "signerWallet",
+          "name": "platform",
           "isMut": true,
-          "isSigner": true
+          "isSigner": false
         },
         {
           "name": "player",
@@ -273,28 +325,56 @@ export const IDL: Idl = {
           "isSigner": false
         },
         {
-          "name": "game",
-          "isMut": true,
+          "name": "systemProgram",
+          "isMut": false,
           "isSigner": false
         }
       ],
       "args": [
         {
-          "name": "tileIndex",
-          "type": "u16"
+          "name": "name",
+          "type": {
+            "array": ["u8", 32]
+          }
         },
         {
-          "name": "resourcesToAdd",
-          "type": "u32"
+          "name": "hotwallet",
+          "type": "publicKey"
         }
       ]
     },
     {
-      "name": "claimPrize",
+      "name": "expireAttack",
       "accounts": [
         {
-          "name": "wallet",
+          "name": "caller",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
           "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "expirePayouts",
+      "accounts": [
+        {
+          "name": "caller",
+          "isMut": false,
           "isSigner": true
         },
         {
@@ -308,7 +388,12 @@ export const IDL: Idl = {
           "isSigner": false
         },
         {
-          "name": "player",
+          "name": "platform",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
           "isMut": true,
           "isSigner": false
         },
@@ -319,884 +404,2465 @@ export const IDL: Idl = {
         }
       ],
       "args": []
-    }
-  ],
-  "accounts": [
+    },
     {
-      "name": "Game",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "player1",
-            "type": "publicKey"
-          },
-          {
-            "name": "player2",
-            "type": "publicKey"
-          },
-          {
-            "name": "player3",
-            "type": "publicKey"
-          },
-          {
-            "name": "player4",
-            "type": "publicKey"
-          },
-          {
-            "name": "gameId",
-            "type": "u64"
-          },
-          {
-            "name": "availableResourcesTimestamp",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer1",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer2",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer3",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer4",
-            "type": "i64"
-          },
-          {
-            "name": "resourcesPerMinute",
-            "type": "u32"
-          },
-          {
-            "name": "totalResourcesAvailable",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer1",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer2",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer3",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer4",
-            "type": "u32"
-          },
-          {
-            "name": "xpPerMinutePerTile",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer1",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer2",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer3",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer4",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor1",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor2",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor3",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor4",
-            "type": "u32"
-          },
-          {
-            "name": "_paddingU32",
-            "type": {
-              "array": ["u8", 4]
-            }
-          },
-          {
-            "name": "tileData",
-            "type": {
-              "array": [
-                {
-                  "defined": "TileData"
-                },
-                144
-              ]
-            }
-          },
-          {
-            "name": "ironTileCountPlayer1",
-            "type": "u8"
-          },
-          {
-            "name": "bronzeTileCountPlayer1",
-            "type": "u8"
-          },
-          {
-            "name": "silverTileCountPlayer1",
-            "type": "u8"
-          },
-          {
-            "name": "goldTileCountPlayer1",
-            "type": "u8"
-          },
-          {
-            "name": "ironTileCountPlayer2",
-            "type": "u8"
-          },
-          {
-            "name": "bronzeTileCountPlayer2",
-            "type": "u8"
-          },
-          {
-            "name": "silverTileCountPlayer2",
-            "type": "u8"
-          },
-          {
-            "name": "goldTileCountPlayer2",
-            "type": "u8"
-          },
-          {
-            "name": "ironTileCountPlayer3",
-            "type": "u8"
-          },
-          {
-            "name": "bronzeTileCountPlayer3",
-            "type": "u8"
-          },
-          {
-            "name": "silverTileCountPlayer3",
-            "type": "u8"
-          },
-          {
-            "name": "goldTileCountPlayer3",
-            "type": "u8"
-          },
-          {
-            "name": "ironTileCountPlayer4",
-            "type": "u8"
-          },
-          {
-            "name": "bronzeTileCountPlayer4",
-            "type": "u8"
-          },
-          {
-            "name": "silverTileCountPlayer4",
-            "type": "u8"
-          },
-          {
-            "name": "goldTileCountPlayer4",
-            "type": "u8"
-          },
-          {
-            "name": "goldTierBonusXpPerMin",
-            "type": "u8"
-          },
-          {
-            "name": "silverTierBonusXpPerMin",
-            "type": "u8"
-          },
-          {
-            "name": "bronzeTierBonusXpPerMin",
-            "type": "u8"
-          },
-          {
-            "name": "ironTierBonusXpPerMin",
-            "type": "u8"
-          },
-          {
-            "name": "gameState",
-            "type": "u8"
-          },
-          {
-            "name": "rows",
-            "type": "u8"
-          },
-          {
-            "name": "columns",
-            "type": "u8"
-          },
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 7]
-            }
-          }
-        ]
-      }
+      "name": "finishGameForPlayer",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
     },
     {
-      "name": "Platform",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "gameCount",
-            "type": "u64"
-          },
-          {
-            "name": "gamesCompleted",
-            "type": "u64"
-          },
-          {
-            "name": "totalPlayers",
-            "type": "u64"
-          },
-          {
-            "name": "gameCost",
-            "type": "u64"
-          },
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 6]
-            }
+      "name": "fulfillRandomness",
+      "accounts": [
+        {
+          "name": "vrfAuthority",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "game",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "randomness",
+          "type": {
+            "array": ["u8", 32]
           }
-        ]
-      }
+        }
+      ]
     },
     {
-      "name": "Player",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "wallet",
-            "type": "publicKey"
-          },
-          {
-            "name": "name",
-            "type": {
-              "array": ["u8", 32]
-            }
-          },
-          {
-            "name": "gamesPlayed",
-            "type": "u32"
-          },
-          {
-            "name": "gamesWon",
-            "type": "u32"
-          },
-          {
-            "name": "lastGame",
-            "type": {
-              "option": "publicKey"
-            }
-          },
-          {
-            "name": "createdAt",
-            "type": "i64"
-          },
-          {
-            "name": "playerStatus",
-            "type": "u8"
-          },
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "hotwallet",
-            "type": "publicKey"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 4]
-            }
-          }
-        ]
-      }
+      "name": "joinGame",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gameTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "gameId",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "Defender",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "game",
-            "type": "publicKey"
-          },
-          {
-            "name": "defenderTileIndex",
-            "type": "u16"
-          },
-          {
-            "name": "defenderTileColor",
-            "type": "u8"
-          },
-          {
-            "name": "attackerTileIndex",
-            "type": "u16"
-          },
-          {
-            "name": "attackerTileColor",
-            "type": "u8"
-          },
-          {
-            "name": "attackStartedAt",
-            "type": "i64"
-          },
-          {
-            "name": "isAttackResolved",
-            "type": "bool"
-          },
-          {
-            "name": "attackerWon",
-            "type": "bool"
-          },
-          {
-            "name": "attackingResult",
-            "type": "u16"
-          },
-          {
-            "name": "defendingResult",
-            "type": "u16"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 3]
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "types": [
+      "name": "leaveGame",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gameTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
     {
-      "name": "Game",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "player1",
-            "type": "publicKey"
-          },
-          {
-            "name": "player2",
-            "type": "publicKey"
-          },
-          {
-            "name": "player3",
-            "type": "publicKey"
-          },
-          {
-            "name": "player4",
-            "type": "publicKey"
-          },
-          {
-            "name": "gameId",
-            "type": "u64"
-          },
-          {
-            "name": "availableResourcesTimestamp",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer1",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer2",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer3",
-            "type": "i64"
-          },
-          {
-            "name": "xpTimestampPlayer4",
-            "type": "i64"
-          },
-          {
-            "name": "resourcesPerMinute",
-            "type": "u32"
-          },
-          {
-            "name": "totalResourcesAvailable",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer1",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer2",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer3",
-            "type": "u32"
-          },
-          {
-            "name": "resourcesSpentPlayer4",
-            "type": "u32"
-          },
-          {
-            "name": "xpPerMinutePerTile",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer1",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer2",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer3",
-            "type": "u32"
-          },
-          {
-            "name": "xpPlayer4",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor1",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor2",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor3",
-            "type": "u32"
-          },
-          {
-            "name": "tileCountColor4",
-            "type": "u32"
-          },
-          {
-            "name": "_paddingU32",
-            "type": {
-              "array": ["u8", 4]
-            }
+      "name": "migrateGame",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "platform",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "gameId",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "migratePlatform",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "platform",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "moveResources",
+      "accounts": [
+        {
+          "name": "playerWallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signerWallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceTileIndex",
+          "type": "u16"
+        },
+        {
+          "name": "destinationTileIndex",
+          "type": "u16"
+        },
+        {
+          "name": "resourcesToMove",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "reinforceDefense",
+      "accounts": [
+        {
+          "name": "playerWallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signerWallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "sourceTileIndex",
+          "type": "u16"
+        },
+        {
+          "name": "resourcesToCommit",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "resign",
+      "accounts": [
+        {
+          "name": "wallet",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "beneficiary",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "resolveAttack",
+      "accounts": [
+        {
+          "name": "caller",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "payer",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "slotHashes",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "revealRoll",
+      "accounts": [
+        {
+          "name": "playerWallet",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "signerWallet",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "player",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "game",
+          "isMut": false,
+          "isSigner": false
+        },
+        {
+          "name": "defender",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "secret",
+          "type": {
+            "array": ["u8", 32]
+          }
+        }
+      ]
+    },
+    {
+      "name": "setPlatformFee",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "platform",
+          "isMut": true,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "feeBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "tickGame",
+      "accounts": [
+        {
+          "name": "cranker",
+          "isMut": true,
+          "isSigner": true
+        },
+        {
+          "name": "game",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "gameTreasury",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "withdrawFees",
+      "accounts": [
+        {
+          "name": "admin",
+          "isMut": false,
+          "isSigner": true
+        },
+        {
+          "name": "platform",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "feeVault",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "destination",
+          "isMut": true,
+          "isSigner": false
+        },
+        {
+          "name": "systemProgram",
+          "isMut": false,
+          "isSigner": false
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "Defender",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "game",
+            "type": "publicKey"
+          },
+          {
+            "name": "defenderTileIndex",
+            "type": "u16"
+          },
+          {
+            "name": "defenderTileColor",
+            "type": "u8"
+          },
+          {
+            "name": "attackerTileIndex",
+            "type": "u16"
+          },
+          {
+            "name": "attackerTileColor",
+            "type": "u8"
+          },
+          {
+            "name": "attackStartedAt",
+            "type": "i64"
+          },
+          {
+            "name": "attackStartedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "isAttackResolved",
+            "type": "bool"
+          },
+          {
+            "name": "attackerWon",
+            "type": "bool"
+          },
+          {
+            "name": "attackingResult",
+            "type": "u16"
+          },
+          {
+            "name": "defendingResult",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "commitReveal",
+            "type": "bool"
+          },
+          {
+            "name": "revealDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "attackerCommitment",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "defenderCommitment",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "attackerSecret",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "defenderSecret",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "attackerRevealed",
+            "type": "bool"
+          },
+          {
+            "name": "defenderRevealed",
+            "type": "bool"
+          },
+          {
+            "name": "vrfRandomness",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "vrfFulfilled",
+            "type": "bool"
+          },
+          {
+            "name": "expiresAt",
+            "type": "i64"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "defenderResourcesAtStart",
+            "type": "u16"
+          },
+          {
+            "name": "reinforcedResources",
+            "type": "u16"
+          },
+          {
+            "name": "contributorCount",
+            "type": "u8"
+          },
+          {
+            "name": "contributorTileIndices",
+            "type": {
+              "array": ["u16", 6]
+            }
+          },
+          {
+            "name": "contributorAllocationBps",
+            "type": {
+              "array": ["u16", 6]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Game",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "players",
+            "type": {
+              "array": [
+                {
+                  "defined": "PlayerSlot"
+                },
+                6
+              ]
+            }
+          },
+          {
+            "name": "gameId",
+            "type": "u64"
+          },
+          {
+            "name": "availableResourcesTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "resourcesPerMinute",
+            "type": "u32"
+          },
+          {
+            "name": "totalResourcesAvailable",
+            "type": "u32"
+          },
+          {
+            "name": "xpPerMinutePerTile",
+            "type": "u32"
+          },
+          {
+            "name": "resourcesRemainder",
+            "type": "u32"
+          },
+          {
+            "name": "goldTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "silverTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "bronzeTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "ironTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "paddingTierBonus",
+            "type": {
+              "array": ["u8", 4]
+            }
+          },
+          {
+            "name": "winningPlayerPubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "winningXpLimit",
+            "type": "u64"
+          },
+          {
+            "name": "entryCost",
+            "type": "u64"
+          },
+          {
+            "name": "vrfAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "gameState",
+            "type": "u8"
+          },
+          {
+            "name": "rows",
+            "type": "u8"
+          },
+          {
+            "name": "columns",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "winnerCalculationFlag",
+            "type": "u8"
+          },
+          {
+            "name": "maxHitThreshold",
+            "type": "u16"
+          },
+          {
+            "name": "maxHitResourceCount",
+            "type": "u8"
+          },
+          {
+            "name": "commitRevealEnabled",
+            "type": "u8"
+          },
+          {
+            "name": "randomnessSource",
+            "type": "u8"
+          },
+          {
+            "name": "expiredAttackPenalty",
+            "type": "u8"
+          },
+          {
+            "name": "combatModel",
+            "type": "u8"
+          },
+          {
+            "name": "maxCombatDice",
+            "type": "u8"
+          },
+          {
+            "name": "resourcesPerCombatDie",
+            "type": "u16"
+          },
+          {
+            "name": "tileAttackCooldownSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "maxOpenAttacksPerPlayer",
+            "type": "u8"
+          },
+          {
+            "name": "maxPlayers",
+            "type": "u8"
+          },
+          {
+            "name": "playerCount",
+            "type": "u8"
+          },
+          {
+            "name": "resignBeneficiaryEnabled",
+            "type": "u8"
+          },
+          {
+            "name": "startingResources",
+            "type": "u16"
+          },
+          {
+            "name": "reinforceWindowSeconds",
+            "type": "u16"
+          },
+          {
+            "name": "paddingConfig",
+            "type": {
+              "array": ["u8", 4]
+            }
+          },
+          {
+            "name": "crankRewardLamports",
+            "type": "u64"
+          },
+          {
+            "name": "winnerReachedLimitAt",
+            "type": "i64"
+          },
+          {
+            "name": "prizePool",
+            "type": "u64"
+          },
+          {
+            "name": "platformFee",
+            "type": "u64"
+          },
+          {
+            "name": "payoutDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "standings",
+            "type": {
+              "array": [
+                {
+                  "defined": "FinalStanding"
+                },
+                6
+              ]
+            }
+          },
+          {
+            "name": "payoutBps",
+            "type": {
+              "array": ["u16", 6]
+            }
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "winCondition",
+            "type": "u8"
+          },
+          {
+            "name": "paddingPayouts",
+            "type": {
+              "array": ["u8", 1]
+            }
+          },
+          {
+            "name": "joinDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "crankRewardsPaid",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Platform",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "gameCount",
+            "type": "u64"
+          },
+          {
+            "name": "gamesCompleted",
+            "type": "u64"
+          },
+          {
+            "name": "totalPlayers",
+            "type": "u64"
+          },
+          {
+            "name": "gameCost",
+            "type": "u64"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": ["u8", 6]
+            }
+          },
+          {
+            "name": "feesCollected",
+            "type": "u64"
+          },
+          {
+            "name": "unclaimedCollected",
+            "type": "u64"
+          },
+          {
+            "name": "feesWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "feeVaultBump",
+            "type": "u8"
+          },
+          {
+            "name": "paddingFees",
+            "type": {
+              "array": ["u8", 5]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Player",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "gamesPlayed",
+            "type": "u32"
+          },
+          {
+            "name": "gamesWon",
+            "type": "u32"
+          },
+          {
+            "name": "lastGame",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "playerStatus",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "hotwallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": ["u8", 4]
+            }
+          }
+        ]
+      }
+    }
+  ],
+  "types": [
+    {
+      "name": "Defender",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "game",
+            "type": "publicKey"
+          },
+          {
+            "name": "defenderTileIndex",
+            "type": "u16"
+          },
+          {
+            "name": "defenderTileColor",
+            "type": "u8"
+          },
+          {
+            "name": "attackerTileIndex",
+            "type": "u16"
+          },
+          {
+            "name": "attackerTileColor",
+            "type": "u8"
+          },
+          {
+            "name": "attackStartedAt",
+            "type": "i64"
+          },
+          {
+            "name": "attackStartedSlot",
+            "type": "u64"
+          },
+          {
+            "name": "isAttackResolved",
+            "type": "bool"
+          },
+          {
+            "name": "attackerWon",
+            "type": "bool"
+          },
+          {
+            "name": "attackingResult",
+            "type": "u16"
+          },
+          {
+            "name": "defendingResult",
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "commitReveal",
+            "type": "bool"
+          },
+          {
+            "name": "revealDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "attackerCommitment",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "defenderCommitment",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "attackerSecret",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "defenderSecret",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "attackerRevealed",
+            "type": "bool"
+          },
+          {
+            "name": "defenderRevealed",
+            "type": "bool"
+          },
+          {
+            "name": "vrfRandomness",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "vrfFulfilled",
+            "type": "bool"
+          },
+          {
+            "name": "expiresAt",
+            "type": "i64"
+          },
+          {
+            "name": "payer",
+            "type": "publicKey"
+          },
+          {
+            "name": "defenderResourcesAtStart",
+            "type": "u16"
+          },
+          {
+            "name": "reinforcedResources",
+            "type": "u16"
+          },
+          {
+            "name": "contributorCount",
+            "type": "u8"
+          },
+          {
+            "name": "contributorTileIndices",
+            "type": {
+              "array": ["u16", 6]
+            }
+          },
+          {
+            "name": "contributorAllocationBps",
+            "type": {
+              "array": ["u16", 6]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "FinalStanding",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "color",
+            "type": "u8"
+          },
+          {
+            "name": "eliminatedOrder",
+            "type": "u8"
+          },
+          {
+            "name": "payoutClaimed",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": "u8"
+          },
+          {
+            "name": "tileCount",
+            "type": "u32"
+          },
+          {
+            "name": "xp",
+            "type": "u32"
+          },
+          {
+            "name": "paddingXp",
+            "type": {
+              "array": ["u8", 4]
+            }
+          },
+          {
+            "name": "payout",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Game",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "players",
+            "type": {
+              "array": [
+                {
+                  "defined": "PlayerSlot"
+                },
+                6
+              ]
+            }
+          },
+          {
+            "name": "gameId",
+            "type": "u64"
+          },
+          {
+            "name": "availableResourcesTimestamp",
+            "type": "i64"
+          },
+          {
+            "name": "resourcesPerMinute",
+            "type": "u32"
+          },
+          {
+            "name": "totalResourcesAvailable",
+            "type": "u32"
+          },
+          {
+            "name": "xpPerMinutePerTile",
+            "type": "u32"
+          },
+          {
+            "name": "resourcesRemainder",
+            "type": "u32"
+          },
+          {
+            "name": "goldTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "silverTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "bronzeTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "ironTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "paddingTierBonus",
+            "type": {
+              "array": ["u8", 4]
+            }
+          },
+          {
+            "name": "winningPlayerPubkey",
+            "type": "publicKey"
+          },
+          {
+            "name": "winningXpLimit",
+            "type": "u64"
+          },
+          {
+            "name": "entryCost",
+            "type": "u64"
+          },
+          {
+            "name": "vrfAuthority",
+            "type": "publicKey"
+          },
+          {
+            "name": "gameState",
+            "type": "u8"
+          },
+          {
+            "name": "rows",
+            "type": "u8"
+          },
+          {
+            "name": "columns",
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "winnerCalculationFlag",
+            "type": "u8"
+          },
+          {
+            "name": "maxHitThreshold",
+            "type": "u16"
+          },
+          {
+            "name": "maxHitResourceCount",
+            "type": "u8"
+          },
+          {
+            "name": "commitRevealEnabled",
+            "type": "u8"
+          },
+          {
+            "name": "randomnessSource",
+            "type": "u8"
+          },
+          {
+            "name": "expiredAttackPenalty",
+            "type": "u8"
+          },
+          {
+            "name": "combatModel",
+            "type": "u8"
+          },
+          {
+            "name": "maxCombatDice",
+            "type": "u8"
+          },
+          {
+            "name": "resourcesPerCombatDie",
+            "type": "u16"
+          },
+          {
+            "name": "tileAttackCooldownSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "maxOpenAttacksPerPlayer",
+            "type": "u8"
+          },
+          {
+            "name": "maxPlayers",
+            "type": "u8"
+          },
+          {
+            "name": "playerCount",
+            "type": "u8"
+          },
+          {
+            "name": "resignBeneficiaryEnabled",
+            "type": "u8"
+          },
+          {
+            "name": "startingResources",
+            "type": "u16"
+          },
+          {
+            "name": "reinforceWindowSeconds",
+            "type": "u16"
+          },
+          {
+            "name": "paddingConfig",
+            "type": {
+              "array": ["u8", 4]
+            }
+          },
+          {
+            "name": "crankRewardLamports",
+            "type": "u64"
+          },
+          {
+            "name": "winnerReachedLimitAt",
+            "type": "i64"
+          },
+          {
+            "name": "prizePool",
+            "type": "u64"
           },
           {
-            "name": "tileData",
+            "name": "platformFee",
+            "type": "u64"
+          },
+          {
+            "name": "payoutDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "standings",
             "type": {
               "array": [
                 {
-                  "defined": "TileData"
+                  "defined": "FinalStanding"
                 },
-                144
+                6
               ]
             }
           },
           {
-            "name": "ironTileCountPlayer1",
+            "name": "payoutBps",
+            "type": {
+              "array": ["u16", 6]
+            }
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "winCondition",
+            "type": "u8"
+          },
+          {
+            "name": "paddingPayouts",
+            "type": {
+              "array": ["u8", 1]
+            }
+          },
+          {
+            "name": "joinDeadline",
+            "type": "i64"
+          },
+          {
+            "name": "crankRewardsPaid",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "GameConfig",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rows",
+            "type": "u8"
+          },
+          {
+            "name": "columns",
+            "type": "u8"
+          },
+          {
+            "name": "maxPlayers",
+            "type": "u8"
+          },
+          {
+            "name": "startingResources",
+            "type": "u16"
+          },
+          {
+            "name": "joinWindowSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "entryCost",
+            "type": "u64"
+          },
+          {
+            "name": "resourcesPerMinute",
+            "type": "u32"
+          },
+          {
+            "name": "xpPerMinutePerTile",
+            "type": "u32"
+          },
+          {
+            "name": "winningXpLimit",
+            "type": "u64"
+          },
+          {
+            "name": "goldTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "silverTierBonusXpPerMin",
+            "type": "u8"
+          },
+          {
+            "name": "bronzeTierBonusXpPerMin",
             "type": "u8"
           },
           {
-            "name": "bronzeTileCountPlayer1",
+            "name": "ironTierBonusXpPerMin",
             "type": "u8"
           },
           {
-            "name": "silverTileCountPlayer1",
+            "name": "crankRewardLamports",
+            "type": "u64"
+          },
+          {
+            "name": "payoutBps",
+            "type": {
+              "array": ["u16", 6]
+            }
+          },
+          {
+            "name": "maxHitThreshold",
+            "type": "u16"
+          },
+          {
+            "name": "maxHitResourceCount",
             "type": "u8"
           },
           {
-            "name": "goldTileCountPlayer1",
+            "name": "combatModel",
             "type": "u8"
           },
           {
-            "name": "ironTileCountPlayer2",
+            "name": "resourcesPerCombatDie",
+            "type": "u16"
+          },
+          {
+            "name": "maxCombatDice",
             "type": "u8"
           },
           {
-            "name": "bronzeTileCountPlayer2",
+            "name": "expiredAttackPenalty",
             "type": "u8"
           },
           {
-            "name": "silverTileCountPlayer2",
+            "name": "tileAttackCooldownSeconds",
+            "type": "u32"
+          },
+          {
+            "name": "maxOpenAttacksPerPlayer",
             "type": "u8"
           },
           {
-            "name": "goldTileCountPlayer2",
+            "name": "reinforceWindowSeconds",
+            "type": "u16"
+          },
+          {
+            "name": "resignBeneficiaryEnabled",
+            "type": "bool"
+          },
+          {
+            "name": "commitRevealEnabled",
+            "type": "bool"
+          },
+          {
+            "name": "randomnessSource",
             "type": "u8"
           },
           {
-            "name": "ironTileCountPlayer3",
+            "name": "vrfAuthority",
+            "type": "publicKey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "Platform",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "type": "publicKey"
+          },
+          {
+            "name": "gameCount",
+            "type": "u64"
+          },
+          {
+            "name": "gamesCompleted",
+            "type": "u64"
+          },
+          {
+            "name": "totalPlayers",
+            "type": "u64"
+          },
+          {
+            "name": "gameCost",
+            "type": "u64"
+          },
+          {
+            "name": "version",
             "type": "u8"
           },
           {
-            "name": "bronzeTileCountPlayer3",
+            "name": "bump",
             "type": "u8"
           },
           {
-            "name": "silverTileCountPlayer3",
+            "name": "padding",
+            "type": {
+              "array": ["u8", 6]
+            }
+          },
+          {
+            "name": "feesCollected",
+            "type": "u64"
+          },
+          {
+            "name": "unclaimedCollected",
+            "type": "u64"
+          },
+          {
+            "name": "feesWithdrawn",
+            "type": "u64"
+          },
+          {
+            "name": "feeBps",
+            "type": "u16"
+          },
+          {
+            "name": "feeVaultBump",
             "type": "u8"
           },
           {
-            "name": "goldTileCountPlayer3",
+            "name": "paddingFees",
+            "type": {
+              "array": ["u8", 5]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "Player",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "publicKey"
+          },
+          {
+            "name": "name",
+            "type": {
+              "array": ["u8", 32]
+            }
+          },
+          {
+            "name": "gamesPlayed",
+            "type": "u32"
+          },
+          {
+            "name": "gamesWon",
+            "type": "u32"
+          },
+          {
+            "name": "lastGame",
+            "type": {
+              "option": "publicKey"
+            }
+          },
+          {
+            "name": "createdAt",
+            "type": "i64"
+          },
+          {
+            "name": "playerStatus",
             "type": "u8"
           },
           {
-            "name": "ironTileCountPlayer4",
+            "name": "version",
             "type": "u8"
           },
           {
-            "name": "bronzeTileCountPlayer4",
+            "name": "bump",
             "type": "u8"
           },
           {
-            "name": "silverTileCountPlayer4",
-            "type": "u8"
+            "name": "hotwallet",
+            "type": "publicKey"
           },
           {
-            "name": "goldTileCountPlayer4",
-            "type": "u8"
+            "name": "padding",
+            "type": {
+              "array": ["u8", 4]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "PlayerSlot",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "publicKey"
           },
           {
-            "name": "goldTierBonusXpPerMin",
-            "type": "u8"
+            "name": "xpTimestamp",
+            "type": "i64"
           },
           {
-            "name": "silverTierBonusXpPerMin",
-            "type": "u8"
+            "name": "resourcesSpent",
+            "type": "u32"
           },
           {
-            "name": "bronzeTierBonusXpPerMin",
-            "type": "u8"
+            "name": "xp",
+            "type": "u32"
           },
           {
-            "name": "ironTierBonusXpPerMin",
-            "type": "u8"
+            "name": "tileCount",
+            "type": "u32"
           },
           {
-            "name": "_paddingTierBonus",
+            "name": "tierTileCounts",
             "type": {
               "array": ["u8", 4]
             }
           },
           {
-            "name": "winningPlayerPubkey",
-            "type": "publicKey"
+            "name": "openAttacks",
+            "type": "u8"
           },
           {
-            "name": "winningXpLimit",
-            "type": "u64"
+            "name": "xpRemainder",
+            "type": "u8"
           },
           {
-            "name": "gameState",
+            "name": "eliminatedOrder",
             "type": "u8"
           },
           {
-            "name": "rows",
+            "name": "resigned",
             "type": "u8"
           },
           {
-            "name": "columns",
-            "type": "u8"
+            "name": "xpPastLimit",
+            "type": "u32"
           },
           {
-            "name": "version",
-            "type": "u8"
+            "name": "limitReachedAt",
+            "type": "i64"
           },
           {
-            "name": "bump",
-            "type": "u8"
+            "name": "eliminatedAt",
+            "type": "i64"
+          }
+        ]
+      }
+    }
+  ],
+  "events": [
+    {
+      "name": "AttackExpired",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "attackerColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "attackerTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "penalty",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AttackResolved",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "attackerTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "attackerWon",
+          "type": "bool",
+          "index": false
+        },
+        {
+          "name": "attackerTileColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "attackerResources",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "attackerRollResult",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderTileColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "defenderResources",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderRollResult",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "hitResourceCount",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AttackStarted",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "attackerColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "defenderColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "attackerTileIndices",
+          "type": {
+            "vec": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "contributorAllocationBps",
+          "type": {
+            "vec": "u16"
+          },
+          "index": false
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderResources",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "AttackVoided",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "attackerColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "attackerTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "DefenseReinforced",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "sourceTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "resourcesCommitted",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "FeesWithdrawn",
+      "fields": [
+        {
+          "name": "destination",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feesWithdrawn",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GameCancelled",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "cancelledBy",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "playersRefunded",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "refundTotal",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GameCreated",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "admin",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "rows",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "columns",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "maxPlayers",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "startingTileIndices",
+          "type": {
+            "vec": "u16"
           },
-          {
-            "name": "winnerCalculationFlag",
-            "type": "u8"
+          "index": false
+        },
+        {
+          "name": "startingResources",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GameMigrated",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "fromVersion",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "toVersion",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GameStarted",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "GameTicked",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "cranker",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "reward",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PayoutClaimed",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "place",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "completed",
+          "type": "bool",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PayoutsExpired",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "unclaimed",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlatformFeeCollected",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "amount",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "feeBps",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlatformFeeUpdated",
+      "fields": [
+        {
+          "name": "oldFeeBps",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "newFeeBps",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlatformMigrated",
+      "fields": [
+        {
+          "name": "fromVersion",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "toVersion",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlayerCreated",
+      "fields": [
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "hotwallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "name",
+          "type": {
+            "array": ["u8", 32]
           },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 2]
-            }
-          }
-        ]
-      }
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlayerEliminated",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "eliminatedOrder",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "playersLeft",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlayerFinishedGame",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "gameState",
+          "type": "u8",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "PlayerJoined",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "startingTileIndex",
+          "type": "u16",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "Platform",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "admin",
-            "type": "publicKey"
-          },
-          {
-            "name": "gameCount",
-            "type": "u64"
-          },
-          {
-            "name": "gamesCompleted",
-            "type": "u64"
-          },
-          {
-            "name": "totalPlayers",
-            "type": "u64"
-          },
-          {
-            "name": "gameCost",
-            "type": "u64"
-          },
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 6]
-            }
-          }
-        ]
-      }
+      "name": "PlayerLeft",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "refund",
+          "type": "u64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "Player",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "wallet",
-            "type": "publicKey"
-          },
-          {
-            "name": "name",
-            "type": {
-              "array": ["u8", 32]
-            }
-          },
-          {
-            "name": "gamesPlayed",
-            "type": "u32"
-          },
-          {
-            "name": "gamesWon",
-            "type": "u32"
-          },
-          {
-            "name": "lastGame",
-            "type": {
-              "option": "publicKey"
-            }
-          },
-          {
-            "name": "createdAt",
-            "type": "i64"
-          },
-          {
-            "name": "playerStatus",
-            "type": "u8"
-          },
-          {
-            "name": "version",
-            "type": "u8"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "hotwallet",
-            "type": "publicKey"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 4]
-            }
-          }
-        ]
-      }
+      "name": "PlayerResigned",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "wallet",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "beneficiary",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "tiles",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "TileData",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "color",
-            "type": "u8"
-          },
-          {
-            "name": "_pad",
-            "type": "u8"
-          },
-          {
-            "name": "resourceCount",
-            "type": "u16"
-          }
-        ]
-      }
+      "name": "RandomnessRequested",
+      "fields": [
+        {
+          "name": "game",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "defender",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "defenderTileIndex",
+          "type": "u16",
+          "index": false
+        }
+      ]
     },
     {
-      "name": "Defender",
-      "type": {
-        "kind": "struct",
-        "fields": [
-          {
-            "name": "game",
-            "type": "publicKey"
-          },
-          {
-            "name": "defenderTileIndex",
-            "type": "u16"
-          },
-          {
-            "name": "defenderTileColor",
-            "type": "u8"
-          },
-          {
-            "name": "attackerTileIndex",
-            "type": "u16"
-          },
-          {
-            "name": "attackerTileColor",
-            "type": "u8"
-          },
-          {
-            "name": "attackStartedAt",
-            "type": "i64"
-          },
-          {
-            "name": "isAttackResolved",
-            "type": "bool"
-          },
-          {
-            "name": "attackerWon",
-            "type": "bool"
-          },
-          {
-            "name": "attackingResult",
-            "type": "u16"
-          },
-          {
-            "name": "defendingResult",
-            "type": "u16"
-          },
-          {
-            "name": "bump",
-            "type": "u8"
-          },
-          {
-            "name": "_padding",
-            "type": {
-              "array": ["u8", 3]
-            }
-          }
-        ]
-      }
-    }
-  ],
-  "events": [
+      "name": "ResourcesAdded",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "tileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "resourcesAdded",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "tileResourceCount",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
     {
-      "name": "AttackResolved",
+      "name": "ResourcesMoved",
       "fields": [
         {
-          "name": "attackerTileColor",
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "color",
           "type": "u8",
           "index": false
         },
         {
-          "name": "attackerResources",
+          "name": "sourceTileIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "attackerRollResult",
+          "name": "destinationTileIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "defenderTileColor",
+          "name": "resourcesMoved",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "sourceResourceCount",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "destinationResourceCount",
+          "type": "u16",
+          "index": false
+        }
+      ]
+    },
+    {
+      "name": "TileCaptured",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "tileIndex",
+          "type": "u16",
+          "index": false
+        },
+        {
+          "name": "previousColor",
           "type": "u8",
           "index": false
         },
         {
-          "name": "defenderResources",
+          "name": "newColor",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "attackerTileIndex",
           "type": "u16",
           "index": false
         },
         {
-          "name": "defenderRollResult",
+          "name": "resourceCount",
           "type": "u16",
           "index": false
+        }
+      ]
+    },
+    {
+      "name": "WinnerDetermined",
+      "fields": [
+        {
+          "name": "gameId",
+          "type": "u64",
+          "index": false
         },
         {
-          "name": "hitResourceCount",
+          "name": "winner",
+          "type": "publicKey",
+          "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "xp",
+          "type": "u64",
+          "index": false
+        },
+        {
+          "name": "reachedLimitAt",
+          "type": "i64",
+          "index": false
+        },
+        {
+          "name": "winCondition",
           "type": "u8",
           "index": false
+        },
+        {
+          "name": "standings",
+          "type": "bytes",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
         }
       ]
     },
     {
-      "name": "GameStarted",
+      "name": "XpAccrued",
       "fields": [
         {
           "name": "gameId",
           "type": "u64",
           "index": false
+        },
+        {
+          "name": "color",
+          "type": "u8",
+          "index": false
+        },
+        {
+          "name": "xpAdded",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "totalXp",
+          "type": "u32",
+          "index": false
+        },
+        {
+          "name": "timestamp",
+          "type": "i64",
+          "index": false
         }
       ]
     }
@@ -1231,6 +2897,186 @@ export const IDL: Idl = {
       "code": 6005,
       "name": "GameFull",
       "msg": "Game is full"
+    },
+    {
+      "code": 6006,
+      "name": "InvalidTreasury",
+      "msg": "Invalid treasury account"
+    },
+    {
+      "code": 6007,
+      "name": "InvalidGameState",
+      "msg": "Invalid game state"
+    },
+    {
+      "code": 6008,
+      "name": "AttackRandomnessNotReady",
+      "msg": "Not enough blocks have been produced since the attack started"
+    },
+    {
+      "code": 6009,
+      "name": "AttackRandomnessExpired",
+      "msg": "Slot hashes for this attack are no longer available"
+    },
+    {
+      "code": 6010,
+      "name": "CommitRevealDisabled",
+      "msg": "Commit-reveal is not enabled for this attack"
+    },
+    {
+      "code": 6011,
+      "name": "AlreadyCommitted",
+      "msg": "A commitment has already been made for this side"
+    },
+    {
+      "code": 6012,
+      "name": "CommitmentsIncomplete",
+      "msg": "Both attacker and defender must commit before revealing"
+    },
+    {
+      "code": 6013,
+      "name": "InvalidReveal",
+      "msg": "Revealed secret does not match the commitment"
+    },
+    {
+      "code": 6014,
+      "name": "RevealWindowClosed",
+      "msg": "The commit-reveal window for this attack has closed"
+    },
+    {
+      "code": 6015,
+      "name": "RevealPending",
+      "msg": "Waiting for both sides to reveal or for the reveal deadline"
+    },
+    {
+      "code": 6016,
+      "name": "InvalidRandomnessSource",
+      "msg": "Invalid randomness source"
+    },
+    {
+      "code": 6017,
+      "name": "RandomnessNotFulfilled",
+      "msg": "Randomness for this attack has not been fulfilled yet"
+    },
+    {
+      "code": 6018,
+      "name": "RandomnessAlreadyFulfilled",
+      "msg": "Randomness for this attack has already been fulfilled"
+    },
+    {
+      "code": 6019,
+      "name": "AttackNotExpired",
+      "msg": "Attack has not expired yet"
+    },
+    {
+      "code": 6020,
+      "name": "InvalidCombatSettings",
+      "msg": "Invalid combat settings"
+    },
+    {
+      "code": 6021,
+      "name": "ReinforcementWindowClosed",
+      "msg": "The reinforcement window for this attack has closed"
+    },
+    {
+      "code": 6022,
+      "name": "AttackingTileLocked",
+      "msg": "Resources can't be withdrawn from a tile with a pending attack"
+    },
+    {
+      "code": 6023,
+      "name": "InvalidAllocation",
+      "msg": "Invalid coordinated attack allocation"
+    },
+    {
+      "code": 6024,
+      "name": "AttackCooldownActive",
+      "msg": "This tile is still on attack cooldown"
+    },
+    {
+      "code": 6025,
+      "name": "TooManyOpenAttacks",
+      "msg": "Too many open attacks"
+    },
+    {
+      "code": 6026,
+      "name": "InvalidGameConfig",
+      "msg": "Invalid game config"
+    },
+    {
+      "code": 6027,
+      "name": "GameNeedsMigration",
+      "msg": "Game must be migrated before it can be played"
+    },
+    {
+      "code": 6028,
+      "name": "TickTooEarly",
+      "msg": "Nothing to tick yet"
+    },
+    {
+      "code": 6029,
+      "name": "NoPayoutDue",
+      "msg": "No payout due"
+    },
+    {
+      "code": 6030,
+      "name": "PayoutClaimWindowClosed",
+      "msg": "The payout claim window has closed"
+    },
+    {
+      "code": 6031,
+      "name": "PayoutClaimWindowOpen",
+      "msg": "The payout claim window is still open"
+    },
+    {
+      "code": 6032,
+      "name": "InvalidPlatformFee",
+      "msg": "Platform fee too high"
+    },
+    {
+      "code": 6033,
+      "name": "InsufficientFees",
+      "msg": "Not enough fees to withdraw"
+    },
+    {
+      "code": 6034,
+      "name": "JoinDeadlineNotReached",
+      "msg": "Only the game admin can cancel before the join deadline"
+    },
+    {
+      "code": 6035,
+      "name": "MissingPlayerAccounts",
+      "msg": "Wallet and player accounts must be passed for every joined player"
+    },
+    {
+      "code": 6036,
+      "name": "AlreadyJoined",
+      "msg": "Player has already joined this game"
+    },
+    {
+      "code": 6037,
+      "name": "GameNotFinished",
+      "msg": "The game is not over for this player"
+    },
+    {
+      "code": 6038,
+      "name": "PlayerNotInPlay",
+      "msg": "Player is no longer in play"
+    },
+    {
+      "code": 6039,
+      "name": "OpenAttacksPending",
+      "msg": "Resolve or expire open attacks first"
+    },
+    {
+      "code": 6040,
+      "name": "InvalidResignBeneficiary",
+      "msg": "Invalid resign beneficiary"
+    },
+    {
+      "code": 6041,
+      "name": "JoinDeadlinePassed",
+      "msg": "The join deadline has passed"
     }
   ]
 };
@@ -1254,7 +3100,7 @@ export interface Game {
 }
 
 export interface TileData {
-  color: number; // 0 = unclaimed, 1-4 = player colors
+  color: number; // 0 = unclaimed, 1-6 = player colors
   resourceCount: number;
 }
 
@@ -1266,7 +3112,7 @@ export interface GameAccount {
   totalTiles: number;
   tilesCoveredPercent: number;
   cost: number;
-  tileData: TileData[]; // Array of 144 tiles with color and resource count
+  tileData: TileData[]; // rows * columns tiles with color and resource count
   rows: number;
   columns: number;
   resourcesPerMinute: number;
@@ -1325,6 +3171,150 @@ export interface PlatformAccountData {
   bump: number;
 }
 
+// Arguments to create_game, see GameConfig in the program
+export interface GameConfig {
+  rows: number;
+  columns: number;
+  maxPlayers: number;
+  startingResources: number;
+  joinWindowSeconds: number;
+  entryCost: BN; // Lamports
+  resourcesPerMinute: number;
+  xpPerMinutePerTile: number;
+  winningXpLimit: BN;
+  goldTierBonusXpPerMin: number;
+  silverTierBonusXpPerMin: number;
+  bronzeTierBonusXpPerMin: number;
+  ironTierBonusXpPerMin: number;
+  crankRewardLamports: BN;
+  payoutBps: number[]; // One entry per finishing place, summing to 10000
+  maxHitThreshold: number;
+  maxHitResourceCount: number;
+  combatModel: number;
+  resourcesPerCombatDie: number;
+  maxCombatDice: number;
+  expiredAttackPenalty: number;
+  tileAttackCooldownSeconds: number;
+  maxOpenAttacksPerPlayer: number;
+  reinforceWindowSeconds: number;
+  resignBeneficiaryEnabled: boolean;
+  commitRevealEnabled: boolean;
+  randomnessSource: number;
+  vrfAuthority: PublicKey;
+}
+
+// The original 11x13, 4-player rules, as GameConfig::default() in the program
+export const DEFAULT_GAME_CONFIG: GameConfig = {
+  rows: 11,
+  columns: 13,
+  maxPlayers: 4,
+  startingResources: 100,
+  joinWindowSeconds: 24 * 60 * 60,
+  entryCost: new BN(100_000_000), // 0.1 SOL
+  resourcesPerMinute: 10,
+  xpPerMinutePerTile: 1,
+  winningXpLimit: new BN(10_000),
+  goldTierBonusXpPerMin: 100,
+  silverTierBonusXpPerMin: 50,
+  bronzeTierBonusXpPerMin: 10,
+  ironTierBonusXpPerMin: 5,
+  crankRewardLamports: new BN(0),
+  payoutBps: [10000, 0, 0, 0, 0, 0],
+  maxHitThreshold: 500,
+  maxHitResourceCount: 5,
+  combatModel: 0, // Flat
+  resourcesPerCombatDie: 10,
+  maxCombatDice: 5,
+  expiredAttackPenalty: 5,
+  tileAttackCooldownSeconds: 0,
+  maxOpenAttacksPerPlayer: 0,
+  reinforceWindowSeconds: 3,
+  resignBeneficiaryEnabled: false,
+  commitRevealEnabled: false,
+  randomnessSource: 1, // SlotHashes
+  vrfAuthority: PublicKey.default,
+};
+
+// TileData is 16 bytes: color u8, pending_attacks u8, resource_count u16, 4 bytes padding, attack_cooldown_until i64
+const TILE_DATA_LEN = 16;
+
+const GAME_STATUSES = ['Waiting', 'In Progress', 'Completed', 'Winner Found (Not Paid Out)', 'Cancelled'];
+
+export const getGameStatus = (gameState: number): string => GAME_STATUSES[gameState] ?? `Unknown (${gameState})`;
+
+const accountsCoder = new BorshAccountsCoder(IDL);
+
+// Decode a Game account. The board trails the Game struct (rows * columns tiles), so it isn't in the IDL.
+// Each seated slot is also flattened into the per-player fields the game UI reads (xpPlayer1, tileCountColor1, ...),
+// where slot i plays color i + 1
+export const parseGameAccount = (gamePubkey: PublicKey, accountData: Buffer): GameAccount => {
+  const account: any = accountsCoder.decode('Game', accountData);
+  const rows: number = account.rows;
+  const columns: number = account.columns;
+  const totalTiles = rows * columns;
+
+  const tiles = accountData.subarray(accountData.length - totalTiles * TILE_DATA_LEN);
+  const tileData: TileData[] = [];
+  for (let i = 0; i < totalTiles; i++) {
+    const offset = i * TILE_DATA_LEN;
+    tileData.push({ color: tiles.readUInt8(offset), resourceCount: tiles.readUInt16LE(offset + 2) });
+  }
+
+  const slots: any[] = account.players.slice(0, account.maxPlayers);
+  const tilesCovered = tileData.filter(tile => tile.color !== 0).length;
+
+  const gameData: GameAccount = {
+    publicKey: gamePubkey,
+    status: getGameStatus(account.gameState),
+    players: slots.map(slot => slot.wallet).filter((wallet: PublicKey) => !wallet.equals(PublicKey.default)),
+    tilesCovered,
+    totalTiles,
+    tilesCoveredPercent: totalTiles ? (tilesCovered / totalTiles) * 100 : 0,
+    cost: account.entryCost.toNumber() / 1e9, // Convert lamports to SOL
+    tileData,
+    rows,
+    columns,
+    resourcesPerMinute: account.resourcesPerMinute,
+    goldTierBonusXpPerMin: account.goldTierBonusXpPerMin,
+    silverTierBonusXpPerMin: account.silverTierBonusXpPerMin,
+    bronzeTierBonusXpPerMin: account.bronzeTierBonusXpPerMin,
+    ironTierBonusXpPerMin: account.ironTierBonusXpPerMin,
+    winningPlayerPubkey: account.winningPlayerPubkey,
+    winningXpLimit: account.winningXpLimit.toNumber(),
+  };
+
+  const gameDataAny = gameData as any;
+  gameDataAny.gameIdValue = BigInt(account.gameId.toString());
+  gameDataAny.maxPlayers = account.maxPlayers;
+  gameDataAny.joinDeadline = account.joinDeadline.toNumber();
+  gameDataAny.totalResourcesAvailable = account.totalResourcesAvailable;
+  gameDataAny.availableResourcesTimestamp = account.availableResourcesTimestamp.toNumber();
+  gameDataAny.xpPerMinutePerTile = account.xpPerMinutePerTile;
+  gameDataAny.gamePlayers = slots.map((slot, colorIndex) =>
+    slot.wallet.equals(PublicKey.default) ? null : { publicKey: slot.wallet.toString(), colorIndex }
+  );
+
+  // Same estimate as accrue_xp on chain, ignoring tier bonuses: whole minutes since the last update
+  const currentTime = Math.floor(Date.now() / 1000);
+  slots.forEach((slot, index) => {
+    const n = index + 1;
+    const xpTimestamp = slot.xpTimestamp.toNumber();
+    const [gold, silver, bronze, iron] = slot.tierTileCounts;
+    gameDataAny[`resourcesSpentPlayer${n}`] = slot.resourcesSpent;
+    gameDataAny[`xpPlayer${n}`] = slot.xp;
+    gameDataAny[`xpTimestampPlayer${n}`] = xpTimestamp;
+    gameDataAny[`tileCountColor${n}`] = slot.tileCount;
+    gameDataAny[`goldTileCountPlayer${n}`] = gold;
+    gameDataAny[`silverTileCountPlayer${n}`] = silver;
+    gameDataAny[`bronzeTileCountPlayer${n}`] = bronze;
+    gameDataAny[`ironTileCountPlayer${n}`] = iron;
+    const minutesElapsed = currentTime - xpTimestamp > 60 ? Math.floor((currentTime - xpTimestamp) / 60) : 0;
+    gameDataAny[`simulatedXpPlayer${n}`] = slot.xp + minutesElapsed * account.xpPerMinutePerTile * slot.tileCount;
+  });
+
+  return gameData;
+};
+
 interface WalletAdapter {
   publicKey: PublicKey | null;
   signTransaction: <T extends Transaction>(tx: T) => Promise<T>;
@@ -1451,62 +3441,25 @@ export class HexoneClient {
         this.program.programId
       );
 
-      // Get platform account data
-      const platformAccountInfo = await this.connection.getAccountInfo(platformPda);
-      if (!platformAccountInfo) {
-        throw new Error('Platform account not found');
-      }
-
-      // The game count is stored at the beginning of the account data
-      const gameCount = platformAccountInfo.data.readUInt32LE(0);
+      const platform = await this.program.account.platform.fetch(platformPda);
+      const gameCount = (platform as any).gameCount.toNumber();
 
-      // Fetch all games
+      // Fetch all games, newest first
       const games: GameAccount[] = [];
       for (let i = gameCount - 1; i >= 0; i--) {
+        const gameIdBuffer = Buffer.alloc(8);
+        gameIdBuffer.writeBigUInt64LE(BigInt(i), 0);
         const [gamePda] = await PublicKey.findProgramAddress(
-          [Buffer.from(`GAME-${i}`)],
+          [Buffer.from('GAME-'), gameIdBuffer],
           this.program.programId
         );
 
         try {
-          const gameAccount = await this.program.account.game.fetch(gamePda);
-          const account = gameAccount as any;
-          const tilesCovered = account.tileData.filter((tile: any) => tile !== 0).length;
-          const totalTiles = account.tileData.length;
-          const tilesCoveredPercent = (tilesCovered / totalTiles) * 100;
-
-          // Parse tile data if available
-          const tileData: TileData[] = [];
-          if (account.tileData && Array.isArray(account.tileData)) {
-            // If tileData is already parsed, use it; otherwise create placeholder
-            account.tileData.forEach((tile: any) => {
-              if (typeof tile === 'object' && tile.color !== undefined) {
-                tileData.push({ color: tile.color, resourceCount: tile.resourceCount || 0 });
-              } else {
-                // Placeholder for unparsed data
-                tileData.push({ color: tile || 0, resourceCount: 0 });
-              }
-            });
-          } else {
-            // Create empty tile data array
-            for (let i = 0; i < 144; i++) {
-              tileData.push({ color: 0, resourceCount: 0 });
-            }
+          const accountInfo = await this.connection.getAccountInfo(gamePda);
+          if (!accountInfo) {
+            continue;
           }
-
-          games.push({
-            publicKey: gamePda,
-            status: this.getGameStatus(account.gameState),
-            players: account.players || [],
-            tilesCovered,
-            totalTiles,
-            tilesCoveredPercent,
-            cost: account.gameCost ? account.gameCost / 1e9 : 0, // Convert lamports to SOL
-            tileData,
-            rows: account.rows || 11,
-            columns: account.columns || 13,
-            resourcesPerMinute: account.resourcesPerMinute || 0
-          });
+          games.push(parseGameAccount(gamePda, accountInfo.data));
         } catch (error) {
           console.error(`Error fetching game ${i}:`, error);
           // Continue with next game even if one fails
@@ -1521,303 +3474,46 @@ export class HexoneClient {
     }
   }
 
-  private fetchGameManually(gamePubkey: PublicKey, accountData: Buffer): GameAccount | null {
-    try {
-      // Skip 8 bytes for Anchor discriminator
-      const data = Buffer.from(accountData.slice(8));
-      
-      // Read pubkeys (32 bytes each)
-      const admin = new PublicKey(data.slice(0, 32));
-      const player1 = new PublicKey(data.slice(32, 64));
-      const player2 = new PublicKey(data.slice(64, 96));
-      const player3 = new PublicKey(data.slice(96, 128));
-      const player4 = new PublicKey(data.slice(128, 160));
-      
-      // Read game_id (8 bytes)
-      const gameIdValue = data.readBigUInt64LE(160);
-      
-      // Read available_resources_timestamp (i64, 8 bytes)
-      const availableResourcesTimestamp = Number(data.readBigInt64LE(168));
-      
-      // Read xp_timestamp_player1-4 (i64 each, 8 bytes each)
-      const xpTimestampPlayer1 = Number(data.readBigInt64LE(176));
-      const xpTimestampPlayer2 = Number(data.readBigInt64LE(184));
-      const xpTimestampPlayer3 = Number(data.readBigInt64LE(192));
-      const xpTimestampPlayer4 = Number(data.readBigInt64LE(200));
-      
-      // Read resources_per_minute (u32, 4 bytes)
-      const resourcesPerMinute = data.readUInt32LE(208);
-      
-      // Read total_resources_available (u32, 4 bytes)
-      const totalResourcesAvailable = data.readUInt32LE(212);
-      
-      // Read resources_spent_player1-4 (u32 each, 4 bytes each)
-      const resourcesSpentPlayer1 = data.readUInt32LE(216);
-      const resourcesSpentPlayer2 = data.readUInt32LE(220);
-      const resourcesSpentPlayer3 = data.readUInt32LE(224);
-      const resourcesSpentPlayer4 = data.readUInt32LE(228);
-      
-      // Read xp_per_minute_per_tile (u32, 4 bytes)
-      const xpPerMinutePerTile = data.readUInt32LE(232);
-      
-      // Read xp_player1-4 (u32 each, 4 bytes each)
-      const xpPlayer1 = data.readUInt32LE(236);
-      const xpPlayer2 = data.readUInt32LE(240);
-      const xpPlayer3 = data.readUInt32LE(244);
-      const xpPlayer4 = data.readUInt32LE(248);
-      
-      // Read tile_count_color1-4 (u32 each, 4 bytes each)
-      const tileCountColor1 = data.readUInt32LE(252);
-      const tileCountColor2 = data.readUInt32LE(256);
-      const tileCountColor3 = data.readUInt32LE(260);
-      const tileCountColor4 = data.readUInt32LE(264);
-      
-      // Read tile_data (144 * 4 bytes) - starts at offset 272 (after 4 bytes padding)
-      const tileData: TileData[] = [];
-      const tileDataStartOffset = 272;
-      const maxTiles = Math.min(144, Math.floor((data.length - tileDataStartOffset) / 4));
-      
-      for (let i = 0; i < maxTiles; i++) {
-        const offset = tileDataStartOffset + (i * 4);
-        if (offset + 4 <= data.length) {
-          const color = data.readUInt8(offset);
-          const resourceCount = data.readUInt16LE(offset + 2);
-          tileData.push({ color, resourceCount });
-        } else {
-          tileData.push({ color: 0, resourceCount: 0 });
-        }
-      }
-      
-      // Fill remaining tiles if needed
-      while (tileData.length < 144) {
-        tileData.push({ color: 0, resourceCount: 0 });
-      }
-      
-      // Read tier counts (16 u8s = 16 bytes) - starts after tileData
-      const tierCountsOffset = tileDataStartOffset + (144 * 4);
-      const ironTileCountPlayer1 = data.length > tierCountsOffset ? data.readUInt8(tierCountsOffset) : 0;
-      const bronzeTileCountPlayer1 = data.length > tierCountsOffset + 1 ? data.readUInt8(tierCountsOffset + 1) : 0;
-      const silverTileCountPlayer1 = data.length > tierCountsOffset + 2 ? data.readUInt8(tierCountsOffset + 2) : 0;
-      const goldTileCountPlayer1 = data.length > tierCountsOffset + 3 ? data.readUInt8(tierCountsOffset + 3) : 0;
-      const ironTileCountPlayer2 = data.length > tierCountsOffset + 4 ? data.readUInt8(tierCountsOffset + 4) : 0;
-      const bronzeTileCountPlayer2 = data.length > tierCountsOffset + 5 ? data.readUInt8(tierCountsOffset + 5) : 0;
-      const silverTileCountPlayer2 = data.length > tierCountsOffset + 6 ? data.readUInt8(tierCountsOffset + 6) : 0;
-      const goldTileCountPlayer2 = data.length > tierCountsOffset + 7 ? data.readUInt8(tierCountsOffset + 7) : 0;
-      const ironTileCountPlayer3 = data.length > tierCountsOffset + 8 ? data.readUInt8(tierCountsOffset + 8) : 0;
-      const bronzeTileCountPlayer3 = data.length > tierCountsOffset + 9 ? data.readUInt8(tierCountsOffset + 9) : 0;
-      const silverTileCountPlayer3 = data.length > tierCountsOffset + 10 ? data.readUInt8(tierCountsOffset + 10) : 0;
-      const goldTileCountPlayer3 = data.length > tierCountsOffset + 11 ? data.readUInt8(tierCountsOffset + 11) : 0;
-      const ironTileCountPlayer4 = data.length > tierCountsOffset + 12 ? data.readUInt8(tierCountsOffset + 12) : 0;
-      const bronzeTileCountPlayer4 = data.length > tierCountsOffset + 13 ? data.readUInt8(tierCountsOffset + 13) : 0;
-      const silverTileCountPlayer4 = data.length > tierCountsOffset + 14 ? data.readUInt8(tierCountsOffset + 14) : 0;
-      const goldTileCountPlayer4 = data.length > tierCountsOffset + 15 ? data.readUInt8(tierCountsOffset + 15) : 0;
-      
-      // Read tier bonus XP per minute (4 u8s = 4 bytes)
-      const tierBonusXpOffset = tierCountsOffset + 16;
-      const goldTierBonusXpPerMin = data.length > tierBonusXpOffset ? data.readUInt8(tierBonusXpOffset) : 100;
-      const silverTierBonusXpPerMin = data.length > tierBonusXpOffset + 1 ? data.readUInt8(tierBonusXpOffset + 1) : 50;
-      const bronzeTierBonusXpPerMin = data.length > tierBonusXpOffset + 2 ? data.readUInt8(tierBonusXpOffset + 2) : 10;
-      const ironTierBonusXpPerMin = data.length > tierBonusXpOffset + 3 ? data.readUInt8(tierBonusXpOffset + 3) : 5;
-      
-      // Skip padding (4 bytes) + winning_player_pubkey (32 bytes) + winning_xp_limit (8 bytes)
-      const paddingOffset = tierBonusXpOffset + 4;
-      const winningPlayerPubkeyOffset = paddingOffset + 4;
-      const winningXpLimitOffset = winningPlayerPubkeyOffset + 32;
-      
-      // Read winning player pubkey (32 bytes)
-      const winningPlayerPubkey = data.length > winningPlayerPubkeyOffset 
-        ? new PublicKey(data.slice(winningPlayerPubkeyOffset, winningPlayerPubkeyOffset + 32))
-        : PublicKey.default;
-      
-      // Read winning XP limit (u64, 8 bytes)
-      const winningXpLimit = data.length > winningXpLimitOffset 
-        ? Number(data.readBigUInt64LE(winningXpLimitOffset))
-        : 10000;
-      
-      // Read game state and other fields (6 bytes: game_state, rows, columns, version, bump, winner_calculation_flag)
-      const gameStateOffset = winningXpLimitOffset + 8;
-      let gameState = 0;
-      let rows = 11;
-      let columns = 13;
-      
-      if (gameStateOffset < data.length) {
-        gameState = data.readUInt8(gameStateOffset);
-      }
-      if (gameStateOffset + 1 < data.length) {
-        rows = data.readUInt8(gameStateOffset + 1);
-      }
-      if (gameStateOffset + 2 < data.length) {
-        columns = data.readUInt8(gameStateOffset + 2);
-      }
-      
-      const tilesCovered = tileData.filter(tile => tile.color !== 0).length;
-      const totalTiles = rows * columns || 144;
-      const tilesCoveredPercent = totalTiles > 0 ? (tilesCovered / totalTiles) * 100 : 0;
-      
-      return {
-        publicKey: gamePubkey,
-        status: this.getGameStatus(gameState),
-        players: [player1, player2, player3, player4].filter(pk => !pk.equals(PublicKey.default)),
-        tilesCovered,
-        totalTiles,
-        tilesCoveredPercent,
-        cost: 0,
-        tileData,
-        rows,
-        columns,
-        resourcesPerMinute,
-        ironTileCountPlayer1,
-        bronzeTileCountPlayer1,
-        silverTileCountPlayer1,
-        goldTileCountPlayer1,
-        ironTileCountPlayer2,
-        bronzeTileCountPlayer2,
-        silverTileCountPlayer2,
-        goldTileCountPlayer2,
-        ironTileCountPlayer3,
-        bronzeTileCountPlayer3,
-        silverTileCountPlayer3,
-        goldTileCountPlayer3,
-        ironTileCountPlayer4,
-        bronzeTileCountPlayer4,
-        silverTileCountPlayer4,
-        goldTileCountPlayer4,
-        goldTierBonusXpPerMin,
-        silverTierBonusXpPerMin,
-        bronzeTierBonusXpPerMin,
-        ironTierBonusXpPerMin,
-        winningPlayerPubkey: winningPlayerPubkey,
-        winningXpLimit: winningXpLimit
-      };
-    } catch (error) {
-      console.error('Error in manual game parsing:', error);
-      return null;
-    }
-  }
-
   async fetchGame(gameId: string): Promise<GameAccount | null> {
     try {
-      // First check account info to see actual data size
       const gamePubkey = new PublicKey(gameId);
       const accountInfo = await this.connection.getAccountInfo(gamePubkey);
       if (!accountInfo) {
         console.error('Game account not found');
         return null;
       }
-      
-      const expectedSize = 8 + 32*5 + 8*6 + 4*16 + 4 + 144*4 + 16 + 4 + 4 + 32 + 8 + 5 + 3; // 932 (added 20 bytes for tier counts/tier bonus XP + 40 bytes for winning player/XP limit)
-      const actualSize = accountInfo.data.length;
-      
-      console.log('Game account data length:', actualSize);
-      console.log('Expected size (with discriminator):', expectedSize);
-      
-      // Try to fetch using Anchor, with fallback to manual parsing
-      let account: any;
-      try {
-        const game = await this.program.account.game.fetch(gamePubkey);
-        account = game as any;
-      } catch (fetchError: any) {
-        console.warn('Failed to fetch game using Anchor deserialization, falling back to manual parsing:', fetchError?.message || fetchError);
-        // Fallback to manual parsing if Anchor deserialization fails
-        if (actualSize < expectedSize) {
-          console.error(`Account size mismatch! Expected ${expectedSize} bytes, got ${actualSize} bytes.`);
-        }
-        return this.fetchGameManually(gamePubkey, accountInfo.data);
-      }
-      
-      // Parse tile data if available
-      const tileData: TileData[] = [];
-      if (account.tileData && Array.isArray(account.tileData)) {
-        // If tileData is already parsed, use it; otherwise create placeholder
-        account.tileData.forEach((tile: any) => {
-          if (typeof tile === 'object' && tile.color !== undefined) {
-            tileData.push({ color: tile.color, resourceCount: tile.resourceCount || 0 });
-          } else {
-            // Placeholder for unparsed data
-            tileData.push({ color: tile || 0, resourceCount: 0 });
-          }
-        });
-      } else {
-        // Create empty tile data array
-        for (let i = 0; i < 144; i++) {
-          tileData.push({ color: 0, resourceCount: 0 });
-        }
-      }
 
-      // Calculate tiles covered from tileData array
-      const tilesCovered = tileData.filter((tile) => tile.color !== 0).length;
-      const totalTiles = tileData.length;
-      const tilesCoveredPercent = (tilesCovered / totalTiles) * 100;
-
-      return {
-        publicKey: new PublicKey(gameId),
-        status: this.getGameStatus(account.gameState),
-        players: [
-          account.admin,
-          account.player1,
-          account.player2,
-          account.player3,
-          account.player4
-        ].filter((player: PublicKey) => !player.equals(PublicKey.default)),
-        tilesCovered,
-        totalTiles,
-        tilesCoveredPercent,
-        cost: account.gameCost ? account.gameCost / 1e9 : 0, // Convert lamports to SOL
-        tileData,
-        rows: account.rows || 11,
-        columns: account.columns || 13,
-        resourcesPerMinute: account.resourcesPerMinute || 0,
-        // Tier counts for each player
-        ironTileCountPlayer1: account.ironTileCountPlayer1 ?? 0,
-        bronzeTileCountPlayer1: account.bronzeTileCountPlayer1 ?? 0,
-        silverTileCountPlayer1: account.silverTileCountPlayer1 ?? 0,
-        goldTileCountPlayer1: account.goldTileCountPlayer1 ?? 0,
-        ironTileCountPlayer2: account.ironTileCountPlayer2 ?? 0,
-        bronzeTileCountPlayer2: account.bronzeTileCountPlayer2 ?? 0,
-        silverTileCountPlayer2: account.silverTileCountPlayer2 ?? 0,
-        goldTileCountPlayer2: account.goldTileCountPlayer2 ?? 0,
-        ironTileCountPlayer3: account.ironTileCountPlayer3 ?? 0,
-        bronzeTileCountPlayer3: account.bronzeTileCountPlayer3 ?? 0,
-        silverTileCountPlayer3: account.silverTileCountPlayer3 ?? 0,
-        goldTileCountPlayer3: account.goldTileCountPlayer3 ?? 0,
-        ironTileCountPlayer4: account.ironTileCountPlayer4 ?? 0,
-        bronzeTileCountPlayer4: account.bronzeTileCountPlayer4 ?? 0,
-        silverTileCountPlayer4: account.silverTileCountPlayer4 ?? 0,
-        goldTileCountPlayer4: account.goldTileCountPlayer4 ?? 0,
-        // Tier bonus XP per minute
-        goldTierBonusXpPerMin: account.goldTierBonusXpPerMin ?? 100,
-        silverTierBonusXpPerMin: account.silverTierBonusXpPerMin ?? 50,
-        bronzeTierBonusXpPerMin: account.bronzeTierBonusXpPerMin ?? 10,
-        ironTierBonusXpPerMin: account.ironTierBonusXpPerMin ?? 5,
-        winningPlayerPubkey: account.winningPlayerPubkey ?? PublicKey.default,
-        winningXpLimit: account.winningXpLimit ?? 10000
-      };
+      return parseGameAccount(gamePubkey, accountInfo.data);
     } catch (error) {
       console.error('Error fetching game:', error);
       return null;
     }
   }
 
-  async createGame(): Promise<string> {
+  // Games are created from the platform's game count, so the new game's PDA is read from the platform first
+  async createGame(config: GameConfig): Promise<string> {
     if (!this.provider?.wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
 
     try {
-      const [gamePda] = await PublicKey.findProgramAddress(
-        [Buffer.from('game')],
+      const [platformPda] = await PublicKey.findProgramAddress(
+        [Buffer.from('platform')],
         this.program.programId
       );
+      const gamePda = await this.getNextGamePda(platformPda);
 
       const tx = await this.program.methods
-        .createGame()
+        .createGame(config)
         .accounts({
-          game: gamePda,
           admin: this.provider.wallet.publicKey,
+          platform: platformPda,
+          game: gamePda,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
 
+      console.log('Create game transaction:', tx);
       return gamePda.toBase58();
     } catch (error) {
       console.error('Error creating game:', error);
@@ -1825,6 +3521,18 @@ export class HexoneClient {
     }
   }
 
+  // PDA of the game the platform will create next
+  async getNextGamePda(platformPda: PublicKey): Promise<PublicKey> {
+    const platform = await this.program.account.platform.fetch(platformPda);
+    const gameIdBuffer = Buffer.alloc(8);
+    gameIdBuffer.writeBigUInt64LE(BigInt((platform as any).gameCount.toString()), 0);
+    const [gamePda] = await PublicKey.findProgramAddress(
+      [Buffer.from('GAME-'), gameIdBuffer],
+      this.program.programId
+    );
+    return gamePda;
+  }
+
   async moveResources(
     game: PublicKey,
     sourceTileIndex: number,
@@ -2005,8 +3713,7 @@ export class HexoneClient {
 
   async resolveAttack(
     game: PublicKey,
-    defenderTileIndex: number,
-    destination: PublicKey
+    defenderTileIndex: number
   ): Promise<string> {
     if (!this.provider?.wallet.publicKey) {
       throw new Error('Wallet not connected');
@@ -2019,7 +3726,7 @@ export class HexoneClient {
         throw new Error('Player account not found');
       }
 
-      const { playerPda, hotwallet } = playerInfo;
+      const { hotwallet } = playerInfo;
       const playerWallet = this.provider.wallet.publicKey;
       
       // Determine signer: use hotwallet if available and not default, otherwise use wallet
@@ -2042,6 +3749,9 @@ export class HexoneClient {
         this.programId
       );
 
+      // The defender account's rent goes back to whoever started the attack
+      const defender = await this.program.account.defender.fetch(defenderPda);
+
       console.log('Calling resolveAttack with:', {
         defenderTileIndex,
         usingHotwallet: useHotwallet,
@@ -2052,12 +3762,11 @@ export class HexoneClient {
       const instruction = await this.program.methods
         .resolveAttack()
         .accounts({
-          playerWallet: playerWallet,
-          signerWallet: signerWallet,
-          player: playerPda,
+          caller: signerWallet,
           game: game,
           defender: defenderPda,
-          destination: destination,
+          payer: (defender as any).payer,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
         })
         .instruction();
 
@@ -2089,7 +3798,7 @@ export class HexoneClient {
     }
   }
 
-  async claimPrize(game: PublicKey): Promise<string> {
+  async claimPayout(game: PublicKey): Promise<string> {
     if (!this.provider?.wallet.publicKey) {
       throw new Error('Wallet not connected');
     }
@@ -2103,27 +3812,37 @@ export class HexoneClient {
 
       const { playerPda } = playerInfo;
 
-      // Find game treasury PDA
+      // Find game treasury, platform and fee vault PDAs
       const [treasuryPda] = await PublicKey.findProgramAddress(
         [Buffer.from('game_treasury'), game.toBuffer()],
         this.programId
       );
+      const [platformPda] = await PublicKey.findProgramAddress(
+        [Buffer.from('platform')],
+        this.programId
+      );
+      const [feeVaultPda] = await PublicKey.findProgramAddress(
+        [Buffer.from('fee_vault')],
+        this.programId
+      );
 
       const tx = await this.program.methods
-        .claimPrize()
+        .claimPayout()
         .accounts({
           wallet: this.provider.wallet.publicKey,
           game: game,
           gameTreasury: treasuryPda,
           player: playerPda,
+          platform: platformPda,
+          feeVault: feeVaultPda,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
 
-      console.log('Claim prize transaction:', tx);
+      console.log('Claim payout transaction:', tx);
       return tx;
     } catch (error) {
-      console.error('Error in claimPrize:', error);
+      console.error('Error in claimPayout:', error);
       throw error;
     }
   }
@@ -2183,19 +3902,4 @@ export class HexoneClient {
       return null;
     }
   }
-
-  private getGameStatus(gameState: number): string {
-    switch (gameState) {
-      case 0:
-        return 'Waiting';
-      case 1:
-        return 'In Progress';
-      case 2:
-        return 'Completed';
-      case 3:
-        return 'Winner Found (Not Paid Out)';
-      default:
-        return 'Unknown';
-    }
-  }
 }
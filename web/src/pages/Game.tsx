@@ -5,7 +5,7 @@ import { WalletMultiButton } from '@solana/wallet-adapter-react-ui';
 import { PublicKey, SystemProgram, Keypair, Transaction } from '@solana/web3.js';
 import { BN } from '@coral-xyz/anchor';
 import { Buffer } from 'buffer';
-import { HexoneClient, GameAccount, PROGRAM_ID } from '../lib/hexone';
+import { HexoneClient, GameAccount, PROGRAM_ID, parseGameAccount } from '../lib/hexone';
 import { EventParser } from '@coral-xyz/anchor';
 import Phaser from 'phaser';
 import { MainScene } from '../components/game/MainScene';
@@ -141,7 +141,7 @@ const Game: React.FC = () => {
         console.log('Game account changed, updating tiles only...');
         
         // Parse the new game data
-        const newGameData = parseGameAccountData(gamePda, accountInfo.data);
+        const newGameData = parseGameAccount(gamePda, accountInfo.data);
         
         // Calculate simulated resources (same logic as fetchGame)
         const gameDataAny = newGameData as any;
@@ -368,8 +368,8 @@ const Game: React.FC = () => {
 
     try {
       setError(null);
-      const tx = await client.claimPrize(game.publicKey);
-      console.log('Claim prize transaction:', tx);
+      const tx = await client.claimPayout(game.publicKey);
+      console.log('Claim payout transaction:', tx);
       
       // Refresh game data after claiming
       setTimeout(() => {
@@ -518,246 +518,6 @@ const Game: React.FC = () => {
     }
   };
 
-  // Parse game account data from buffer
-  const parseGameAccountData = (gamePda: PublicKey, accountData: Buffer): GameAccount => {
-      // Skip 8 bytes for Anchor discriminator
-    const data: Buffer = Buffer.from(accountData.slice(8));
-      console.log('Data after discriminator length:', data.length);
-      
-      // Read pubkeys (32 bytes each)
-      const admin = new PublicKey(data.slice(0, 32));
-      const player1 = new PublicKey(data.slice(32, 64));
-      const player2 = new PublicKey(data.slice(64, 96));
-      const player3 = new PublicKey(data.slice(96, 128));
-      const player4 = new PublicKey(data.slice(128, 160));
-      
-      // Read game_id (8 bytes)
-      const gameIdValue: bigint = data.readBigUInt64LE(160);
-      
-      // Read available_resources_timestamp (i64, 8 bytes)
-      const availableResourcesTimestamp = Number(data.readBigInt64LE(168));
-      
-      // Read xp_timestamp_player1-4 (i64 each, 8 bytes each)
-      const xpTimestampPlayer1 = Number(data.readBigInt64LE(176));
-      const xpTimestampPlayer2 = Number(data.readBigInt64LE(184));
-      const xpTimestampPlayer3 = Number(data.readBigInt64LE(192));
-      const xpTimestampPlayer4 = Number(data.readBigInt64LE(200));
-      
-      // Read resources_per_minute (u32, 4 bytes)
-      const resourcesPerMinute = data.readUInt32LE(208);
-      
-      // Read total_resources_available (u32, 4 bytes)
-      const totalResourcesAvailable = data.readUInt32LE(212);
-      
-      // Read resources_spent_player1-4 (u32 each, 4 bytes each)
-      const resourcesSpentPlayer1 = data.readUInt32LE(216);
-      const resourcesSpentPlayer2 = data.readUInt32LE(220);
-      const resourcesSpentPlayer3 = data.readUInt32LE(224);
-      const resourcesSpentPlayer4 = data.readUInt32LE(228);
-      
-      // Read xp_per_minute_per_tile (u32, 4 bytes)
-      const xpPerMinutePerTile = data.readUInt32LE(232);
-      
-      // Read xp_player1-4 (u32 each, 4 bytes each)
-      const xpPlayer1 = data.readUInt32LE(236);
-      const xpPlayer2 = data.readUInt32LE(240);
-      const xpPlayer3 = data.readUInt32LE(244);
-      const xpPlayer4 = data.readUInt32LE(248);
-      
-      // Read tile_count_color1-4 (u32 each, 4 bytes each)
-      const tileCountColor1 = data.readUInt32LE(252);
-      const tileCountColor2 = data.readUInt32LE(256);
-      const tileCountColor3 = data.readUInt32LE(260);
-      const tileCountColor4 = data.readUInt32LE(264);
-      
-      // Skip _padding_u32 (4 bytes)
-      
-      // Read tile_data (144 * 4 bytes) - starts at offset 272
-      // Each TileData is: color (u8) + _pad (u8) + resource_count (u16) = 4 bytes
-      const tileData: Array<{ color: number; resourceCount: number }> = [];
-      for (let i = 0; i < 144; i++) {
-          const offset = 272 + (i * 4);
-          const color = data.readUInt8(offset);
-          const resourceCount = data.readUInt16LE(offset + 2);
-          tileData.push({ color, resourceCount });
-      }
-      
-      // Read tier counts (16 u8s = 16 bytes) - starts after tileData
-      const tierCountsOffset = 272 + (144 * 4);
-      const ironTileCountPlayer1 = data.length > tierCountsOffset ? data.readUInt8(tierCountsOffset) : 0;
-      const bronzeTileCountPlayer1 = data.length > tierCountsOffset + 1 ? data.readUInt8(tierCountsOffset + 1) : 0;
-      const silverTileCountPlayer1 = data.length > tierCountsOffset + 2 ? data.readUInt8(tierCountsOffset + 2) : 0;
-      const goldTileCountPlayer1 = data.length > tierCountsOffset + 3 ? data.readUInt8(tierCountsOffset + 3) : 0;
-      const ironTileCountPlayer2 = data.length > tierCountsOffset + 4 ? data.readUInt8(tierCountsOffset + 4) : 0;
-      const bronzeTileCountPlayer2 = data.length > tierCountsOffset + 5 ? data.readUInt8(tierCountsOffset + 5) : 0;
-      const silverTileCountPlayer2 = data.length > tierCountsOffset + 6 ? data.readUInt8(tierCountsOffset + 6) : 0;
-      const goldTileCountPlayer2 = data.length > tierCountsOffset + 7 ? data.readUInt8(tierCountsOffset + 7) : 0;
-      const ironTileCountPlayer3 = data.length > tierCountsOffset + 8 ? data.readUInt8(tierCountsOffset + 8) : 0;
-      const bronzeTileCountPlayer3 = data.length > tierCountsOffset + 9 ? data.readUInt8(tierCountsOffset + 9) : 0;
-      const silverTileCountPlayer3 = data.length > tierCountsOffset + 10 ? data.readUInt8(tierCountsOffset + 10) : 0;
-      const goldTileCountPlayer3 = data.length > tierCountsOffset + 11 ? data.readUInt8(tierCountsOffset + 11) : 0;
-      const ironTileCountPlayer4 = data.length > tierCountsOffset + 12 ? data.readUInt8(tierCountsOffset + 12) : 0;
-      const bronzeTileCountPlayer4 = data.length > tierCountsOffset + 13 ? data.readUInt8(tierCountsOffset + 13) : 0;
-      const silverTileCountPlayer4 = data.length > tierCountsOffset + 14 ? data.readUInt8(tierCountsOffset + 14) : 0;
-      const goldTileCountPlayer4 = data.length > tierCountsOffset + 15 ? data.readUInt8(tierCountsOffset + 15) : 0;
-      
-      // Read tier bonus XP per minute (4 u8s = 4 bytes)
-      const tierBonusXpOffset = tierCountsOffset + 16;
-      const goldTierBonusXpPerMin = data.length > tierBonusXpOffset ? data.readUInt8(tierBonusXpOffset) : 100;
-      const silverTierBonusXpPerMin = data.length > tierBonusXpOffset + 1 ? data.readUInt8(tierBonusXpOffset + 1) : 50;
-      const bronzeTierBonusXpPerMin = data.length > tierBonusXpOffset + 2 ? data.readUInt8(tierBonusXpOffset + 2) : 10;
-      const ironTierBonusXpPerMin = data.length > tierBonusXpOffset + 3 ? data.readUInt8(tierBonusXpOffset + 3) : 5;
-      
-      // Skip padding (4 bytes) + winning_player_pubkey (32 bytes) + winning_xp_limit (8 bytes)
-      const paddingOffset = tierBonusXpOffset + 4;
-      const winningPlayerPubkeyOffset = paddingOffset + 4;
-      const winningXpLimitOffset = winningPlayerPubkeyOffset + 32;
-      
-      // Read winning player pubkey (32 bytes)
-      const winningPlayerPubkey = data.length > winningPlayerPubkeyOffset 
-        ? new PublicKey(data.slice(winningPlayerPubkeyOffset, winningPlayerPubkeyOffset + 32))
-        : PublicKey.default;
-      
-      // Read winning XP limit (u64, 8 bytes)
-      const winningXpLimit = data.length > winningXpLimitOffset 
-        ? Number(data.readBigUInt64LE(winningXpLimitOffset))
-        : 10000;
-      
-      // Read game state and other fields (6 bytes: game_state, rows, columns, version, bump, winner_calculation_flag)
-      const gameStateOffset = winningXpLimitOffset + 8;
-      const gameState = data.length > gameStateOffset ? data.readUInt8(gameStateOffset) : 0;
-      const rows = data.length > gameStateOffset + 1 ? data.readUInt8(gameStateOffset + 1) : 11;
-      const columns = data.length > gameStateOffset + 2 ? data.readUInt8(gameStateOffset + 2) : 13;
-      
-      // Log tier counts for debugging
-      console.log('Tier counts read from account:', {
-        player1: { gold: goldTileCountPlayer1, silver: silverTileCountPlayer1, bronze: bronzeTileCountPlayer1, iron: ironTileCountPlayer1 },
-        player2: { gold: goldTileCountPlayer2, silver: silverTileCountPlayer2, bronze: bronzeTileCountPlayer2, iron: ironTileCountPlayer2 },
-        player3: { gold: goldTileCountPlayer3, silver: silverTileCountPlayer3, bronze: bronzeTileCountPlayer3, iron: ironTileCountPlayer3 },
-        player4: { gold: goldTileCountPlayer4, silver: silverTileCountPlayer4, bronze: bronzeTileCountPlayer4, iron: ironTileCountPlayer4 }
-      });
-      
-      // Map game state to string representation
-      let gameStateStr = 'Unknown';
-      switch (gameState) {
-        case 0:
-          gameStateStr = 'Waiting';
-          break;
-        case 1:
-          gameStateStr = 'In Progress';
-          break;
-        case 2:
-          gameStateStr = 'Completed';
-          break;
-        case 3:
-          gameStateStr = 'Winner Found (Not Paid Out)';
-          break;
-        default:
-          gameStateStr = `Unknown (${gameState})`;
-      }
-      
-      const tilesCovered = tileData.filter(tile => tile.color !== 0).length;
-
-      const gameData: GameAccount = {
-        publicKey: gamePda,
-        status: gameStateStr,
-        players: [player1, player2, player3, player4].filter(pk => !pk.equals(PublicKey.default)),
-        tilesCovered,
-        totalTiles: rows * columns || 0,
-        tilesCoveredPercent: rows * columns ? (tilesCovered / (rows * columns)) * 100 : 0,
-        cost: 0, // Cost is not stored in the game account
-        tileData,
-        rows,
-        columns,
-        resourcesPerMinute
-      };
-
-      // Map players to their color indices based on position
-      // player1 = Red (0), player2 = Yellow (1), player3 = Green (2), player4 = Blue (3)
-      const gamePlayers = [
-        !player1.equals(PublicKey.default) ? { publicKey: player1.toString(), colorIndex: 0 } : null,
-        !player2.equals(PublicKey.default) ? { publicKey: player2.toString(), colorIndex: 1 } : null,
-        !player3.equals(PublicKey.default) ? { publicKey: player3.toString(), colorIndex: 2 } : null,
-        !player4.equals(PublicKey.default) ? { publicKey: player4.toString(), colorIndex: 3 } : null,
-      ];
-      
-      // Store game players for UI
-      (gameData as any).gamePlayers = gamePlayers;
-
-      // Store resource data
-      (gameData as any).totalResourcesAvailable = totalResourcesAvailable;
-      (gameData as any).resourcesSpentPlayer1 = resourcesSpentPlayer1;
-      (gameData as any).resourcesSpentPlayer2 = resourcesSpentPlayer2;
-      (gameData as any).resourcesSpentPlayer3 = resourcesSpentPlayer3;
-      (gameData as any).resourcesSpentPlayer4 = resourcesSpentPlayer4;
-      (gameData as any).availableResourcesTimestamp = availableResourcesTimestamp;
-      
-      // Store XP data
-      (gameData as any).xpPlayer1 = xpPlayer1;
-      (gameData as any).xpPlayer2 = xpPlayer2;
-      (gameData as any).xpPlayer3 = xpPlayer3;
-      (gameData as any).xpPlayer4 = xpPlayer4;
-      (gameData as any).xpTimestampPlayer1 = xpTimestampPlayer1;
-      (gameData as any).xpTimestampPlayer2 = xpTimestampPlayer2;
-      (gameData as any).xpTimestampPlayer3 = xpTimestampPlayer3;
-      (gameData as any).xpTimestampPlayer4 = xpTimestampPlayer4;
-      (gameData as any).xpPerMinutePerTile = xpPerMinutePerTile;
-      (gameData as any).tileCountColor1 = tileCountColor1;
-      (gameData as any).tileCountColor2 = tileCountColor2;
-      (gameData as any).tileCountColor3 = tileCountColor3;
-      (gameData as any).tileCountColor4 = tileCountColor4;
-      
-      // Store tier counts
-      (gameData as any).ironTileCountPlayer1 = ironTileCountPlayer1;
-      (gameData as any).bronzeTileCountPlayer1 = bronzeTileCountPlayer1;
-      (gameData as any).silverTileCountPlayer1 = silverTileCountPlayer1;
-      (gameData as any).goldTileCountPlayer1 = goldTileCountPlayer1;
-      (gameData as any).ironTileCountPlayer2 = ironTileCountPlayer2;
-      (gameData as any).bronzeTileCountPlayer2 = bronzeTileCountPlayer2;
-      (gameData as any).silverTileCountPlayer2 = silverTileCountPlayer2;
-      (gameData as any).goldTileCountPlayer2 = goldTileCountPlayer2;
-      (gameData as any).ironTileCountPlayer3 = ironTileCountPlayer3;
-      (gameData as any).bronzeTileCountPlayer3 = bronzeTileCountPlayer3;
-      (gameData as any).silverTileCountPlayer3 = silverTileCountPlayer3;
-      (gameData as any).goldTileCountPlayer3 = goldTileCountPlayer3;
-      (gameData as any).ironTileCountPlayer4 = ironTileCountPlayer4;
-      (gameData as any).bronzeTileCountPlayer4 = bronzeTileCountPlayer4;
-      (gameData as any).silverTileCountPlayer4 = silverTileCountPlayer4;
-      (gameData as any).goldTileCountPlayer4 = goldTileCountPlayer4;
-      
-      // Store tier bonus XP per minute
-      (gameData as any).goldTierBonusXpPerMin = goldTierBonusXpPerMin;
-      (gameData as any).silverTierBonusXpPerMin = silverTierBonusXpPerMin;
-      (gameData as any).bronzeTierBonusXpPerMin = bronzeTierBonusXpPerMin;
-      (gameData as any).ironTierBonusXpPerMin = ironTierBonusXpPerMin;
-      
-      // Store winning player and XP limit
-      (gameData as any).winningPlayerPubkey = winningPlayerPubkey;
-      (gameData as any).winningXpLimit = winningXpLimit;
-      
-      // Calculate simulated XP for each player
-      const calculateSimulatedXP = (savedXP: number, timestamp: number, tileCount: number) => {
-        const currentTime = Math.floor(Date.now() / 1000); // Current time in seconds
-        const timeDiff = currentTime - timestamp;
-        if (timeDiff > 60) {
-          const minutesElapsed = Math.floor(timeDiff / 60);
-          const xpGained = minutesElapsed * xpPerMinutePerTile * tileCount;
-          return savedXP + xpGained;
-        }
-        return savedXP;
-      };
-      
-      (gameData as any).simulatedXpPlayer1 = calculateSimulatedXP(xpPlayer1, xpTimestampPlayer1, tileCountColor1);
-      (gameData as any).simulatedXpPlayer2 = calculateSimulatedXP(xpPlayer2, xpTimestampPlayer2, tileCountColor2);
-      (gameData as any).simulatedXpPlayer3 = calculateSimulatedXP(xpPlayer3, xpTimestampPlayer3, tileCountColor3);
-      (gameData as any).simulatedXpPlayer4 = calculateSimulatedXP(xpPlayer4, xpTimestampPlayer4, tileCountColor4);
-
-      // Store game ID value in gameData for later use
-      (gameData as any).gameIdValue = gameIdValue;
-
-      return gameData;
-    };
-
   const fetchGame = async (skipLoading = false) => {
     if (!gameId) return;
     try {
@@ -778,7 +538,7 @@ const Game: React.FC = () => {
       console.log('Game account data length:', gameAccountInfo.data.length);
       
       // Parse the game data
-      const gameData = parseGameAccountData(gamePda, gameAccountInfo.data);
+      const gameData = parseGameAccount(gamePda, gameAccountInfo.data);
 
       // Calculate simulated total resources based on timestamp
       const gameDataAny = gameData as any;
@@ -893,11 +653,6 @@ const Game: React.FC = () => {
         client.getProgram().programId
       );
 
-      const [platformPda] = await PublicKey.findProgramAddress(
-        [Buffer.from('platform')],
-        client.getProgram().programId
-      );
-
       const [gameTreasuryPda] = await PublicKey.findProgramAddress(
         [Buffer.from('game_treasury'), game.publicKey.toBuffer()],
         client.getProgram().programId
@@ -912,7 +667,6 @@ const Game: React.FC = () => {
         .accounts({
           wallet: wallet.publicKey,
           player: playerPda,
-          platform: platformPda,
           game: game.publicKey,
           gameTreasury: gameTreasuryPda,
           systemProgram: SystemProgram.programId,
@@ -1666,41 +1420,10 @@ const Game: React.FC = () => {
             }
 
             try {
-              // Determine destination for rent: use hotwallet if it's being used as signer, otherwise use main wallet
-              let destinationWallet = wallet.publicKey;
-              
-              try {
-                // Get player account to check hotwallet
-                const [playerPda] = await PublicKey.findProgramAddress(
-                  [Buffer.from('player'), wallet.publicKey.toBuffer()],
-                  client.getProgram().programId
-                );
-                const playerAccount = await client.getProgram().account.player.fetch(playerPda);
-                const hotwallet = (playerAccount as any).hotwallet as PublicKey;
-                const isDefaultHotwallet = hotwallet.equals(PublicKey.default);
-                
-                // Check if hotwallet keypair exists and matches
-                const storageKey = `hexone_hotwallet_${wallet.publicKey.toString()}`;
-                const stored = localStorage.getItem(storageKey);
-                if (stored && !isDefaultHotwallet) {
-                  const hotwalletKeypair = Keypair.fromSecretKey(
-                    Uint8Array.from(JSON.parse(stored))
-                  );
-                  // If hotwallet is being used (matches the account's hotwallet), use it as destination
-                  if (hotwalletKeypair.publicKey.equals(hotwallet)) {
-                    destinationWallet = hotwallet;
-                    console.log('[RESOLVE ATTACK] Using hotwallet as destination for rent:', hotwallet.toString());
-                  }
-                }
-              } catch (err) {
-                console.warn('Could not fetch player account for hotwallet check, using main wallet:', err);
-              }
-              
-              // Resolve attack - use hotwallet as destination if it's being used, otherwise use main wallet
+              // Resolve attack - the defender account's rent goes back to whoever started it
               const tx = await client.resolveAttack(
                 game.publicKey,
-                attackData.defenderTileIndex,
-                destinationWallet
+                attackData.defenderTileIndex
               );
 
               // Wait for transaction confirmation
@@ -4,7 +4,7 @@ import { useConnection, useWallet } from '@solana/wallet-adapter-react';
 import { WalletMultiButton } from '@solana/wallet-adapter-react-ui';
 import { PublicKey, SystemProgram, Transaction, Keypair } from '@solana/web3.js';
 import { BN } from '@coral-xyz/anchor';
-import { HexoneClient, GameAccount, PROGRAM_ID } from '../lib/hexone';
+import { HexoneClient, GameAccount, PROGRAM_ID, DEFAULT_GAME_CONFIG, parseGameAccount } from '../lib/hexone';
 import { Buffer } from 'buffer';
 import { useNetwork } from '../contexts/NetworkContext';
 import { RpcSettingsPopup } from '../components/RpcSettingsPopup';
@@ -384,122 +384,10 @@ const Games: React.FC = () => {
             continue; // Skip if game doesn't exist
           }
 
-          // Skip 8 bytes for Anchor discriminator
-          const data = gameAccountInfo.data.slice(8);
-          
-          // Read pubkeys (32 bytes each)
-          const admin = new PublicKey(data.slice(0, 32));
-          const player1 = new PublicKey(data.slice(32, 64));
-          const player2 = new PublicKey(data.slice(64, 96));
-          const player3 = new PublicKey(data.slice(96, 128));
-          const player4 = new PublicKey(data.slice(128, 160));
-          
-          // Read game_id (8 bytes)
-          const gameId = data.readBigUInt64LE(160);
-          
-          // Read available_resources_timestamp (i64, 8 bytes)
-          const availableResourcesTimestamp = Number(data.readBigInt64LE(168));
-          
-          // Read xp_timestamp_player1-4 (i64 each, 8 bytes each)
-          const xpTimestampPlayer1 = Number(data.readBigInt64LE(176));
-          const xpTimestampPlayer2 = Number(data.readBigInt64LE(184));
-          const xpTimestampPlayer3 = Number(data.readBigInt64LE(192));
-          const xpTimestampPlayer4 = Number(data.readBigInt64LE(200));
-          
-          // Read resources_per_minute (u32, 4 bytes)
-          const resourcesPerMinute = data.readUInt32LE(208);
-          
-          // Read total_resources_available (u32, 4 bytes)
-          const totalResourcesAvailable = data.readUInt32LE(212);
-          
-          // Read resources_spent_player1-4 (u32 each, 4 bytes each)
-          const resourcesSpentPlayer1 = data.readUInt32LE(216);
-          const resourcesSpentPlayer2 = data.readUInt32LE(220);
-          const resourcesSpentPlayer3 = data.readUInt32LE(224);
-          const resourcesSpentPlayer4 = data.readUInt32LE(228);
-          
-          // Read xp_per_minute_per_tile (u32, 4 bytes)
-          const xpPerMinutePerTile = data.readUInt32LE(232);
-          
-          // Read xp_player1-4 (u32 each, 4 bytes each)
-          const xpPlayer1 = data.readUInt32LE(236);
-          const xpPlayer2 = data.readUInt32LE(240);
-          const xpPlayer3 = data.readUInt32LE(244);
-          const xpPlayer4 = data.readUInt32LE(248);
-          
-          // Read tile_count_color1-4 (u32 each, 4 bytes each)
-          const tileCountColor1 = data.readUInt32LE(252);
-          const tileCountColor2 = data.readUInt32LE(256);
-          const tileCountColor3 = data.readUInt32LE(260);
-          const tileCountColor4 = data.readUInt32LE(264);
-          
-          // Skip _padding_u32 (4 bytes)
-          
-          // Read tile_data (144 * 4 bytes) - starts at offset 272
-          // Each TileData is: color (u8) + _pad (u8) + resource_count (u16) = 4 bytes
-          const parsedTileData: Array<{ color: number; resourceCount: number }> = [];
-          for (let j = 0; j < 144; j++) {
-            const offset = 272 + (j * 4);
-            const color = data.readUInt8(offset);
-            const resourceCount = data.readUInt16LE(offset + 2);
-            parsedTileData.push({ color, resourceCount });
-          }
-          
-          // Read tier counts (16 u8s = 16 bytes) - starts after tileData
-          const tierCountsOffset = 272 + (144 * 4);
-          
-          // Read tier bonus XP per minute (4 u8s = 4 bytes)
-          const tierBonusXpOffset = tierCountsOffset + 16;
-          
-          // Skip padding (4 bytes) + winning_player_pubkey (32 bytes) + winning_xp_limit (8 bytes)
-          const paddingOffset = tierBonusXpOffset + 4;
-          const winningPlayerPubkeyOffset = paddingOffset + 4;
-          const winningXpLimitOffset = winningPlayerPubkeyOffset + 32;
-          
-          // Read game state and other fields - game_state is at winningXpLimitOffset + 8
-          const gameStateOffset = winningXpLimitOffset + 8;
-          const gameState = data.length > gameStateOffset ? data.readUInt8(gameStateOffset) : 0;
-          const rows = data.length > gameStateOffset + 1 ? data.readUInt8(gameStateOffset + 1) : 11;
-          const columns = data.length > gameStateOffset + 2 ? data.readUInt8(gameStateOffset + 2) : 13;
-          
-          // Map game state to string representation
-          let gameStateStr = 'Unknown';
-          switch (gameState) {
-            case 0:
-              gameStateStr = 'Waiting';
-              break;
-            case 1:
-              gameStateStr = 'In Progress';
-              break;
-            case 2:
-              gameStateStr = 'Completed';
-              break;
-            case 3:
-              gameStateStr = 'Winner Found (Not Paid Out)';
-              break;
-            default:
-              gameStateStr = `Unknown (${gameState})`;
-          }
+          const game = parseGameAccount(gamePda, gameAccountInfo.data);
 
-          const tilesCovered = parsedTileData.filter(tile => tile.color !== 0).length;
-          const totalTiles = 137; // Fixed total tiles for the game board
-
-          const game: GameAccount = {
-            publicKey: gamePda,
-            status: gameStateStr,
-            players: [player1, player2, player3, player4].filter(pk => !pk.equals(PublicKey.default)),
-            tilesCovered,
-            totalTiles,
-            tilesCoveredPercent: totalTiles ? (tilesCovered / totalTiles) * 100 : 0,
-            cost: 0, // Cost is not stored in the game account
-            tileData: parsedTileData,
-            rows,
-            columns,
-            resourcesPerMinute
-          };
-          
           // Store game ID for display
-          (game as any).gameId = Number(gameId);
+          (game as any).gameId = Number((game as any).gameIdValue);
 
           allGames.push(game);
           console.log(`Successfully fetched game ${i}`);
@@ -733,12 +621,12 @@ const Games: React.FC = () => {
 
       // Build both instructions in a single transaction using client
       const createGameIx = await client.getProgram().methods
-        .createGame()
+        .createGame(DEFAULT_GAME_CONFIG)
         .accounts({
           admin: wallet.publicKey,
           platform: platformPda,
           game: gamePda,
-          system_program: SystemProgram.programId,
+          systemProgram: SystemProgram.programId,
         })
         .instruction();
 
@@ -750,7 +638,6 @@ const Games: React.FC = () => {
         .accounts({
           wallet: wallet.publicKey,
           player: playerPda,
-          platform: platformPda,
           game: gamePda,
           gameTreasury: gameTreasuryPda,
           systemProgram: SystemProgram.programId,
@@ -1185,7 +1072,8 @@ const Games: React.FC = () => {
                         <span style={{ 
                           color: game.status === 'In Progress' ? '#00ff00' : 
                                  game.status === 'Completed' ? '#ffa500' :
-                                 game.status === 'Winner Found (Not Paid Out)' ? '#ff8800' : '#888'
+                                 game.status === 'Winner Found (Not Paid Out)' ? '#ff8800' :
+                                 game.status === 'Cancelled' ? '#ff4444' : '#888'
                         }}>
                           {game.status}
                         </span>